@@ -0,0 +1,339 @@
+//! Parsing of `#[template(…)]` attributes
+//!
+//! The arguments are not parsed as [`syn::Meta`], because it cannot represent values like
+//! `concat!(…)`. Keys that askama-enum does not interpret are forwarded verbatim to askama.
+
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{token, Token};
+
+/// A `#[template(…)]` attribute
+#[derive(Clone)]
+pub(crate) struct TemplateArgs {
+    pub(crate) path: syn::Path,
+    pub(crate) args: Punctuated<TemplateArg, Token![,]>,
+}
+
+/// A single argument in a `#[template(…)]` attribute
+#[derive(Clone)]
+pub(crate) enum TemplateArg {
+    /// `key = value`
+    NameValue {
+        key: syn::Ident,
+        eq_token: Token![=],
+        value: Box<syn::Expr>,
+    },
+    /// `key(…)`
+    List {
+        key: syn::Ident,
+        paren_token: token::Paren,
+        tokens: TokenStream,
+    },
+    /// `key`
+    Path(syn::Ident),
+}
+
+/// A list `key(…)` that was taken out of a `#[template(…)]` attribute
+pub(crate) struct TemplateList {
+    pub(crate) key: syn::Ident,
+    pub(crate) tokens: TokenStream,
+}
+
+impl TemplateArgs {
+    /// The path is always `template`, even if the attribute was spelled `#[enum_template_attr]`
+    pub(crate) fn from_attr(attr: &syn::Attribute) -> syn::Result<Self> {
+        Ok(Self {
+            path: syn::Ident::new("template", attr.path.span()).into(),
+            args: attr.parse_args_with(Punctuated::parse_terminated)?,
+        })
+    }
+
+    /// Whether the attribute contains the key `name`, regardless of its kind
+    pub(crate) fn has_key(&self, name: &str) -> bool {
+        self.args.iter().any(|arg| arg.key() == name)
+    }
+
+    /// The value of the key `name = …`
+    pub(crate) fn value(&self, name: &str) -> Option<&syn::Expr> {
+        self.args.iter().find_map(|arg| match arg {
+            TemplateArg::NameValue { key, value, .. } if key == name => Some(&**value),
+            _ => None,
+        })
+    }
+
+    /// The mutable value of the key `name = …`
+    pub(crate) fn value_mut(&mut self, name: &str) -> Option<&mut syn::Expr> {
+        self.args.iter_mut().find_map(|arg| match arg {
+            TemplateArg::NameValue { key, value, .. } if key == name => Some(&mut **value),
+            _ => None,
+        })
+    }
+
+    /// Append the key `name = value`
+    pub(crate) fn push_value(&mut self, key: syn::Ident, value: syn::Expr) {
+        self.args.push(TemplateArg::NameValue {
+            key,
+            eq_token: Default::default(),
+            value: Box::new(value),
+        });
+    }
+
+    /// Replace `path = ["…", "…"]` with the first candidate that exists in the templates directory
+    pub(crate) fn resolve_path_candidates(&mut self) -> syn::Result<()> {
+        let array = match self.value_mut("path") {
+            Some(syn::Expr::Array(array)) => array,
+            _ => return Ok(()),
+        };
+        let root = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join("templates");
+        let candidates = array
+            .elems
+            .iter()
+            .map(lit_str)
+            .collect::<syn::Result<Vec<_>>>()?;
+        let chosen = candidates
+            .iter()
+            .find(|candidate| root.join(candidate.value()).is_file());
+        let chosen = match chosen {
+            Some(chosen) => chosen.clone(),
+            None => {
+                let tried = candidates
+                    .iter()
+                    .map(|candidate| format!("{:?}", candidate.value()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let msg = format!(
+                    "none of the template paths exist in the `templates` directory: {}",
+                    tried,
+                );
+                return Err(syn::Error::new(array.span(), msg));
+            }
+        };
+        *self.value_mut("path").unwrap() = syn::Expr::Lit(syn::ExprLit {
+            attrs: vec![],
+            lit: syn::Lit::Str(chosen),
+        });
+        Ok(())
+    }
+
+    /// Remove the key `name = …`, and return its value
+    pub(crate) fn take_key(&mut self, name: &str) -> Option<syn::Expr> {
+        let mut value = None;
+        self.args = std::mem::take(&mut self.args)
+            .into_iter()
+            .filter_map(|arg| match arg {
+                TemplateArg::NameValue { key, value: v, .. } if key == name => {
+                    value = Some(*v);
+                    None
+                }
+                arg => Some(arg),
+            })
+            .collect();
+        value
+    }
+
+    /// Remove the flag `name`, and return it
+    pub(crate) fn take_path(&mut self, name: &str) -> Option<syn::Ident> {
+        let mut found = None;
+        self.args = std::mem::take(&mut self.args)
+            .into_iter()
+            .filter_map(|arg| match arg {
+                TemplateArg::Path(key) if key == name => {
+                    found = Some(key);
+                    None
+                }
+                arg => Some(arg),
+            })
+            .collect();
+        found
+    }
+
+    /// Remove the flag `name` or `name = true`, and return if it was set; `name = false` opts out
+    pub(crate) fn take_flag(&mut self, name: &str) -> syn::Result<bool> {
+        if self.take_path(name).is_some() {
+            return Ok(true);
+        }
+        match self.take_key(name) {
+            Some(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Bool(value),
+                ..
+            })) => Ok(value.value),
+            Some(value) => {
+                let msg = format!("expected `{}`, or `{} = false` to opt out", name, name);
+                Err(syn::Error::new(value.span(), msg))
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Remove all lists `name(…)`, and return them
+    pub(crate) fn take_lists(&mut self, name: &str) -> Vec<TemplateList> {
+        let mut lists = Vec::new();
+        self.args = std::mem::take(&mut self.args)
+            .into_iter()
+            .filter_map(|arg| match arg {
+                TemplateArg::List { key, tokens, .. } if key == name => {
+                    lists.push(TemplateList { key, tokens });
+                    None
+                }
+                arg => Some(arg),
+            })
+            .collect();
+        lists
+    }
+
+    /// Remove all keys and flags named in `names`
+    pub(crate) fn remove(&mut self, names: &[&str]) {
+        for name in names {
+            self.take_key(name);
+            self.take_path(name);
+        }
+    }
+
+    /// Replace the template by an empty inline `source`, for variants that render without one
+    pub(crate) fn clear_source(&mut self, span: proc_macro2::Span) {
+        self.remove(SOURCE_KEYS);
+        self.push_value(syn::Ident::new("source", span), syn::parse_quote!(""));
+    }
+}
+
+/// The keys that choose the template of a variant
+const SOURCE_KEYS: &[&str] = &["source", "path", "doc", "path_pattern", "case"];
+
+impl Parse for TemplateArg {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let key = syn::Ident::parse_any(input)?;
+        if input.peek(Token![=]) {
+            Ok(Self::NameValue {
+                key,
+                eq_token: input.parse()?,
+                value: input.parse()?,
+            })
+        } else if input.peek(token::Paren) {
+            let content;
+            let paren_token = syn::parenthesized!(content in input);
+            Ok(Self::List {
+                key,
+                paren_token,
+                tokens: content.parse()?,
+            })
+        } else if key == "source_from_doc" {
+            // an alias of `doc`, so the checks and merging of `doc` apply to it, too
+            Ok(Self::Path(syn::Ident::new("doc", key.span())))
+        } else {
+            Ok(Self::Path(key))
+        }
+    }
+}
+
+impl TemplateArg {
+    pub(crate) fn key(&self) -> &syn::Ident {
+        match self {
+            Self::NameValue { key, .. } | Self::List { key, .. } | Self::Path(key) => key,
+        }
+    }
+}
+
+impl TemplateList {
+    /// Parse `key(key = value, …)`
+    pub(crate) fn parse_args(&self) -> syn::Result<Punctuated<TemplateArg, Token![,]>> {
+        Punctuated::parse_terminated.parse2(self.tokens.clone())
+    }
+
+    /// Parse `key(predicate, key = value, …)`
+    pub(crate) fn parse_predicate_and_args(
+        &self,
+    ) -> syn::Result<(syn::Meta, Punctuated<TemplateArg, Token![,]>)> {
+        let parser = |input: ParseStream<'_>| {
+            let predicate = input.parse()?;
+            if input.is_empty() {
+                return Ok((predicate, Punctuated::new()));
+            }
+            input.parse::<Token![,]>()?;
+            Ok((predicate, Punctuated::parse_terminated(input)?))
+        };
+        parser.parse2(self.tokens.clone())
+    }
+}
+
+impl ToTokens for TemplateArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.path.to_tokens(tokens);
+        token::Paren(self.path.span()).surround(tokens, |tokens| self.args.to_tokens(tokens));
+    }
+}
+
+impl ToTokens for TemplateArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::NameValue {
+                key,
+                eq_token,
+                value,
+            } => {
+                key.to_tokens(tokens);
+                eq_token.to_tokens(tokens);
+                value.to_tokens(tokens);
+            }
+            Self::List {
+                key,
+                paren_token,
+                tokens: inner,
+            } => {
+                key.to_tokens(tokens);
+                paren_token.surround(tokens, |tokens| inner.to_tokens(tokens));
+            }
+            Self::Path(key) => key.to_tokens(tokens),
+        }
+    }
+}
+
+/// Override the keys in `base` with `overrides`
+///
+/// `source`, `source_file`, `source_env`, `path`, `path_pattern` and `doc` are mutually exclusive, so one replaces the others.
+pub(crate) fn merge(
+    base: &Punctuated<TemplateArg, Token![,]>,
+    overrides: impl IntoIterator<Item = TemplateArg>,
+) -> Punctuated<TemplateArg, Token![,]> {
+    let overrides = overrides.into_iter().collect::<Vec<_>>();
+    let is_overridden = |arg: &TemplateArg| {
+        overrides.iter().any(|over| {
+            let (a, b) = (arg.key(), over.key());
+            let is_source = |key: &syn::Ident| {
+                key == "source"
+                    || key == "source_file"
+                    || key == "source_env"
+                    || key == "path"
+                    || key == "path_pattern"
+                    || key == "doc"
+            };
+            a == b || (is_source(a) && is_source(b))
+        })
+    };
+    let mut result = base
+        .iter()
+        .filter(|arg| !is_overridden(arg))
+        .cloned()
+        .collect::<Punctuated<_, _>>();
+    result.extend(overrides);
+    result
+}
+
+/// The value of a `key = "…"` argument
+pub(crate) fn lit_str(value: &syn::Expr) -> syn::Result<syn::LitStr> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => Ok(s.clone()),
+        value => Err(syn::Error::new(value.span(), "expected a string literal")),
+    }
+}
@@ -0,0 +1,104 @@
+//! `assert_renders_eq!(…)` and `assert_renders_ok!(…)` for the tests of downstream crates
+
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::Token;
+
+/// `value, expected` or `value`, with an optional trailing comma
+struct AssertArgs {
+    value: syn::Expr,
+    expected: Option<syn::Expr>,
+}
+
+impl Parse for AssertArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let value = input.parse()?;
+        let mut expected = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            expected = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { value, expected })
+    }
+}
+
+/// Render `value`, and panic with the type of `value` and the error if rendering fails
+fn make_render(value: &syn::Expr) -> proc_macro2::TokenStream {
+    quote! {
+        fn type_name<T: ?::std::marker::Sized>(_: &T) -> &'static ::std::primitive::str {
+            ::std::any::type_name::<T>()
+        }
+
+        let value = &(#value);
+        let rendered = match askama::Template::render(value) {
+            ::std::result::Result::Ok(rendered) => rendered,
+            ::std::result::Result::Err(err) => {
+                ::std::panic!("could not render `{}`: {}", type_name(value), err);
+            }
+        };
+    }
+}
+
+pub(crate) fn make_assert_renders_eq(
+    input: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let args: AssertArgs = syn::parse2(input)?;
+    let expected = match &args.expected {
+        Some(expected) => expected,
+        None => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "expected `assert_renders_eq!(value, \"expected output\")`",
+            ));
+        }
+    };
+    let render = make_render(&args.value);
+    Ok(quote! {{
+        #render
+        let expected = &(#expected);
+        let expected: &::std::primitive::str = ::std::convert::AsRef::as_ref(expected);
+        if rendered != expected {
+            let mut diff = ::std::string::String::new();
+            let mut expected_lines = expected.split('\n');
+            let mut rendered_lines = rendered.split('\n');
+            loop {
+                match (expected_lines.next(), rendered_lines.next()) {
+                    (::std::option::Option::None, ::std::option::Option::None) => break,
+                    (::std::option::Option::Some(e), ::std::option::Option::Some(r)) if e == r => {
+                        diff.push_str(&::std::format!("  {:?}\n", e));
+                    }
+                    (e, r) => {
+                        if let ::std::option::Option::Some(e) = e {
+                            diff.push_str(&::std::format!("- {:?}\n", e));
+                        }
+                        if let ::std::option::Option::Some(r) = r {
+                            diff.push_str(&::std::format!("+ {:?}\n", r));
+                        }
+                    }
+                }
+            }
+            ::std::panic!(
+                "rendered output of `{}` does not match (- expected, + rendered):\n{}",
+                type_name(value),
+                diff,
+            );
+        }
+    }})
+}
+
+pub(crate) fn make_assert_renders_ok(
+    input: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let args: AssertArgs = syn::parse2(input)?;
+    if let Some(expected) = &args.expected {
+        return Err(syn::Error::new_spanned(
+            expected,
+            "use `assert_renders_eq!` to compare the output",
+        ));
+    }
+    let render = make_render(&args.value);
+    Ok(quote! {{
+        #render
+        rendered
+    }})
+}
@@ -0,0 +1,221 @@
+//! `#[template(block = "name")]`: render a single `{% block %}` of a shared template file
+//!
+//! askama 0.11 cannot render a single block, so the block is cut out of the file, and used as the
+//! `source` of the variant. `#[template(blocks("name", …))]` does the same for `render_block()`,
+//! with one struct per block next to the struct of the whole template.
+
+use std::path::PathBuf;
+
+use quote::format_ident;
+use syn::parse::Parser;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+
+use crate::args::{self, TemplateArgs, TemplateList};
+use crate::expand::Include;
+
+/// The name, source and `ext` of a block of `blocks(…)`
+pub(crate) type BlockSource = (syn::LitStr, syn::LitStr, Option<syn::LitStr>);
+
+/// Take `blocks("name", …)` out of `meta`, and cut the blocks out of the file of its `path`
+///
+/// The files that were read are added to `includes`, so the crate is rebuilt if they change.
+pub(crate) fn take_blocks(
+    meta: &mut TemplateArgs,
+    owner: &str,
+    includes: &mut Vec<Include>,
+) -> syn::Result<Vec<BlockSource>> {
+    let mut sources = Vec::new();
+    for list in meta.take_lists("blocks") {
+        let path = match meta.value("path") {
+            Some(path) => args::lit_str(path)?,
+            None => {
+                return Err(syn::Error::new(
+                    list.key.span(),
+                    "`blocks(…)` needs a `path` to take the blocks from",
+                ));
+            }
+        };
+        for name in parse_names(&list)? {
+            let (source, full_path) = extract(&path, &name, owner)?;
+            includes.push(Include::File(full_path));
+            let ext = std::path::Path::new(&path.value())
+                .extension()
+                .map(|ext| syn::LitStr::new(&ext.to_string_lossy(), path.span()));
+            sources.push((name.clone(), syn::LitStr::new(&source, name.span()), ext));
+        }
+    }
+    Ok(sources)
+}
+
+/// Take `block = "name"` out of `meta`, and use the block of the file of its `path` as `source`
+///
+/// The file that was read is added to `includes`, so the crate is rebuilt if it changes.
+pub(crate) fn take_block(
+    meta: &mut TemplateArgs,
+    owner: &str,
+    includes: &mut Vec<Include>,
+) -> syn::Result<()> {
+    let block = match meta.take_key("block") {
+        Some(block) => args::lit_str(&block)?,
+        None => return Ok(()),
+    };
+    let path = match meta.take_key("path") {
+        Some(path) => args::lit_str(&path)?,
+        None => {
+            return Err(syn::Error::new(
+                block.span(),
+                "`block` needs a `path` to take the block from, e.g. in the enum's #[template]",
+            ));
+        }
+    };
+    let (source, full_path) = extract(&path, &block, owner)?;
+    includes.push(Include::File(full_path));
+    let source = syn::LitStr::new(&source, block.span());
+    meta.push_value(
+        syn::Ident::new("source", block.span()),
+        parse_quote!(#source),
+    );
+    let ext = std::path::Path::new(&path.value())
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned());
+    if let (false, Some(ext)) = (meta.has_key("ext"), ext) {
+        let ext = syn::LitStr::new(&ext, path.span());
+        meta.push_value(syn::Ident::new("ext", path.span()), parse_quote!(#ext));
+    }
+    Ok(())
+}
+
+/// The names in `blocks("name", …)`
+pub(crate) fn parse_names(list: &TemplateList) -> syn::Result<Vec<syn::LitStr>> {
+    let names = Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated
+        .parse2(list.tokens.clone())?
+        .into_iter()
+        .collect::<Vec<_>>();
+    for (index, name) in names.iter().enumerate() {
+        if let Some(first) = names[..index]
+            .iter()
+            .find(|first| first.value() == name.value())
+        {
+            let mut err = syn::Error::new(name.span(), "duplicate block");
+            err.combine(syn::Error::new(first.span(), "first used here"));
+            return Err(err);
+        }
+    }
+    Ok(names)
+}
+
+/// The struct that renders the block `name` of the variant `helper`
+pub(crate) fn struct_name(helper: &syn::Ident, name: &str) -> syn::Ident {
+    let name = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    format_ident!("{}_block_{}", helper, name)
+}
+
+/// `fn render_block(&self, block: &str) -> askama::Result<String>` and `fn blocks(&self)`
+///
+/// `blocks` is the `match` of `blocks()`, `render_impls` render every block that any variant has.
+pub(crate) fn make_methods(
+    vis: &syn::Visibility,
+    blocks: proc_macro2::TokenStream,
+    render_impls: Vec<(String, syn::ExprMatch)>,
+) -> Vec<syn::ImplItem> {
+    let (names, render_impls): (Vec<_>, Vec<_>) = render_impls.into_iter().unzip();
+    let render_block = parse_quote! {
+        /// Render the `{% block %}` named `block` of the variant's template
+        ///
+        /// Fails if the block is not in the `#[template(blocks(…))]` of the variant.
+        #vis fn render_block(
+            &self,
+            block: &::std::primitive::str,
+        ) -> askama::Result<::std::string::String> {
+            match block {
+                #(#names if self.blocks().contains(&block) => #render_impls,)*
+                _ => ::std::result::Result::Err(askama::Error::Custom(::std::convert::From::from(
+                    ::std::format!("the variant has no block {:?}", block),
+                ))),
+            }
+        }
+    };
+    let blocks = parse_quote! {
+        /// The names of the variant's `#[template(blocks(…))]`, which `render_block()` can render
+        #vis fn blocks(&self) -> &'static [&'static ::std::primitive::str] {
+            #blocks
+        }
+    };
+    vec![render_block, blocks]
+}
+
+/// The content of `{% block name %}…{% endblock %}` in the file `path`, and the file's full path
+pub(crate) fn extract(
+    path: &syn::LitStr,
+    block: &syn::LitStr,
+    owner: &str,
+) -> syn::Result<(String, String)> {
+    let full_path = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("templates")
+        .join(path.value());
+    let text = std::fs::read_to_string(&full_path).map_err(|err| {
+        let msg = format!("{}: could not read {:?}: {}", owner, path.value(), err);
+        syn::Error::new(path.span(), msg)
+    })?;
+    match find_block(&text, &block.value()) {
+        Some(content) => Ok((content.to_owned(), full_path.display().to_string())),
+        None => {
+            let msg = format!(
+                "{}: there is no `{{% block {} %}}` in {:?}",
+                owner,
+                block.value(),
+                path.value(),
+            );
+            Err(syn::Error::new(block.span(), msg))
+        }
+    }
+}
+
+/// The text between `{% block name %}` and its `{% endblock %}`, nested blocks included
+///
+/// Whitespace control with `{%- … -%}` on these two tags is applied to the text.
+fn find_block<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    let mut depth = 0_usize;
+    let mut start = None;
+    let mut rest = 0;
+    while let Some(open) = text[rest..].find("{%") {
+        let open = rest + open;
+        let close = open + text[open..].find("%}")? + 2;
+        let tag = text[open + 2..close - 2].trim_matches(|c: char| "-+~".contains(c));
+        let mut words = tag.split_whitespace();
+        match words.next() {
+            Some("block") => {
+                if start.is_none() && words.next() == Some(name) {
+                    let trim = text[..close - 2].ends_with('-');
+                    start = Some((close, depth, trim));
+                }
+                depth += 1;
+            }
+            Some("endblock") => {
+                depth = depth.checked_sub(1)?;
+                if let Some((start, start_depth, trim_start)) = start {
+                    if depth == start_depth {
+                        let content = &text[start..open];
+                        let content = match trim_start {
+                            true => content.trim_start(),
+                            false => content,
+                        };
+                        return match text[open + 2..].starts_with('-') {
+                            true => Some(content.trim_end()),
+                            false => Some(content),
+                        };
+                    }
+                }
+            }
+            _ => {}
+        }
+        rest = close;
+    }
+    None
+}
@@ -0,0 +1,115 @@
+//! `rename_all = "…"` rules, named like serde's
+
+/// How to rename a variant name, which is expected to be in PascalCase
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub(crate) fn from_lit(lit: &syn::LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected one of \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \
+                    \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\", \
+                    or \"SCREAMING-KEBAB-CASE\"",
+                ));
+            }
+        })
+    }
+
+    /// `case = "…"` of `#[template(path_pattern = "…")]`
+    pub(crate) fn from_case_lit(lit: &syn::LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "snake" => Self::Snake,
+            "kebab" => Self::Kebab,
+            "lower" => Self::Lower,
+            "SCREAMING_SNAKE" => Self::ScreamingSnake,
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected one of \"snake\", \"kebab\", \"lower\", or \"SCREAMING_SNAKE\"",
+                ));
+            }
+        })
+    }
+
+    /// Like [`RenameRule::apply()`], but keep acronyms together, e.g. `HTTPError` -> `http_error`
+    pub(crate) fn apply_with_acronyms(self, name: &str) -> String {
+        match self {
+            Self::Snake => separate_words(name, '_').to_ascii_lowercase(),
+            Self::ScreamingSnake => separate_words(name, '_').to_ascii_uppercase(),
+            Self::Kebab => separate_words(name, '-').to_ascii_lowercase(),
+            Self::ScreamingKebab => separate_words(name, '-').to_ascii_uppercase(),
+            _ => self.apply(name),
+        }
+    }
+
+    pub(crate) fn apply(self, name: &str) -> String {
+        match self {
+            Self::Lower => name.to_ascii_lowercase(),
+            Self::Upper => name.to_ascii_uppercase(),
+            Self::Pascal => name.to_owned(),
+            Self::Camel => {
+                let mut chars = name.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            Self::Snake => separate(name, '_').to_ascii_lowercase(),
+            Self::ScreamingSnake => separate(name, '_').to_ascii_uppercase(),
+            Self::Kebab => separate(name, '-').to_ascii_lowercase(),
+            Self::ScreamingKebab => separate(name, '-').to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Insert `separator` before every uppercase letter but the first one
+fn separate(name: &str, separator: char) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    for (index, c) in name.char_indices() {
+        if index > 0 && c.is_uppercase() {
+            result.push(separator);
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Insert `separator` before every word but the first one
+///
+/// A word starts at an uppercase letter after a lowercase letter or digit, or at the last
+/// uppercase letter of an acronym if a lowercase letter follows.
+fn separate_words(name: &str, separator: char) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (index, &c) in chars.iter().enumerate() {
+        if index > 0 && c.is_uppercase() {
+            let after_upper = chars[index - 1].is_uppercase();
+            let before_lower = matches!(chars.get(index + 1), Some(c) if c.is_lowercase());
+            if !after_upper || before_lower {
+                result.push(separator);
+            }
+        }
+        result.push(c);
+    }
+    result
+}
@@ -0,0 +1,72 @@
+//! `#[enum_template(check_size_hint)]` and `#[enum_template(validate_json)]`: check the output of
+//! `render()` in debug builds
+
+use quote::quote;
+
+use crate::{match_variants, VariantDefinition};
+
+/// Panic if the output is more than `factor` times longer than the variant's `SIZE_HINT`
+pub(crate) fn check_size_hint(
+    factor: usize,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    static_ty_generics: &proc_macro2::TokenStream,
+    render_impl: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let size_hints = match_variants(data, variant_definitions, |definition| {
+        let size_hint = definition.size_hint(static_ty_generics);
+        let variant_path = &definition.variant_path;
+        quote!((#size_hint, #variant_path))
+    });
+    quote! {
+        let rendered = #render_impl;
+        #[cfg(debug_assertions)]
+        if let ::std::result::Result::Ok(rendered) = &rendered {
+            let (size_hint, variant): (::std::primitive::usize, &::std::primitive::str) =
+                #size_hints;
+            ::std::debug_assert!(
+                rendered.len() <= size_hint.saturating_mul(#factor),
+                "rendering {} produced {} bytes, but its SIZE_HINT is only {}",
+                variant,
+                rendered.len(),
+                size_hint,
+            );
+        }
+        rendered
+    }
+}
+
+/// Panic if a variant whose `EXTENSION` is `json` renders invalid JSON
+pub(crate) fn validate_json(
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    static_ty_generics: &proc_macro2::TokenStream,
+    render_impl: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let extensions = match_variants(data, variant_definitions, |definition| {
+        let ty = definition.template_type(static_ty_generics);
+        let variant_path = &definition.variant_path;
+        quote! {(
+            <#ty as askama::Template>::EXTENSION,
+            #variant_path,
+        )}
+    });
+    quote! {
+        let rendered = { #render_impl };
+        #[cfg(debug_assertions)]
+        if let ::std::result::Result::Ok(rendered) = &rendered {
+            let (extension, variant): (
+                ::std::option::Option<&::std::primitive::str>,
+                &::std::primitive::str,
+            ) = #extensions;
+            if extension == ::std::option::Option::Some("json") {
+                if let ::std::result::Result::Err(err) =
+                    serde_json::from_str::<serde::de::IgnoredAny>(rendered)
+                {
+                    ::std::panic!("rendering {} produced invalid JSON: {}", variant, err);
+                }
+            }
+        }
+        rendered
+    }
+}
@@ -0,0 +1,91 @@
+//! `#[enum_template(conditional)]`: ETags, and `304 Not Modified` responses for the web integrations
+
+use quote::quote;
+use syn::parse_quote;
+
+use crate::response;
+
+/// The helper functions, need to be put into the `const _` block once
+pub(crate) fn make_helpers() -> proc_macro2::TokenStream {
+    quote! {
+        /// A strong ETag of the rendered template
+        fn etag_of(body: &::std::primitive::str) -> ::std::string::String {
+            let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+            ::std::hash::Hash::hash(body, &mut hasher);
+            ::std::format!("\"{:016x}\"", ::std::hash::Hasher::finish(&hasher))
+        }
+
+        /// Whether the value of an `If-None-Match` header matches the ETag
+        fn etag_matches(
+            if_none_match: ::std::option::Option<&::std::primitive::str>,
+            etag: &::std::primitive::str,
+        ) -> ::std::primitive::bool {
+            match if_none_match {
+                ::std::option::Option::Some(if_none_match) => {
+                    if_none_match.split(',').any(|tag| {
+                        let tag = tag.trim();
+                        tag == "*" || tag.trim_start_matches("W/") == etag
+                    })
+                }
+                ::std::option::Option::None => false,
+            }
+        }
+
+        /// Whether to answer with `304 Not Modified`
+        ///
+        /// The precondition is only evaluated for successful responses, see RFC 9110, 13.2.1.
+        fn is_not_modified(
+            status: ::std::primitive::u16,
+            if_none_match: ::std::option::Option<&::std::primitive::str>,
+            etag: &::std::primitive::str,
+        ) -> ::std::primitive::bool {
+            (200..300).contains(&status) && etag_matches(if_none_match, etag)
+        }
+    }
+}
+
+pub(crate) fn make_methods(vis: &syn::Visibility, parts: &response::Parts) -> Vec<syn::ImplItem> {
+    let mut methods = vec![parse_quote! {
+        /// A strong ETag of the rendered template, including the quotes
+        #vis fn etag(&self) -> askama::Result<::std::string::String> {
+            ::std::result::Result::Ok(etag_of(&askama::Template::render(self)?))
+        }
+    }];
+    if cfg!(feature = "axum") {
+        let response = response::axum(parts, quote!(self), Some(quote!(if_none_match)));
+        methods.push(parse_quote! {
+            /// Render the template into an axum response with an `ETag` header
+            ///
+            /// If `if_none_match`, the value of the request's `If-None-Match` header, matches
+            /// the ETag, and the variant's status code is 2xx, the response has the status code 304,
+            /// the variant's extra headers, and no body. Variants with a `redirect`
+            /// are answered with a 303 redirect instead.
+            /// If rendering fails, the response has the status code 500, and contains the error message.
+            #vis fn to_axum_conditional_response(
+                &self,
+                if_none_match: ::std::option::Option<&::std::primitive::str>,
+            ) -> axum::response::Response {
+                #response
+            }
+        });
+    }
+    if cfg!(feature = "rouille") {
+        let response = response::rouille(parts, quote!(self), Some(quote!(if_none_match)));
+        methods.push(parse_quote! {
+            /// Render the template into a rouille response with an `ETag` header
+            ///
+            /// If `if_none_match`, the value of the request's `If-None-Match` header, matches
+            /// the ETag, and the variant's status code is 2xx, the response has the status code 304,
+            /// the variant's extra headers, and no body. Variants with a `redirect`
+            /// are answered with a 303 redirect instead.
+            /// If rendering fails, the response has the status code 500, and contains the error message.
+            #vis fn to_rouille_conditional_response(
+                &self,
+                if_none_match: ::std::option::Option<&::std::primitive::str>,
+            ) -> rouille::Response {
+                #response
+            }
+        });
+    }
+    methods
+}
@@ -0,0 +1,119 @@
+//! The enum's `EXTENSION`, `SIZE_HINT` and `MIME_TYPE`
+
+use quote::{quote, ToTokens};
+
+use crate::args::TemplateArgs;
+use crate::{EnumOptions, VariantDefinition};
+
+/// Take `meta_default` out of the `#[template]` of a variant, and remember the variant's helper in
+/// `constants_variant` with the marker, so `make_consts()` takes the constants from it
+pub(crate) fn take_meta_default(
+    meta: &mut TemplateArgs,
+    is_local: bool,
+    helper: &syn::Ident,
+    constants_variant: &mut Option<(syn::Ident, syn::Ident)>,
+) -> syn::Result<()> {
+    let marker = match meta.take_path("meta_default") {
+        Some(marker) => marker,
+        None => return Ok(()),
+    };
+    if !is_local {
+        return Err(syn::Error::new(
+            marker.span(),
+            "`meta_default` can only be used in the #[template] of a variant",
+        ));
+    }
+    if meta.has_key("skip") || meta.has_key("redirect") {
+        return Err(syn::Error::new(
+            marker.span(),
+            "`skip` and `redirect` variants render nothing, so they cannot supply the constants",
+        ));
+    }
+    if let Some(key) = ["display", "fmt"].iter().find(|key| meta.has_key(key)) {
+        let msg = format!(
+            "`{}` variants have no template, so they cannot supply the constants",
+            key,
+        );
+        return Err(syn::Error::new(marker.span(), msg));
+    }
+    if let Some((_, first)) = constants_variant {
+        let mut err = syn::Error::new(
+            marker.span(),
+            "`meta_default` can only be used on one variant",
+        );
+        err.combine(syn::Error::new(first.span(), "first used here"));
+        return Err(err);
+    }
+    *constants_variant = Some((helper.clone(), marker));
+    Ok(())
+}
+
+/// The constants of `impl Template`
+///
+/// Unless the enum overrides them, they are taken from `constants_variant`, i.e. the variant with
+/// `meta_default` or the default template, or from the first variant with a template.
+pub(crate) fn make_consts(
+    options: &EnumOptions,
+    variant_definitions: &[VariantDefinition],
+    constants_variant: Option<syn::Ident>,
+    static_ty_generics: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let dflt_or_fst = constants_variant
+        .and_then(|name| {
+            variant_definitions
+                .iter()
+                .find(|definition| definition.helper == name)
+        })
+        .filter(|definition| definition.has_template())
+        .unwrap_or_else(|| {
+            let definition = variant_definitions
+                .iter()
+                .find(|definition| definition.has_template());
+            definition.unwrap_or(&variant_definitions[0])
+        });
+    let dflt_or_fst_type = dflt_or_fst.template_type(static_ty_generics);
+
+    let extension = match &options.extension {
+        Some(extension) => quote!(::std::option::Option::Some(#extension)),
+        None => quote!(
+            <#dflt_or_fst_type as askama::Template>::EXTENSION
+        ),
+    };
+    let size_hint = match &options.size_hint {
+        Some(size_hint) => quote!(#size_hint),
+        None if variant_definitions
+            .iter()
+            .any(|definition| definition.size_hint.is_some()) =>
+        {
+            let size_hints = variant_definitions
+                .iter()
+                .map(|definition| definition.size_hint(static_ty_generics));
+            quote! {{
+                let size_hints = [#(#size_hints),*];
+                let mut max = 0;
+                let mut index = 0;
+                while index < size_hints.len() {
+                    if size_hints[index] > max {
+                        max = size_hints[index];
+                    }
+                    index += 1;
+                }
+                max
+            }}
+        }
+        None => quote!(
+            <#dflt_or_fst_type as askama::Template>::SIZE_HINT
+        ),
+    };
+    let mime_type = match options.mime.as_ref().or(dflt_or_fst.mime.as_ref()) {
+        Some(mime) => mime.to_token_stream(),
+        None => quote!(
+            <#dflt_or_fst_type as askama::Template>::MIME_TYPE
+        ),
+    };
+    quote! {
+        const EXTENSION: ::std::option::Option<&'static str> = #extension;
+        const SIZE_HINT: ::std::primitive::usize = #size_hint;
+        const MIME_TYPE: &'static ::std::primitive::str = #mime_type;
+    }
+}
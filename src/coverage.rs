@@ -0,0 +1,35 @@
+//! `#[enum_template(coverage)]`: remember which variants were rendered
+
+use quote::quote;
+
+use crate::unraw;
+
+/// The table of flags, one per variant, needs to be put into the `const _` block once
+pub(crate) fn make_table(data: &syn::DataEnum) -> proc_macro2::TokenStream {
+    let len = data.variants.len();
+    let flags = data
+        .variants
+        .iter()
+        .map(|_| quote!(::std::sync::atomic::AtomicBool::new(false)));
+    quote! {
+        static COVERAGE: [::std::sync::atomic::AtomicBool; #len] = [#(#flags,)*];
+    }
+}
+
+/// Mark the variant with the index `index` as rendered
+pub(crate) fn make_mark(index: usize) -> proc_macro2::TokenStream {
+    quote!(COVERAGE[#index].store(true, ::std::sync::atomic::Ordering::Relaxed))
+}
+
+/// The body of `render_coverage()`
+pub(crate) fn make_report(data: &syn::DataEnum) -> proc_macro2::TokenStream {
+    let names = data.variants.iter().map(|variant| unraw(&variant.ident));
+    quote! {
+        let names: &[&'static ::std::primitive::str] = &[#(#names,)*];
+        names
+            .iter()
+            .zip(&COVERAGE)
+            .map(|(&name, flag)| (name, flag.load(::std::sync::atomic::Ordering::Relaxed)))
+            .collect()
+    }
+}
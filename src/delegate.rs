@@ -0,0 +1,329 @@
+//! `#[template(delegate)]`: render a field that implements `Template` itself
+//!
+//! Without a `source`, the field is rendered directly. With `source = "…{{ inner }}…"`, the output
+//! of a newtype variant's field is wrapped.
+
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+
+use crate::args::{self, TemplateArgs};
+use crate::fields;
+
+/// `delegate` or `delegate = "field"` in the `#[template]` of a variant
+pub(crate) struct Delegation<'a> {
+    /// `delegate` with an inline `source`, which wraps the output of the field as `{{ inner }}`
+    pub(crate) wrapper: Option<syn::Ident>,
+    /// The field that is rendered directly, with `delegate = "field"` or `delegate` without `source`
+    pub(crate) transparent: Option<(syn::Member, &'a syn::Field)>,
+}
+
+impl Delegation<'_> {
+    pub(crate) fn is_some(&self) -> bool {
+        self.wrapper.is_some() || self.transparent.is_some()
+    }
+}
+
+/// Take `delegate` and `delegate = "field"` out of the `#[template]` of a variant
+///
+/// `own_source` is the key that gives the variant a template of its own, if any. A field that is
+/// rendered directly has no use for the keys inherited from the enum, so they are removed.
+pub(crate) fn take<'a>(
+    meta: &mut TemplateArgs,
+    variant: &'a syn::Variant,
+    own_source: Option<&str>,
+) -> syn::Result<Delegation<'a>> {
+    let field = match meta.take_key("delegate") {
+        Some(value) => Some(args::lit_str(&value)?),
+        None => None,
+    };
+    let path = meta.take_path("delegate");
+    let delegation = match (field, path, own_source) {
+        (Some(field), _, Some(key)) => {
+            let msg = format!(
+                "`delegate = \"…\"` renders the field directly, so it cannot be combined \
+                with `{}`",
+                key,
+            );
+            return Err(syn::Error::new(field.span(), msg));
+        }
+        (None, Some(path), Some(key)) if key != "source" => {
+            let msg = format!(
+                "`delegate` renders the field directly, or wraps it in an inline `source`, \
+                so it cannot be combined with `{}`",
+                key,
+            );
+            return Err(syn::Error::new(path.span(), msg));
+        }
+        (Some(field), _, None) => Delegation {
+            wrapper: None,
+            transparent: Some(find_field(variant, Some(&field), field.span())?),
+        },
+        (None, Some(path), None) => Delegation {
+            wrapper: None,
+            transparent: Some(find_field(variant, None, path.span())?),
+        },
+        (None, wrapper, _) => Delegation {
+            wrapper,
+            transparent: None,
+        },
+    };
+    if delegation.transparent.is_some() {
+        meta.remove(crate::INHERITED_KEYS);
+    }
+    Ok(delegation)
+}
+
+/// Take `delegate_with = "path::to::adapter"` out of the `#[template]` of a variant
+///
+/// The adapter's template is rendered, so the variant's own template is replaced by an empty one.
+pub(crate) fn take_adapter(
+    meta: &mut TemplateArgs,
+    own_source: Option<&str>,
+    delegated: bool,
+) -> syn::Result<Option<(syn::Path, proc_macro2::Span)>> {
+    let lit = match meta.take_key("delegate_with") {
+        Some(value) => args::lit_str(&value)?,
+        None => return Ok(None),
+    };
+    if delegated {
+        return Err(syn::Error::new(
+            lit.span(),
+            "cannot combine `delegate_with` with `delegate`",
+        ));
+    }
+    if let Some(key) = own_source {
+        let msg = format!(
+            "`delegate_with` renders the adapter's template, so it cannot be combined with `{}`",
+            key,
+        );
+        return Err(syn::Error::new(lit.span(), msg));
+    }
+    let path = lit.parse::<syn::Path>()?;
+    if !meta.has_key("ext") {
+        return Err(syn::Error::new(
+            lit.span(),
+            "`delegate_with` needs an `ext` for the constants of the variant, \
+            e.g. the `ext` of the adapter's template",
+        ));
+    }
+    meta.clear_source(lit.span());
+    Ok(Some((path, lit.span())))
+}
+
+/// Turn the `{{ inner }}` of the inline `source` of a `delegate` variant into the rendered field
+pub(crate) fn wrap_source(
+    meta: &mut TemplateArgs,
+    wrapper: &syn::Ident,
+    variant: &syn::Variant,
+    has_computed: bool,
+) -> syn::Result<()> {
+    let is_newtype = matches!(
+        &variant.fields,
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1,
+    );
+    if !is_newtype {
+        return Err(syn::Error::new(
+            wrapper.span(),
+            "`delegate` needs a newtype variant like `Item(Card)`",
+        ));
+    }
+    if has_computed {
+        return Err(syn::Error::new(
+            wrapper.span(),
+            "computed values cannot be added to `delegate` variants",
+        ));
+    }
+    match meta.value_mut("source") {
+        Some(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(source),
+            ..
+        })) => {
+            *source = wrap_inner(source)?;
+            Ok(())
+        }
+        _ => Err(syn::Error::new(
+            wrapper.span(),
+            "`delegate` needs an inline `source` that contains `{{ inner }}`",
+        )),
+    }
+}
+
+/// A variant that renders one of its fields directly, without a template of its own
+pub(crate) struct Transparent {
+    /// The rendered field
+    pub(crate) member: syn::Member,
+    /// Whether the field is a reference, so the binding needs to be dereferenced once
+    pub(crate) is_reference: bool,
+    /// `type _EnumName_index_VariantName<…> = FieldType;`, used to look up the field's constants
+    pub(crate) alias: proc_macro2::TokenStream,
+}
+
+impl Transparent {
+    /// Render `field` directly, with `helper` as the alias of its type
+    ///
+    /// The field's own template is used, so `cfg(…)` and `alternate(…)` of the variant are errors.
+    pub(crate) fn new(
+        ast: &syn::DeriveInput,
+        variant: &syn::Variant,
+        helper: &syn::Ident,
+        lifetime: &syn::Lifetime,
+        (member, field): (syn::Member, &syn::Field),
+        cfg_alternatives: &[syn::Meta],
+        alternate: bool,
+    ) -> syn::Result<Self> {
+        if let Some(attr) = field.attrs.iter().find(|attr| fields::is_field_attr(attr)) {
+            return Err(syn::Error::new(
+                attr.path.span(),
+                "cannot use #[template_field] on a field that is rendered directly",
+            ));
+        }
+        if let Some(predicate) = cfg_alternatives.first() {
+            return Err(syn::Error::new(
+                predicate.span(),
+                "cannot combine `delegate` without a `source` with `cfg(…)`, \
+                `debug(…)` or `release(…)`",
+            ));
+        }
+        if alternate {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "cannot combine `delegate` without a `source` with `alternate(…)`",
+            ));
+        }
+        let (is_reference, ty) = match &field.ty {
+            syn::Type::Reference(reference) => (true, &*reference.elem),
+            ty => (false, ty),
+        };
+        let params = ast.generics.params.iter().map(|param| match param {
+            syn::GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+            syn::GenericParam::Type(param) => param.ident.to_token_stream(),
+            syn::GenericParam::Const(param) => {
+                let (ident, ty) = (&param.ident, &param.ty);
+                quote!(const #ident: #ty)
+            }
+        });
+        let alias = quote! {
+            type #helper<#lifetime, #(#params),*> = #ty;
+        };
+        Ok(Self {
+            member,
+            is_reference,
+            alias,
+        })
+    }
+}
+
+/// `#[template(delegate_with = "path::to::adapter")]`, the variant renders the adapter's output
+pub(crate) struct Adapter {
+    /// The function that turns references to all fields of the variant into a `Template`
+    pub(crate) path: syn::Path,
+    /// The span of the attribute's value, used for errors about the adapter
+    pub(crate) span: proc_macro2::Span,
+    /// A function that is never called, but fails to compile if the adapter does not fit
+    pub(crate) check: proc_macro2::TokenStream,
+}
+
+impl Adapter {
+    /// The adapter `path` of `variant`, with its `check` named after `helper`
+    pub(crate) fn new(
+        ast: &syn::DeriveInput,
+        variant: &syn::Variant,
+        helper: &syn::Ident,
+        (path, span): (syn::Path, proc_macro2::Span),
+    ) -> syn::Result<Self> {
+        fields::reject_field_attrs(variant, "`delegate_with` variants")?;
+        let (impl_generics, _, where_clause) = ast.generics.split_for_impl();
+        let check_name = format_ident!("{}_adapter", helper);
+        let tmp_names = (0..variant.fields.len())
+            .map(|index| format_ident!("_{}", index))
+            .collect::<Vec<_>>();
+        let tys = variant.fields.iter().map(|field| &field.ty);
+        let check = quote_spanned! {span=>
+            #[allow(dead_code)]
+            fn #check_name #impl_generics (#(#tmp_names: &#tys),*) #where_clause {
+                fn adapter_returns_a_template<T: askama::Template>(_: &T) {}
+                adapter_returns_a_template(&#path(#(#tmp_names),*));
+            }
+        };
+        Ok(Self { path, span, check })
+    }
+}
+
+/// The field that a transparent `delegate` variant renders: its only field, or the field `name`
+pub(crate) fn find_field<'a>(
+    variant: &'a syn::Variant,
+    name: Option<&syn::LitStr>,
+    span: proc_macro2::Span,
+) -> syn::Result<(syn::Member, &'a syn::Field)> {
+    let name = match name {
+        Some(name) => name,
+        None => {
+            return match variant.fields.iter().next() {
+                Some(field) if variant.fields.len() == 1 => {
+                    let member = match &field.ident {
+                        Some(ident) => syn::Member::Named(ident.clone()),
+                        None => syn::Member::Unnamed(0.into()),
+                    };
+                    Ok((member, field))
+                }
+                _ => Err(syn::Error::new(
+                    span,
+                    "`delegate` needs a variant with exactly one field, \
+                    or use `delegate = \"field\"` to select one",
+                )),
+            };
+        }
+    };
+    let value = name.value();
+    let found = variant
+        .fields
+        .iter()
+        .enumerate()
+        .find(|(index, field)| match &field.ident {
+            Some(ident) => crate::unraw(ident) == value,
+            None => index.to_string() == value,
+        });
+    match found {
+        Some((index, field)) => match &field.ident {
+            Some(ident) => Ok((syn::Member::Named(ident.clone()), field)),
+            None => Ok((syn::Member::Unnamed(index.into()), field)),
+        },
+        None => {
+            let msg = format!("the variant has no field `{}`", value);
+            Err(syn::Error::new(name.span(), msg))
+        }
+    }
+}
+
+/// Turn `{{ inner }}` into `{{ inner|safe }}`, because the inner template is already escaped
+pub(crate) fn wrap_inner(source: &syn::LitStr) -> syn::Result<syn::LitStr> {
+    let value = source.value();
+    let mut result = String::with_capacity(value.len() + 8);
+    let mut found = false;
+    let mut rest = value.as_str();
+    while let Some(start) = rest.find("{{") {
+        let (text, expr) = rest.split_at(start);
+        result.push_str(text);
+        let end = match expr.find("}}") {
+            Some(end) => end,
+            None => break,
+        };
+        let inner = expr[2..end].trim();
+        if inner == "inner" {
+            result.push_str("{{ inner|safe }}");
+            found = true;
+        } else {
+            found |= inner.starts_with("inner|") || inner.starts_with("inner |");
+            result.push_str(&expr[..end + 2]);
+        }
+        rest = &expr[end + 2..];
+    }
+    result.push_str(rest);
+    match found {
+        true => Ok(syn::LitStr::new(&result, source.span())),
+        false => Err(syn::Error::new(
+            source.span(),
+            "`delegate` needs `{{ inner }}` in the source",
+        )),
+    }
+}
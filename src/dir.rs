@@ -0,0 +1,44 @@
+//! `#[template(dir = "emails/")]`: a directory that is prepended to the `path` of every variant
+
+use crate::args::{self, TemplateArgs};
+
+/// Join `dir` and `path`, with a single `/` between the parts
+///
+/// A `path` that starts with `/` ignores `dir`, and is relative to the templates directory, too.
+pub(crate) fn join(dir: &str, path: &str) -> String {
+    let joined = match path.starts_with('/') {
+        true => path.to_owned(),
+        false => format!("{}/{}", dir, path),
+    };
+    joined
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Take `dir` out of `meta`, and prepend it to the `path` of `meta`
+///
+/// The directory is returned, so it can be prepended to the result of `path_pattern`, too.
+pub(crate) fn apply(meta: &mut TemplateArgs) -> syn::Result<Option<String>> {
+    let dir = match meta.take_key("dir") {
+        Some(dir) => args::lit_str(&dir)?.value(),
+        None => return Ok(None),
+    };
+    if dir.is_empty() {
+        return Ok(None);
+    }
+    prefix_path(meta, &dir);
+    Ok(Some(dir))
+}
+
+/// Prepend `dir` to the `path` of `meta`, if it has one
+pub(crate) fn prefix_path(meta: &mut TemplateArgs, dir: &str) {
+    if let Some(syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(path),
+        ..
+    })) = meta.value_mut("path")
+    {
+        *path = syn::LitStr::new(&join(dir, &path.value()), path.span());
+    }
+}
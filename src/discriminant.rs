@@ -0,0 +1,61 @@
+//! `#[template(expose_discriminant)]`: the variant's discriminant as the field `discriminant`
+
+use quote::quote;
+use syn::parse_quote;
+
+/// The discriminant of every variant, explicit or implicit, as an expression
+///
+/// Like rustc, a variant without an explicit discriminant gets the previous discriminant plus one.
+pub(crate) fn values(data: &syn::DataEnum) -> Vec<syn::Expr> {
+    let mut last: Option<&syn::Expr> = None;
+    let mut offset = 0_usize;
+    data.variants
+        .iter()
+        .map(|variant| {
+            if let Some((_, expr)) = &variant.discriminant {
+                last = Some(expr);
+                offset = 0;
+            }
+            let lit = syn::LitInt::new(&offset.to_string(), variant.ident.span());
+            let value = match (last, offset) {
+                (Some(expr), 0) => expr.clone(),
+                (Some(expr), _) => parse_quote!((#expr) + #lit),
+                (None, _) => parse_quote!(#lit),
+            };
+            offset += 1;
+            value
+        })
+        .collect()
+}
+
+/// The integer type of `#[repr(…)]`, or `isize` like for enums without a `#[repr]`
+pub(crate) fn ty(ast: &syn::DeriveInput) -> syn::Type {
+    const INTEGERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    for attr in ast.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let Ok(syn::Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested {
+                if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = nested {
+                    if let Some(ident) = path.get_ident() {
+                        if INTEGERS.iter().any(|int| ident == int) {
+                            return syn::Type::Verbatim(quote!(::std::primitive::#ident));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    parse_quote!(::std::primitive::isize)
+}
+
+/// `discriminant: ty`, the field of `#[template(expose_discriminant)]`
+pub(crate) fn field(name: &syn::Ident, ty: &syn::Type) -> syn::Field {
+    syn::Field {
+        attrs: vec![],
+        vis: syn::Visibility::Inherited,
+        ident: Some(name.clone()),
+        colon_token: Some(syn::Token![:](name.span())),
+        ty: ty.clone(),
+    }
+}
@@ -0,0 +1,59 @@
+//! `impl Display` of the enum, which supports padding, and `{:#}` for `#[template(alternate(…))]`
+
+use std::iter::FromIterator;
+
+use quote::quote;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+
+use crate::{make_render_impl, EnumOptions, Flavor, VariantDefinition};
+
+/// The body of `fn fmt(&self, f: &mut Formatter<'_>)`
+pub(crate) fn make_fmt(
+    ast: &syn::DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> proc_macro2::TokenStream {
+    let has_alternate = variant_definitions
+        .iter()
+        .any(|definition| definition.alternate.is_some());
+    let display_into = |writer: syn::Expr| match has_alternate {
+        true => {
+            let match_alternate_impl = make_render_impl(
+                ast,
+                options,
+                data,
+                variant_definitions,
+                "render_into",
+                Punctuated::from_iter([writer.clone()]),
+                Flavor::Alternate,
+            );
+            quote! {
+                match alternate {
+                    true => #match_alternate_impl,
+                    false => askama::Template::render_into(self, #writer),
+                }
+                .map_err(|_| ::std::fmt::Error {})
+            }
+        }
+        false => quote! {
+            askama::Template::render_into(self, #writer).map_err(|_| ::std::fmt::Error {})
+        },
+    };
+    let display_padded = display_into(parse_quote!(&mut buffer));
+    let display_direct = display_into(parse_quote!(f));
+    let alternate = match has_alternate {
+        true => quote!(let alternate = f.alternate();),
+        false => quote!(),
+    };
+    quote! {
+        #alternate
+        if f.width().is_some() || f.precision().is_some() {
+            let mut buffer = ::std::string::String::new();
+            #display_padded?;
+            return f.pad(&buffer);
+        }
+        #display_direct
+    }
+}
@@ -0,0 +1,59 @@
+//! `#[template(doc)]`: use the doc comment of a variant as its template source
+
+use syn::parse_quote;
+
+use crate::args::TemplateArgs;
+
+/// Take `doc` out of `meta`, and use the doc comment of `template_owner` as the `source`
+///
+/// `template_owner` is the variant of `like = "…"`, or `variant` itself.
+pub(crate) fn take(
+    meta: &mut TemplateArgs,
+    template_owner: &syn::Variant,
+    variant: &syn::Variant,
+) -> syn::Result<()> {
+    let doc = match meta.take_path("doc") {
+        Some(doc) => doc,
+        None => return Ok(()),
+    };
+    if meta.has_key("source") || meta.has_key("path") {
+        return Err(syn::Error::new(
+            doc.span(),
+            "cannot combine `doc` with `source` or `path`",
+        ));
+    }
+    let source = match doc_source(&template_owner.attrs) {
+        Some(source) => syn::LitStr::new(&source, doc.span()),
+        None => {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "`doc` needs a doc comment on the variant",
+            ));
+        }
+    };
+    meta.push_value(syn::Ident::new("source", doc.span()), parse_quote!(#source));
+    Ok(())
+}
+
+/// The lines of all `///` comments in `attrs`, with the leading space of each line removed
+fn doc_source(attrs: &[syn::Attribute]) -> Option<String> {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+        if let Ok(syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(lit),
+            ..
+        })) = attr.parse_meta()
+        {
+            for line in lit.value().lines() {
+                lines.push(line.strip_prefix(' ').unwrap_or(line).to_owned());
+            }
+        }
+    }
+    match lines.is_empty() {
+        true => None,
+        false => Some(lines.join("\n")),
+    }
+}
@@ -0,0 +1,61 @@
+//! `#[template(enforce_escape = "html")]`: forbid other escape modes in the variants of the enum
+
+use crate::args::{self, TemplateArg, TemplateArgs};
+
+/// The lists whose keys can override the `escape` of a variant
+const LISTS: &[&str] = &["alternate", "cfg", "debug", "release"];
+
+/// Check that the `escape` keys of `meta` are the one of `enforce_escape = "…"` in `global_meta`
+///
+/// `meta` is the merged `#[template]` of a variant, or of the untagged variants. The keys
+/// `enforce_escape` and `allow_unescaped` are removed, because askama does not know them.
+pub(crate) fn check(
+    meta: &mut TemplateArgs,
+    global_meta: Option<&TemplateArgs>,
+    owner: &str,
+) -> syn::Result<()> {
+    meta.take_key("enforce_escape");
+    let allowed = meta.take_flag("allow_unescaped")?;
+    let enforced = match global_meta.and_then(|meta| meta.value("enforce_escape")) {
+        Some(enforced) if !allowed => args::lit_str(enforced)?,
+        _ => return Ok(()),
+    };
+    let mut escapes = meta
+        .value("escape")
+        .cloned()
+        .into_iter()
+        .collect::<Vec<_>>();
+    for arg in &meta.args {
+        let list = match arg {
+            TemplateArg::List { key, tokens, .. } if LISTS.iter().any(|name| key == name) => {
+                args::TemplateList {
+                    key: key.clone(),
+                    tokens: tokens.clone(),
+                }
+            }
+            _ => continue,
+        };
+        let list_args = match list.key == "cfg" {
+            true => list.parse_predicate_and_args()?.1,
+            false => list.parse_args()?,
+        };
+        escapes.extend(list_args.into_iter().filter_map(|arg| match arg {
+            TemplateArg::NameValue { key, value, .. } if key == "escape" => Some(*value),
+            _ => None,
+        }));
+    }
+    for escape in escapes {
+        let value = args::lit_str(&escape)?;
+        if value.value() != enforced.value() {
+            let msg = format!(
+                "{} uses `escape = {:?}`, but the enum enforces `escape = {:?}`; \
+                add `allow_unescaped` to the variant's #[template] if this is intended",
+                owner,
+                value.value(),
+                enforced.value(),
+            );
+            return Err(syn::Error::new(value.span(), msg));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! `#[enum_template(examples)]`: instances of the variants, built from their examples
+
+use quote::quote;
+use syn::parse_quote;
+
+use crate::args::TemplateArgs;
+use crate::fields::FieldTransform;
+use crate::{fail_at, EnumOptions, VariantDefinition};
+
+/// `#[enum_template(examples(strict))]`
+pub(crate) struct Examples {
+    /// Every variant with fields needs an example
+    pub(crate) strict: bool,
+}
+
+/// Take `example = "…"` out of the `#[template]` of a variant, and return the variant's example:
+/// the value of `example`, or built from the fields' examples
+pub(crate) fn take(
+    meta: &mut TemplateArgs,
+    is_local: bool,
+    variant: &syn::Variant,
+) -> syn::Result<Option<syn::Expr>> {
+    let example = crate::take_variant_key(meta, "example", is_local, variant)?;
+    let field_examples = variant
+        .fields
+        .iter()
+        .map(FieldTransform::example)
+        .collect::<syn::Result<Vec<_>>>()?;
+    if let Some(example) = example {
+        return Ok(Some(example.parse()?));
+    }
+    if field_examples.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+    if let Some(field) = variant
+        .fields
+        .iter()
+        .zip(&field_examples)
+        .find(|(_, example)| example.is_none())
+        .map(|(field, _)| field)
+    {
+        let msg = "all fields need an `example`, or the variant needs an `example`";
+        return Err(syn::Error::new_spanned(field, msg));
+    }
+    let ident = &variant.ident;
+    let values = field_examples.into_iter().flatten();
+    Ok(Some(match &variant.fields {
+        syn::Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            parse_quote!(Self::#ident { #(#names: #values,)* })
+        }
+        _ => parse_quote!(Self::#ident(#(#values,)*)),
+    }))
+}
+
+/// `fn examples()` with `#[enum_template(examples)]`, which the variants' `example` needs
+pub(crate) fn make(
+    options: &EnumOptions,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> Result<Option<syn::ImplItem>, proc_macro::TokenStream> {
+    match &options.examples {
+        Some(examples) => make_examples(examples, ast, data, variant_definitions).map(Some),
+        None => match variant_definitions.iter().find_map(|d| d.example.as_ref()) {
+            Some(example) => Err(fail_at(
+                example,
+                "`example` needs #[enum_template(examples)]",
+            )),
+            None => Ok(None),
+        },
+    }
+}
+
+/// `fn examples() -> Vec<Self>`
+fn make_examples(
+    examples: &Examples,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> Result<syn::ImplItem, proc_macro::TokenStream> {
+    let mut values = Vec::new();
+    for (variant, definition) in data.variants.iter().zip(variant_definitions) {
+        let ident = &variant.ident;
+        match (&definition.example, &variant.fields) {
+            (Some(example), _) => values.push(quote!(#example)),
+            (None, syn::Fields::Unit) => values.push(quote!(Self::#ident)),
+            (None, _) if examples.strict => {
+                return Err(fail_at(
+                    ident,
+                    "`examples(strict)` needs an `example` for every variant with fields",
+                ));
+            }
+            (None, _) => {}
+        }
+    }
+    let vis = &ast.vis;
+    Ok(parse_quote! {
+        /// An instance of every unit variant, and of every variant with an `example`
+        #vis fn examples() -> ::std::vec::Vec<Self> {
+            ::std::vec![#(#values),*]
+        }
+    })
+}
@@ -0,0 +1,202 @@
+//! `source = include_str!("…")`, `source = concat!(…)`, `source_file = "…"` and `source_env = "…"`
+//!
+//! askama only accepts string literals, so the macros are expanded before the `source` is
+//! copied onto the generated struct.
+
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::Token;
+
+use crate::args::TemplateArgs;
+
+/// A file or an environment variable that a template was read from
+///
+/// The generated code mentions it, so the crate is rebuilt if it changes.
+pub(crate) enum Include {
+    /// The absolute path of a file
+    File(String),
+    /// The name of an environment variable
+    Env(syn::LitStr),
+}
+
+impl ToTokens for Include {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::File(path) => quote!(
+                const _: &[::std::primitive::u8] = ::std::include_bytes!(#path);
+            ),
+            Self::Env(name) => quote!(
+                const _: &::std::primitive::str = ::std::env!(#name);
+            ),
+        });
+    }
+}
+
+/// Replace a macro invocation in the `source` of `meta` with a string literal
+///
+/// `owner`, e.g. `MyEnum::Variant`, is used in errors about unreadable files. The included files
+/// and environment variables are returned, so the crate can be rebuilt if they change.
+pub(crate) fn expand_source(meta: &mut TemplateArgs, owner: &str) -> syn::Result<Vec<Include>> {
+    let mut includes = Vec::new();
+    if let Some(value) = meta.take_key("source_file") {
+        read_source_file(meta, &value, owner, &mut includes)?;
+    }
+    if let Some(value) = meta.take_key("source_env") {
+        read_source_env(meta, &value, owner, &mut includes)?;
+    }
+    let source = match meta.value_mut("source") {
+        Some(source @ syn::Expr::Macro(_)) => source,
+        _ => return Ok(includes),
+    };
+    let value = expand(source, owner, &mut includes)?;
+    *source = syn::parse_quote!(#value);
+    Ok(includes)
+}
+
+/// Read the file of `source_file = "…"` into a `source`, and use its extension as `ext`
+///
+/// A proc-macro cannot find out the file it was invoked from on the supported compilers, so the
+/// path is relative to the directory of the crate's `Cargo.toml`, like for `include_str!`.
+fn read_source_file(
+    meta: &mut TemplateArgs,
+    value: &syn::Expr,
+    owner: &str,
+    includes: &mut Vec<Include>,
+) -> syn::Result<()> {
+    let path = crate::args::lit_str(value)?;
+    if let Some(key) = ["source", "path", "source_env"]
+        .iter()
+        .find(|key| meta.has_key(key))
+    {
+        let msg = format!("`source_file` cannot be combined with `{}`", key);
+        return Err(syn::Error::new(path.span(), msg));
+    }
+    let full_path = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(path.value());
+    let source = std::fs::read_to_string(&full_path).map_err(|err| {
+        let msg = format!("{}: could not read {}: {}", owner, full_path.display(), err);
+        syn::Error::new(path.span(), msg)
+    })?;
+    includes.push(Include::File(full_path.display().to_string()));
+    let source = syn::LitStr::new(&source, path.span());
+    meta.push_value(
+        syn::Ident::new("source", path.span()),
+        syn::parse_quote!(#source),
+    );
+    let ext = full_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned());
+    if let (false, Some(ext)) = (meta.has_key("ext"), ext) {
+        let ext = syn::LitStr::new(&ext, path.span());
+        meta.push_value(syn::Ident::new("ext", path.span()), syn::parse_quote!(#ext));
+    }
+    Ok(())
+}
+
+/// Read the environment variable of `source_env = "…"` into a `source`
+///
+/// The variable is read when the crate is compiled, not when the template is rendered.
+fn read_source_env(
+    meta: &mut TemplateArgs,
+    value: &syn::Expr,
+    owner: &str,
+    includes: &mut Vec<Include>,
+) -> syn::Result<()> {
+    let name = crate::args::lit_str(value)?;
+    if let Some(key) = ["source", "path"].iter().find(|key| meta.has_key(key)) {
+        let msg = format!("`source_env` cannot be combined with `{}`", key);
+        return Err(syn::Error::new(name.span(), msg));
+    }
+    let source = std::env::var(name.value()).map_err(|err| {
+        let msg = format!(
+            "{}: could not read the environment variable {:?}: {}",
+            owner,
+            name.value(),
+            err,
+        );
+        syn::Error::new(name.span(), msg)
+    })?;
+    includes.push(Include::Env(name.clone()));
+    let source = syn::LitStr::new(&source, name.span());
+    meta.push_value(
+        syn::Ident::new("source", name.span()),
+        syn::parse_quote!(#source),
+    );
+    Ok(())
+}
+
+fn expand(expr: &syn::Expr, owner: &str, includes: &mut Vec<Include>) -> syn::Result<syn::LitStr> {
+    let mac = match expr {
+        syn::Expr::Macro(expr) => &expr.mac,
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => return Ok(lit.clone()),
+        expr => return Err(syn::Error::new(expr.span(), "expected a string literal")),
+    };
+    let name = mac.path.segments.last().map(|segment| &segment.ident);
+    let args = Punctuated::<syn::Expr, Token![,]>::parse_terminated.parse2(mac.tokens.clone())?;
+    let value = match name {
+        Some(name) if name == "include_str" => {
+            let path = match args.first() {
+                Some(path) if args.len() == 1 => crate::args::lit_str(path)?,
+                _ => {
+                    return Err(syn::Error::new(
+                        mac.path.span(),
+                        "`include_str!` expects exactly one string literal",
+                    ));
+                }
+            };
+            let full_path = std::env::var_os("CARGO_MANIFEST_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_default()
+                .join(path.value());
+            match std::fs::read_to_string(&full_path) {
+                Ok(value) => {
+                    includes.push(Include::File(full_path.display().to_string()));
+                    value
+                }
+                Err(err) => {
+                    let msg = format!("{}: could not read {:?}: {}", owner, path.value(), err);
+                    return Err(syn::Error::new(path.span(), msg));
+                }
+            }
+        }
+        Some(name) if name == "concat" => {
+            let mut value = String::new();
+            for arg in &args {
+                match arg {
+                    syn::Expr::Lit(syn::ExprLit { lit, .. }) => match lit {
+                        syn::Lit::Str(lit) => value.push_str(&lit.value()),
+                        syn::Lit::Char(lit) => value.push(lit.value()),
+                        syn::Lit::Int(lit) => value.push_str(lit.base10_digits()),
+                        syn::Lit::Float(lit) => value.push_str(lit.base10_digits()),
+                        syn::Lit::Bool(lit) => value.push_str(&lit.value.to_string()),
+                        lit => {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "`concat!` only accepts string, char, number and bool literals",
+                            ));
+                        }
+                    },
+                    arg => value.push_str(&expand(arg, owner, includes)?.value()),
+                }
+            }
+            value
+        }
+        _ => {
+            return Err(syn::Error::new(
+                mac.path.span(),
+                "only `include_str!(…)` and `concat!(…)` can be used in `source`",
+            ));
+        }
+    };
+    Ok(syn::LitStr::new(&value, expr.span()))
+}
@@ -0,0 +1,96 @@
+//! `#[template(feature = "whitelabel", …)]`: a `#[template]` of a variant that is used if a cargo
+//! feature is enabled
+//!
+//! The attributes are merged into one `#[template]` with `cfg(…)` alternatives, so the checks and the
+//! code generation of `cfg(…)` apply to them.
+
+use quote::quote;
+use syn::punctuated::Punctuated;
+
+use crate::args::{self, TemplateArg, TemplateArgs};
+use crate::is_template_attr;
+
+/// Merge the `#[template]` attributes of a variant, if any of them has a `feature`
+///
+/// The attributes with a `feature` are tried in declaration order, so the first one whose feature is
+/// enabled is used. The attribute without a `feature` is used if none of the features is enabled.
+pub(crate) fn merge(variant: &syn::Variant) -> syn::Result<Option<TemplateArgs>> {
+    let mut metas = Vec::new();
+    for attr in variant.attrs.iter().filter(|attr| is_template_attr(attr)) {
+        let mut meta = TemplateArgs::from_attr(attr)?;
+        let feature = match meta.take_key("feature") {
+            Some(feature) => Some(args::lit_str(&feature)?),
+            None => None,
+        };
+        metas.push((attr, feature, meta));
+    }
+    if metas.iter().all(|(_, feature, _)| feature.is_none()) {
+        return Ok(None);
+    }
+    let mut default = None;
+    let mut gated = Vec::<(syn::LitStr, TemplateArgs)>::new();
+    for (attr, feature, meta) in metas {
+        match feature {
+            Some(feature) => {
+                if let Some((first, _)) = gated.iter().find(|(f, _)| f.value() == feature.value()) {
+                    let mut err = syn::Error::new(
+                        feature.span(),
+                        "this feature is used by another #[template] of the variant",
+                    );
+                    err.combine(syn::Error::new(first.span(), "first used here"));
+                    return Err(err);
+                }
+                gated.push((feature, meta));
+            }
+            None if default.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    &attr.path,
+                    "a variant with feature-gated templates can have only one #[template] without \
+                    a `feature`",
+                ));
+            }
+            None => default = Some(meta),
+        }
+    }
+    let mut meta = match default {
+        Some(meta) => meta,
+        None => {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "a variant with feature-gated templates needs a #[template] without a `feature`, \
+                which is used if none of the features is enabled",
+            ));
+        }
+    };
+    if let Some(cfg) = meta.args.iter().find(|arg| arg.key() == "cfg") {
+        return Err(syn::Error::new(
+            cfg.key().span(),
+            "cannot combine `cfg(…)` with feature-gated templates",
+        ));
+    }
+
+    let features = gated.iter().map(|(feature, _)| feature).collect::<Vec<_>>();
+    for (index, (feature, gated_meta)) in gated.iter().enumerate() {
+        let earlier = &features[..index];
+        let predicate = quote!(all(feature = #feature, not(any(#(feature = #earlier),*))));
+        push_cfg(&mut meta, feature.span(), predicate, &gated_meta.args);
+    }
+    let span = variant.ident.span();
+    let predicate = quote!(not(any(#(feature = #features),*)));
+    push_cfg(&mut meta, span, predicate, &Punctuated::new());
+    Ok(Some(meta))
+}
+
+/// Add `cfg(predicate, args…)` to `meta`
+fn push_cfg(
+    meta: &mut TemplateArgs,
+    span: proc_macro2::Span,
+    predicate: proc_macro2::TokenStream,
+    args: &Punctuated<TemplateArg, syn::Token![,]>,
+) {
+    meta.args.push(TemplateArg::List {
+        key: syn::Ident::new("cfg", span),
+        paren_token: syn::token::Paren(span),
+        tokens: quote!(#predicate, #args),
+    });
+}
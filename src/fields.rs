@@ -0,0 +1,411 @@
+//! Field attributes `#[template_field(…)]`
+//!
+//! A field with such an attribute is not stored as a reference in the generated struct,
+//! but converted in the match arm, and stored as the result of the conversion.
+
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Token};
+
+use crate::EnumOptions;
+
+/// How to convert a field before it is handed to the template
+pub(crate) enum FieldTransform {
+    /// `#[template_field(base64)]`, `#[template_field(base64(url_safe))]`
+    Base64 { url_safe: bool },
+    /// `#[template_field(time_format = "…")]` for chrono types
+    ChronoFormat(syn::LitStr),
+    /// `#[template_field(time_format = "…")]` for time types
+    TimeFormat(syn::LitStr),
+    /// `#[template_field(sanitize)]`
+    Sanitize,
+    /// `#[template_field(markdown(tables, footnotes, rename = "…"))]`
+    Markdown {
+        tables: bool,
+        footnotes: bool,
+        rename: Option<syn::Ident>,
+    },
+    /// `#[template_field(render)]` for fields that implement `Template`
+    Render,
+}
+
+/// Where a field is converted
+pub(crate) struct FieldContext<'a> {
+    pub(crate) options: &'a EnumOptions,
+    /// `EnumName::VariantName`
+    pub(crate) variant_path: &'a str,
+    /// The name or index of the field
+    pub(crate) field: String,
+}
+
+/// The crate that a date or time type belongs to
+enum TimeCrate {
+    Chrono,
+    Time,
+}
+
+pub(crate) fn is_field_attr(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident("template_field")
+}
+
+/// Fail at the first `#[template_field]` of `variant`, for kinds of variants that have no use for it
+///
+/// `variants` names the kind in the error, e.g. "untagged variants".
+pub(crate) fn reject_field_attrs(variant: &syn::Variant, variants: &str) -> syn::Result<()> {
+    match variant
+        .fields
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .find(|attr| is_field_attr(attr))
+    {
+        Some(attr) => {
+            let msg = format!("cannot use #[template_field] in {}", variants);
+            Err(syn::Error::new(attr.path.span(), msg))
+        }
+        None => Ok(()),
+    }
+}
+
+impl FieldTransform {
+    /// Parse the `#[template_field(…)]` attribute of a field, if any
+    pub(crate) fn from_field(field: &syn::Field) -> syn::Result<Option<Self>> {
+        let mut result = None;
+        for attr in field.attrs.iter().filter(|attr| is_field_attr(attr)) {
+            let keys = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Meta, Token![,]>::parse_terminated,
+            )?;
+            for key in keys {
+                if key.path().is_ident("example") {
+                    continue;
+                }
+                if result.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "cannot have more than one conversion for a field",
+                    ));
+                }
+                result = Some(Self::from_meta(field, key)?);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Parse `#[template_field(example = "…")]`, the value of the field in `examples()`
+    pub(crate) fn example(field: &syn::Field) -> syn::Result<Option<syn::Expr>> {
+        let mut result = None;
+        for attr in field.attrs.iter().filter(|attr| is_field_attr(attr)) {
+            let keys = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Meta, Token![,]>::parse_terminated,
+            )?;
+            for key in keys
+                .into_iter()
+                .filter(|key| key.path().is_ident("example"))
+            {
+                let lit = match key {
+                    syn::Meta::NameValue(syn::MetaNameValue {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }) => lit,
+                    key => return Err(syn::Error::new(key.span(), "expected `example = \"…\"`")),
+                };
+                if result.is_some() {
+                    return Err(syn::Error::new(lit.span(), "duplicated key"));
+                }
+                result = Some(lit.parse()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn from_meta(field: &syn::Field, key: syn::Meta) -> syn::Result<Self> {
+        let path = key.path();
+        if path.is_ident("base64") {
+            let url_safe = match &key {
+                syn::Meta::Path(_) => false,
+                syn::Meta::List(list) => match list.nested.iter().collect::<Vec<_>>()[..] {
+                    [syn::NestedMeta::Meta(syn::Meta::Path(flag))] if flag.is_ident("url_safe") => {
+                        true
+                    }
+                    _ => return Err(syn::Error::new(list.span(), "expected `base64(url_safe)`")),
+                },
+                syn::Meta::NameValue(_) => {
+                    return Err(syn::Error::new(key.span(), "expected `base64(url_safe)`"));
+                }
+            };
+            if !cfg!(feature = "base64") {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "`base64` needs the feature `base64` of askama-enum",
+                ));
+            }
+            if !is_bytes(&field.ty) {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "`base64` needs a field of type `Vec<u8>`, `&[u8]` or `[u8; N]`",
+                ));
+            }
+            Ok(Self::Base64 { url_safe })
+        } else if path.is_ident("time_format") {
+            let format = match &key {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    lit: syn::Lit::Str(format),
+                    ..
+                }) => format.clone(),
+                key => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `time_format = \"…\"`",
+                    ))
+                }
+            };
+            match time_crate(&field.ty) {
+                Some(TimeCrate::Chrono) if cfg!(feature = "chrono") => Ok(Self::ChronoFormat(format)),
+                Some(TimeCrate::Time) if cfg!(feature = "time") => Ok(Self::TimeFormat(format)),
+                Some(TimeCrate::Chrono) => Err(syn::Error::new(
+                    path.span(),
+                    "`time_format` for chrono types needs the feature `chrono` of askama-enum",
+                )),
+                Some(TimeCrate::Time) => Err(syn::Error::new(
+                    path.span(),
+                    "`time_format` for time types needs the feature `time` of askama-enum",
+                )),
+                None => Err(syn::Error::new(
+                    path.span(),
+                    "`time_format` needs a field of a chrono type like `DateTime<Tz>` or `NaiveDate`, \
+                    or of a time type like `OffsetDateTime` or `Date`",
+                )),
+            }
+        } else if path.is_ident("sanitize") {
+            if !matches!(key, syn::Meta::Path(_)) {
+                return Err(syn::Error::new(key.span(), "expected `sanitize`"));
+            }
+            if !cfg!(feature = "ammonia") {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "`sanitize` needs the feature `ammonia` of askama-enum",
+                ));
+            }
+            Ok(Self::Sanitize)
+        } else if path.is_ident("markdown") {
+            let (mut tables, mut footnotes, mut rename) = (false, false, None);
+            match &key {
+                syn::Meta::Path(_) => {}
+                syn::Meta::List(list) => {
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::Path(flag))
+                                if flag.is_ident("tables") =>
+                            {
+                                tables = true;
+                            }
+                            syn::NestedMeta::Meta(syn::Meta::Path(flag))
+                                if flag.is_ident("footnotes") =>
+                            {
+                                footnotes = true;
+                            }
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                                path,
+                                lit: syn::Lit::Str(lit),
+                                ..
+                            })) if path.is_ident("rename") => {
+                                if field.ident.is_none() {
+                                    return Err(syn::Error::new(
+                                        path.span(),
+                                        "`rename` cannot be used for tuple fields",
+                                    ));
+                                }
+                                rename = Some(lit.parse()?);
+                            }
+                            nested => {
+                                return Err(syn::Error::new(
+                                    nested.span(),
+                                    "expected `tables`, `footnotes` or `rename = \"…\"`",
+                                ));
+                            }
+                        }
+                    }
+                }
+                syn::Meta::NameValue(_) => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `markdown(tables, footnotes, rename = \"…\")`",
+                    ));
+                }
+            }
+            if !cfg!(feature = "pulldown-cmark") {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "`markdown` needs the feature `pulldown-cmark` of askama-enum",
+                ));
+            }
+            Ok(Self::Markdown {
+                tables,
+                footnotes,
+                rename,
+            })
+        } else if path.is_ident("render") {
+            if !matches!(key, syn::Meta::Path(_)) {
+                return Err(syn::Error::new(key.span(), "expected `render`"));
+            }
+            Ok(Self::Render)
+        } else {
+            Err(syn::Error::new(path.span(), "unknown key"))
+        }
+    }
+
+    /// The type of the field in the generated struct
+    pub(crate) fn ty(&self) -> syn::Type {
+        match self {
+            Self::Base64 { .. }
+            | Self::ChronoFormat(_)
+            | Self::TimeFormat(_)
+            | Self::Sanitize
+            | Self::Markdown { .. }
+            | Self::Render => parse_quote!(::std::string::String),
+        }
+    }
+
+    /// The name of the field in the generated struct, if it differs from the enum's field
+    pub(crate) fn rename(&self) -> Option<&syn::Ident> {
+        match self {
+            Self::Markdown { rename, .. } => rename.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Convert the reference to the field in the enum into the field in the generated struct
+    ///
+    /// The expression is placed in a match arm of `render()`, so it can return an error.
+    pub(crate) fn convert(&self, value: &syn::Ident, ctx: &FieldContext<'_>) -> syn::Expr {
+        match self {
+            Self::Base64 { url_safe } => {
+                let engine = match url_safe {
+                    false => quote!(STANDARD),
+                    true => quote!(URL_SAFE),
+                };
+                parse_quote! {
+                    base64::Engine::encode(&base64::engine::general_purpose::#engine, #value)
+                }
+            }
+            Self::ChronoFormat(format) => {
+                let msg = format!(
+                    "could not format field `{}` of `{}` with {:?}",
+                    ctx.field,
+                    ctx.variant_path,
+                    format.value(),
+                );
+                parse_quote! {{
+                    let mut formatted = ::std::string::String::new();
+                    match ::std::fmt::Write::write_fmt(
+                        &mut formatted,
+                        ::std::format_args!("{}", #value.format(#format)),
+                    ) {
+                        ::std::result::Result::Ok(()) => formatted,
+                        ::std::result::Result::Err(_) => {
+                            return ::std::result::Result::Err(askama::Error::Custom(
+                                ::std::convert::From::from(#msg),
+                            ));
+                        }
+                    }
+                }}
+            }
+            Self::TimeFormat(format) => {
+                let msg = format!(
+                    "could not format field `{}` of `{}`: ",
+                    ctx.field, ctx.variant_path,
+                );
+                parse_quote! {
+                    match #value.format(time::macros::format_description!(#format)) {
+                        ::std::result::Result::Ok(formatted) => formatted,
+                        ::std::result::Result::Err(err) => {
+                            return ::std::result::Result::Err(askama::Error::Custom(
+                                ::std::convert::From::from(::std::format!("{}{}", #msg, err)),
+                            ));
+                        }
+                    }
+                }
+            }
+            Self::Sanitize => {
+                let text = quote!(::std::convert::AsRef::<::std::primitive::str>::as_ref(#value));
+                match &ctx.options.sanitize_config {
+                    Some(config) => parse_quote! {
+                        ::std::string::ToString::to_string(&(#config).clean(#text))
+                    },
+                    None => parse_quote!(ammonia::clean(#text)),
+                }
+            }
+            Self::Markdown {
+                tables, footnotes, ..
+            } => {
+                let tables = match tables {
+                    true => quote!(| pulldown_cmark::Options::ENABLE_TABLES),
+                    false => quote!(),
+                };
+                let footnotes = match footnotes {
+                    true => quote!(| pulldown_cmark::Options::ENABLE_FOOTNOTES),
+                    false => quote!(),
+                };
+                parse_quote! {{
+                    let parser = pulldown_cmark::Parser::new_ext(
+                        ::std::convert::AsRef::<::std::primitive::str>::as_ref(#value),
+                        pulldown_cmark::Options::empty() #tables #footnotes,
+                    );
+                    let mut html = ::std::string::String::new();
+                    pulldown_cmark::html::push_html(&mut html, parser);
+                    html
+                }}
+            }
+            Self::Render => parse_quote!(askama::Template::render(#value)?),
+        }
+    }
+}
+
+/// Whether the type (or the type it references) is a chrono or time type
+fn time_crate(ty: &syn::Type) -> Option<TimeCrate> {
+    match ty {
+        syn::Type::Group(group) => time_crate(&group.elem),
+        syn::Type::Paren(paren) => time_crate(&paren.elem),
+        syn::Type::Reference(reference) => time_crate(&reference.elem),
+        syn::Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let name = segment.ident.to_string();
+            match name.as_str() {
+                "DateTime" | "NaiveDate" | "NaiveDateTime" | "NaiveTime" => Some(TimeCrate::Chrono),
+                "OffsetDateTime" | "PrimitiveDateTime" | "UtcDateTime" | "Date" | "Time" => {
+                    Some(TimeCrate::Time)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `Vec<u8>`, `&[u8]` or `[u8; N]`
+fn is_bytes(ty: &syn::Type) -> bool {
+    let is_u8 = |ty: &syn::Type| matches!(ty, syn::Type::Path(p) if p.path.is_ident("u8"));
+    match ty {
+        syn::Type::Group(group) => is_bytes(&group.elem),
+        syn::Type::Paren(paren) => is_bytes(&paren.elem),
+        syn::Type::Array(array) => is_u8(&array.elem),
+        syn::Type::Reference(reference) => {
+            matches!(&*reference.elem, syn::Type::Slice(slice) if is_u8(&slice.elem))
+        }
+        syn::Type::Path(path) => {
+            let segment = match path.path.segments.last() {
+                Some(segment) if segment.ident == "Vec" => segment,
+                _ => return false,
+            };
+            match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => {
+                    match args.args.iter().collect::<Vec<_>>()[..] {
+                        [syn::GenericArgument::Type(ty)] => is_u8(ty),
+                        _ => false,
+                    }
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
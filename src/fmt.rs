@@ -0,0 +1,169 @@
+//! `#[template(fmt = "…")]` and `#[template(display = "field")]`: write the variant with `write!()`
+//! instead of an askama template
+
+use quote::format_ident;
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::args::{self, TemplateArgs};
+use crate::{delegate, fields};
+
+/// A format string whose placeholders are the fields of a variant
+pub(crate) struct Fmt {
+    /// The format string, with every field renamed to `_index`
+    pub(crate) format: syn::LitStr,
+    /// The fields used in `format`, and their names in it
+    pub(crate) fields: Vec<(syn::Member, syn::Ident)>,
+}
+
+impl Fmt {
+    /// Check the placeholders `{name}` or `{index}` of `lit` against the fields of `variant`
+    ///
+    /// `key` is the name of the key in the `#[template]` that `lit` belongs to, used in errors.
+    pub(crate) fn parse(variant: &syn::Variant, lit: &syn::LitStr, key: &str) -> syn::Result<Self> {
+        let value = lit.value();
+        let mut format = String::with_capacity(value.len());
+        let mut fields = Vec::<(syn::Member, syn::Ident)>::new();
+        let mut rest = value.as_str();
+        while let Some(pos) = rest.find(|c| c == '{' || c == '}') {
+            format.push_str(&rest[..=pos]);
+            let brace = rest.as_bytes()[pos];
+            rest = &rest[pos + 1..];
+            if rest.as_bytes().first() == Some(&brace) {
+                format.push(brace as char);
+                rest = &rest[1..];
+                continue;
+            }
+            if brace == b'}' {
+                let msg = format!(
+                    "unmatched `}}` in `{}`, use `}}}}` for a literal brace",
+                    key
+                );
+                return Err(syn::Error::new(lit.span(), msg));
+            }
+            let end = match rest.find('}') {
+                Some(end) => end,
+                None => {
+                    let msg = format!(
+                        "unmatched `{{` in `{}`, use `{{{{` for a literal brace",
+                        key
+                    );
+                    return Err(syn::Error::new(lit.span(), msg));
+                }
+            };
+            let (name, spec) = match rest[..end].find(':') {
+                Some(colon) => rest[..end].split_at(colon),
+                None => (&rest[..end], ""),
+            };
+            let name = name.trim();
+            let found =
+                variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .find(|(index, field)| match &field.ident {
+                        Some(ident) => crate::unraw(ident) == name,
+                        None => index.to_string() == name,
+                    });
+            let (index, field) = match found {
+                Some(found) => found,
+                None if name.is_empty() => {
+                    let msg = format!(
+                        "the placeholders in `{}` need a field, e.g. `{{name}}` or `{{0}}`",
+                        key,
+                    );
+                    return Err(syn::Error::new(lit.span(), msg));
+                }
+                None => {
+                    let msg = format!(
+                        "`{}` uses `{{{}}}`, but the variant has no such field",
+                        key, name,
+                    );
+                    return Err(syn::Error::new(lit.span(), msg));
+                }
+            };
+            let ident = format_ident!("_{}", index);
+            format.push_str(&ident.to_string());
+            format.push_str(spec);
+            format.push('}');
+            if !fields.iter().any(|(_, other)| *other == ident) {
+                let member = match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(index.into()),
+                };
+                fields.push((member, ident));
+            }
+            rest = &rest[end + 1..];
+        }
+        format.push_str(rest);
+        Ok(Self {
+            format: syn::LitStr::new(&format, lit.span()),
+            fields,
+        })
+    }
+}
+
+/// Take `display = "field"` and `fmt = "…"` out of the `#[template]` of a variant
+///
+/// Both write the variant without askama, so its template is replaced by an empty one.
+/// `own_source` is the key that gives the variant a template of its own, if any.
+pub(crate) fn take(
+    meta: &mut TemplateArgs,
+    variant: &syn::Variant,
+    own_source: Option<&str>,
+    delegated: bool,
+    skip: bool,
+) -> syn::Result<(Option<syn::Member>, Option<Fmt>)> {
+    let (key, value) = match (meta.take_key("display"), meta.take_key("fmt")) {
+        (Some(_), Some(value)) => {
+            return Err(syn::Error::new(
+                value.span(),
+                "cannot combine `display` with `fmt`",
+            ));
+        }
+        (Some(value), None) => ("display", value),
+        (None, Some(value)) => ("fmt", value),
+        (None, None) => return Ok((None, None)),
+    };
+    let lit = args::lit_str(&value)?;
+    if delegated {
+        let msg = format!("cannot combine `{}` with `delegate`", key);
+        return Err(syn::Error::new(lit.span(), msg));
+    }
+    if meta.has_key("delegate_with") || meta.has_key("with") || skip {
+        let msg = format!(
+            "cannot combine `{}` with `delegate_with`, `with` or `skip`",
+            key,
+        );
+        return Err(syn::Error::new(lit.span(), msg));
+    }
+    if let Some(own) = own_source {
+        let msg = match key {
+            "display" => format!(
+                "`display` writes the field with `Display`, so it cannot be combined with `{}`",
+                own,
+            ),
+            _ => format!(
+                "`fmt` writes the variant with `write!()`, so it cannot be combined with `{}`",
+                own,
+            ),
+        };
+        return Err(syn::Error::new(lit.span(), msg));
+    }
+    fields::reject_field_attrs(variant, &format!("`{}` variants", key))?;
+    let written = match key {
+        "display" => {
+            let (member, _) = delegate::find_field(variant, Some(&lit), lit.span())?;
+            (Some(member), None)
+        }
+        _ => (None, Some(Fmt::parse(variant, &lit, "fmt")?)),
+    };
+    meta.clear_source(lit.span());
+    if !meta.has_key("ext") {
+        meta.push_value(syn::Ident::new("ext", lit.span()), parse_quote!("txt"));
+    }
+    if !meta.has_key("escape") {
+        meta.push_value(syn::Ident::new("escape", lit.span()), parse_quote!("none"));
+    }
+    Ok(written)
+}
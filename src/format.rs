@@ -0,0 +1,116 @@
+//! `#[template(format = "txt", …)]`: more than one template per variant, and `render_as()`
+
+use quote::format_ident;
+use syn::parse_quote;
+
+use crate::args::{self, TemplateArgs};
+use crate::is_template_attr;
+
+/// The `#[template]` attributes of a variant with more than one format
+pub(crate) struct Formats {
+    /// The attribute that is used by `render()`, without its `format` key
+    pub(crate) default: TemplateArgs,
+    /// The format of `default`, if it has one
+    pub(crate) default_name: Option<syn::LitStr>,
+    /// The attributes of the other formats, without their `format` keys
+    pub(crate) others: Vec<(syn::LitStr, TemplateArgs)>,
+}
+
+impl Formats {
+    /// All formats of the variant, in the order of its attributes
+    pub(crate) fn names(&self) -> Vec<String> {
+        self.default_name
+            .iter()
+            .chain(self.others.iter().map(|(name, _)| name))
+            .map(syn::LitStr::value)
+            .collect()
+    }
+}
+
+/// Split the `#[template]` attributes of a variant into its formats
+///
+/// Returns `None` if no attribute has a `format`. The attribute without a `format` is used by
+/// `render()`, or the first attribute if all of them have one.
+pub(crate) fn split(variant: &syn::Variant) -> syn::Result<Option<Formats>> {
+    let mut unnamed = None;
+    let mut named = Vec::<(syn::LitStr, TemplateArgs)>::new();
+    for attr in variant.attrs.iter().filter(|attr| is_template_attr(attr)) {
+        let mut meta = TemplateArgs::from_attr(attr)?;
+        meta.resolve_path_candidates()?;
+        match meta.take_key("format") {
+            Some(name) => {
+                let name = args::lit_str(&name)?;
+                if named.iter().any(|(other, _)| other.value() == name.value()) {
+                    let msg = format!("the format {:?} is used twice", name.value());
+                    return Err(syn::Error::new(name.span(), msg));
+                }
+                named.push((name, meta));
+            }
+            None if unnamed.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    &attr.path,
+                    "a variant with more than one format can have only one #[template] without a \
+                    `format`, which is used by `render()`",
+                ));
+            }
+            None => unnamed = Some(meta),
+        }
+    }
+    if named.is_empty() {
+        return Ok(None);
+    }
+    let (default, default_name) = match unnamed {
+        Some(meta) => (meta, None),
+        None => {
+            let (name, meta) = named.remove(0);
+            (meta, Some(name))
+        }
+    };
+    Ok(Some(Formats {
+        default,
+        default_name,
+        others: named,
+    }))
+}
+
+/// The struct that renders `format` for the variant `helper`
+pub(crate) fn struct_name(helper: &syn::Ident, format: &str) -> syn::Ident {
+    let format = format
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    format_ident!("{}_format_{}", helper, format)
+}
+
+/// `fn render_as(&self, format: &str) -> Option<askama::Result<String>>`
+///
+/// `formats` is the `match` of `formats()`, `render_impls` render every format that any variant has.
+pub(crate) fn make_methods(
+    vis: &syn::Visibility,
+    formats: proc_macro2::TokenStream,
+    render_impls: Vec<(String, syn::ExprMatch)>,
+) -> Vec<syn::ImplItem> {
+    let (names, render_impls): (Vec<_>, Vec<_>) = render_impls.into_iter().unzip();
+    let render_as = parse_quote! {
+        /// Render the variant's template for `format`, or `None` if the variant has no such format
+        #vis fn render_as(
+            &self,
+            format: &::std::primitive::str,
+        ) -> ::std::option::Option<askama::Result<::std::string::String>> {
+            if !self.formats().contains(&format) {
+                return ::std::option::Option::None;
+            }
+            ::std::option::Option::Some(match format {
+                #(#names => #render_impls,)*
+                _ => askama::Template::render(self),
+            })
+        }
+    };
+    let formats = parse_quote! {
+        /// The names of the variant's `#[template(format = "…")]`, empty if it has only one template
+        #vis fn formats(&self) -> &'static [&'static ::std::primitive::str] {
+            #formats
+        }
+    };
+    vec![render_as, formats]
+}
@@ -0,0 +1,91 @@
+//! `#[template_for(variants(A, B, …), key = value, …)]`: one `#[template]` for a group of variants
+//!
+//! The groups are expanded before anything else looks at the enum: every listed variant gets the
+//! attribute `#[template(key = value, …)]`, as if it was written on the variant itself.
+
+use std::collections::HashMap;
+
+use syn::parse::Parser;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::Token;
+
+use crate::args::TemplateArgs;
+use crate::{is_template_attr, unraw};
+
+/// Add the `#[template]` of its group to every listed variant
+pub(crate) fn apply(ast: &mut syn::DeriveInput) -> syn::Result<()> {
+    let data = match &mut ast.data {
+        syn::Data::Enum(data) => data,
+        _ => return Ok(()),
+    };
+    let mut listed = HashMap::<String, syn::Ident>::new();
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("template_for"))
+    {
+        let mut meta = TemplateArgs::from_attr(attr)?;
+        let mut lists = meta.take_lists("variants").into_iter();
+        let list = match lists.next() {
+            Some(list) => list,
+            None => {
+                return Err(syn::Error::new(
+                    attr.path.span(),
+                    "#[template_for] needs a list of variants, e.g. `variants(Ok, Created)`",
+                ));
+            }
+        };
+        if let Some(other) = lists.next() {
+            return Err(syn::Error::new(
+                other.key.span(),
+                "#[template_for] can only have one `variants(…)` list",
+            ));
+        }
+        let names = Punctuated::<syn::Ident, Token![,]>::parse_terminated.parse2(list.tokens)?;
+        if names.is_empty() {
+            return Err(syn::Error::new(
+                list.key.span(),
+                "`variants(…)` needs at least one variant",
+            ));
+        }
+        for name in names {
+            let variant = data
+                .variants
+                .iter_mut()
+                .find(|variant| unraw(&variant.ident) == unraw(&name));
+            let variant = match variant {
+                Some(variant) => variant,
+                None => {
+                    let msg = format!("there is no variant `{}`", unraw(&name));
+                    return Err(syn::Error::new(name.span(), msg));
+                }
+            };
+            if let Some(first) = listed.get(&unraw(&name)) {
+                let msg = format!(
+                    "`{}` is listed in more than one #[template_for]",
+                    unraw(&name),
+                );
+                let mut err = syn::Error::new(name.span(), msg);
+                err.combine(syn::Error::new(first.span(), "first listed here"));
+                return Err(err);
+            }
+            if let Some(own) = variant.attrs.iter().find(|attr| is_template_attr(attr)) {
+                let msg = format!(
+                    "`{}` has a #[template] of its own, so it cannot be listed in #[template_for]",
+                    unraw(&name),
+                );
+                let mut err = syn::Error::new(name.span(), msg);
+                err.combine(syn::Error::new(
+                    own.path.span(),
+                    "the variant's own #[template] is here",
+                ));
+                return Err(err);
+            }
+            listed.insert(unraw(&name), name);
+            variant.attrs.push(parse_quote!(#[#meta]));
+        }
+    }
+    Ok(())
+}
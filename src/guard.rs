@@ -0,0 +1,119 @@
+//! `#[template(if = "…", …)]`: choose between the templates of a variant at runtime
+
+use quote::{format_ident, quote};
+
+use crate::args::{self, TemplateArgs};
+use crate::is_template_attr;
+
+/// The `#[template]` attributes of a variant with conditional templates
+pub(crate) struct Guards {
+    /// The unconditional attribute, which is used if no condition holds
+    pub(crate) default: TemplateArgs,
+    /// The conditional attributes in declaration order, without their `if` keys
+    pub(crate) others: Vec<(syn::LitStr, TemplateArgs)>,
+}
+
+/// Split the `#[template]` attributes of a variant into its conditional ones and the fallback
+///
+/// Returns `None` if no attribute has an `if`.
+pub(crate) fn split(variant: &syn::Variant) -> syn::Result<Option<Guards>> {
+    let mut metas = Vec::new();
+    for attr in variant.attrs.iter().filter(|attr| is_template_attr(attr)) {
+        let mut meta = TemplateArgs::from_attr(attr)?;
+        let condition = match meta.take_key("if") {
+            Some(condition) => Some(args::lit_str(&condition)?),
+            None => None,
+        };
+        metas.push((attr, condition, meta));
+    }
+    if metas.iter().all(|(_, condition, _)| condition.is_none()) {
+        return Ok(None);
+    }
+    let mut default = None;
+    let mut guarded = Vec::new();
+    for (attr, condition, meta) in metas {
+        match condition {
+            Some(condition) => {
+                parse(&condition)?;
+                if default.is_some() {
+                    return Err(syn::Error::new(
+                        condition.span(),
+                        "the #[template] without an `if` must come after all conditional ones",
+                    ));
+                }
+                guarded.push((condition, meta));
+            }
+            None if default.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    &attr.path,
+                    "a variant with conditional templates can have only one #[template] without \
+                    an `if`",
+                ));
+            }
+            None => default = Some(meta),
+        }
+    }
+    let default = match default {
+        Some(default) => default,
+        None => {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "a variant with conditional templates needs a #[template] without an `if`, \
+                which is used if no condition holds",
+            ));
+        }
+    };
+    Ok(Some(Guards {
+        default,
+        others: guarded,
+    }))
+}
+
+/// The condition of `if = "…"`
+pub(crate) fn parse(condition: &syn::LitStr) -> syn::Result<syn::Expr> {
+    condition.parse().map_err(|err| {
+        let msg = format!("the condition is not a valid expression: {}", err);
+        syn::Error::new(condition.span(), msg)
+    })
+}
+
+/// The struct that renders the `index`th conditional template of the variant `helper`
+pub(crate) fn struct_name(helper: &syn::Ident, index: usize) -> syn::Ident {
+    format_ident!("{}_if_{}", helper, index)
+}
+
+/// `if condition { then } else { otherwise }`, where `condition` can use the fields of `variant`
+///
+/// The fields are bound to `_0`, `_1`, … by `make_render_impl()`. Named fields are available by
+/// their name, too.
+pub(crate) fn make_branch(
+    variant: &syn::Variant,
+    condition: &syn::Expr,
+    then: syn::Expr,
+    otherwise: syn::Expr,
+) -> syn::Expr {
+    let bindings = bind_fields(variant);
+    syn::Expr::Verbatim(quote!({
+        let condition: ::std::primitive::bool = {
+            #bindings
+            #condition
+        };
+        if condition { #then } else { #otherwise }
+    }))
+}
+
+/// Make the named fields of `variant`, which are bound to `_0`, `_1`, …, available by their name
+pub(crate) fn bind_fields(variant: &syn::Variant) -> proc_macro2::TokenStream {
+    let (names, tmp_names): (Vec<_>, Vec<_>) = variant
+        .fields
+        .iter()
+        .enumerate()
+        .filter_map(|(index, field)| Some((field.ident.as_ref()?, format_ident!("_{}", index))))
+        .unzip();
+    quote! {
+        #(
+            #[allow(unused_variables)]
+            let #names = #tmp_names;
+        )*
+    }
+}
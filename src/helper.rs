@@ -0,0 +1,344 @@
+//! The helper structs that borrow the fields of a variant, and derive `askama::Template`
+
+use std::iter::FromIterator;
+
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Token};
+
+use crate::fields::{self, FieldTransform};
+use crate::{discriminant, Computed};
+
+/// The helper struct of a variant
+pub(crate) struct Helper<'a> {
+    pub(crate) ast: &'a syn::DeriveInput,
+    pub(crate) variant: &'a syn::Variant,
+    /// The name of the struct, e.g. `_MyEnum_0_Variant`
+    pub(crate) name: &'a syn::Ident,
+    /// The lifetime of the borrowed fields
+    pub(crate) lifetime: &'a syn::Lifetime,
+}
+
+/// The fields that are added to the fields of the variant
+pub(crate) struct Extra<'a> {
+    /// The values of `#[enum_template(compute(…))]`
+    pub(crate) computed: &'a [Computed],
+    /// The name of the field of `expose_enum = "…"`
+    pub(crate) outer: Option<&'a syn::Ident>,
+    /// The type of the field `discriminant` of `expose_discriminant`
+    pub(crate) discriminant: Option<&'a syn::Type>,
+}
+
+impl Helper<'_> {
+    /// `PhantomData<&'lt MyEnum<…>>`, so the struct uses its lifetime and the enum's generics
+    fn phantom_type(&self) -> syn::Type {
+        let lifetime = self.lifetime;
+        let enum_name = &self.ast.ident;
+        let (_, ty_generics, _) = self.ast.generics.split_for_impl();
+        parse_quote!(::std::marker::PhantomData::<
+            & #lifetime #enum_name #ty_generics,
+        >)
+    }
+
+    /// The fields of a struct whose template does not use the fields of the variant
+    pub(crate) fn empty_fields(&self) -> syn::Fields {
+        let phantom_type = self.phantom_type();
+        syn::Fields::Unnamed(parse_quote!((#phantom_type)))
+    }
+
+    /// The fields of a `delegate` variant with an inline `source`, which gets the rendered field as
+    /// `inner`
+    pub(crate) fn delegate_fields(
+        &self,
+        wrapper: &syn::Ident,
+        field_transforms: &[Option<FieldTransform>],
+    ) -> syn::Result<syn::Fields> {
+        if field_transforms[0].is_some() {
+            return Err(syn::Error::new(
+                wrapper.span(),
+                "cannot use #[template_field] in `delegate` variants",
+            ));
+        }
+        let (name, phantom_type) = (self.name, self.phantom_type());
+        Ok(syn::Fields::Named(parse_quote!({
+            inner: ::std::string::String,
+            #name: #phantom_type,
+        })))
+    }
+
+    /// References to the fields of the variant, or the results of their `#[template_field]`, and
+    /// the `extra` fields
+    pub(crate) fn fields(
+        &self,
+        extra: &Extra<'_>,
+        field_transforms: &[Option<FieldTransform>],
+    ) -> syn::Result<syn::Fields> {
+        let Extra {
+            computed,
+            outer,
+            discriminant,
+        } = *extra;
+        let variant = self.variant;
+        let variant_span = variant.ident.span();
+        let discriminant_name = syn::Ident::new("discriminant", variant_span);
+        let computed_names = || computed.iter().map(|computed| &computed.name);
+        let mut extra_fields = computed.iter().map(Computed::field).collect::<Vec<_>>();
+        extra_fields.extend(outer.map(|outer| outer_field(outer, self.lifetime, self.ast)));
+        if let Some(ty) = discriminant {
+            extra_fields.push(discriminant::field(&discriminant_name, ty));
+        }
+        Ok(match &variant.fields {
+            syn::Fields::Named(fields) => {
+                let names = fields
+                    .named
+                    .iter()
+                    .zip(field_transforms)
+                    .filter_map(|(field, transform)| {
+                        transform
+                            .as_ref()
+                            .and_then(FieldTransform::rename)
+                            .or(field.ident.as_ref())
+                    })
+                    .collect::<Vec<_>>();
+                check_computed_collisions(computed, names.iter().copied())?;
+                if let Some(outer) = outer {
+                    check_outer_collision(outer, names.iter().copied().chain(computed_names()))?;
+                }
+                if discriminant.is_some() {
+                    check_discriminant_collision(
+                        &discriminant_name,
+                        names.into_iter().chain(computed_names()).chain(outer),
+                    )?;
+                }
+                let mut fields = self.borrowed_fields(&fields.named, field_transforms);
+                fields.extend(extra_fields);
+                fields.push(syn::Field {
+                    attrs: vec![],
+                    vis: syn::Visibility::Inherited,
+                    ident: Some(self.name.clone()),
+                    colon_token: Some(Token![:](variant_span)),
+                    ty: self.phantom_type(),
+                });
+                syn::Fields::Named(syn::FieldsNamed {
+                    brace_token: syn::token::Brace(variant_span),
+                    named: Punctuated::from_iter(fields),
+                })
+            }
+            syn::Fields::Unnamed(_) if outer.is_some() => {
+                return Err(syn::Error::new(
+                    variant_span,
+                    "`expose_enum` cannot be used in tuple variants, \
+                    only in variants with named fields and unit variants; \
+                    use `expose_enum = \"\"` in the variant's #[template] to opt out",
+                ));
+            }
+            syn::Fields::Unnamed(_) if discriminant.is_some() => {
+                return Err(syn::Error::new(
+                    variant_span,
+                    "`expose_discriminant` cannot be used in tuple variants, \
+                    only in variants with named fields and unit variants; \
+                    use `expose_discriminant = false` in the variant's #[template] to opt out",
+                ));
+            }
+            syn::Fields::Unnamed(_) if !computed.is_empty() => {
+                return Err(syn::Error::new(
+                    variant_span,
+                    "computed values cannot be added to tuple variants, \
+                    only to variants with named fields and unit variants",
+                ));
+            }
+            syn::Fields::Unnamed(fields) => {
+                let mut fields = self.borrowed_fields(&fields.unnamed, field_transforms);
+                fields.push(syn::Field {
+                    attrs: vec![],
+                    vis: syn::Visibility::Inherited,
+                    ident: None,
+                    colon_token: None,
+                    ty: self.phantom_type(),
+                });
+                syn::Fields::Unnamed(syn::FieldsUnnamed {
+                    paren_token: syn::token::Paren(variant_span),
+                    unnamed: Punctuated::from_iter(fields),
+                })
+            }
+            syn::Fields::Unit if !extra_fields.is_empty() => {
+                if let Some(outer) = outer {
+                    check_outer_collision(outer, computed_names())?;
+                }
+                if discriminant.is_some() {
+                    check_discriminant_collision(
+                        &discriminant_name,
+                        computed_names().chain(outer),
+                    )?;
+                }
+                extra_fields.push(syn::Field {
+                    attrs: vec![],
+                    vis: syn::Visibility::Inherited,
+                    ident: Some(self.name.clone()),
+                    colon_token: Some(Token![:](variant_span)),
+                    ty: self.phantom_type(),
+                });
+                syn::Fields::Named(syn::FieldsNamed {
+                    brace_token: syn::token::Brace(variant_span),
+                    named: Punctuated::from_iter(extra_fields),
+                })
+            }
+            syn::Fields::Unit => syn::Fields::Unnamed(syn::FieldsUnnamed {
+                paren_token: syn::token::Paren(variant_span),
+                unnamed: Punctuated::from_iter([syn::Field {
+                    attrs: vec![],
+                    vis: syn::Visibility::Inherited,
+                    ident: None,
+                    colon_token: None,
+                    ty: self.phantom_type(),
+                }]),
+            }),
+        })
+    }
+
+    /// The fields of the variant as references, or as the results of their `#[template_field]`
+    fn borrowed_fields(
+        &self,
+        fields: &Punctuated<syn::Field, Token![,]>,
+        field_transforms: &[Option<FieldTransform>],
+    ) -> Vec<syn::Field> {
+        fields
+            .iter()
+            .zip(field_transforms)
+            .map(|(field, transform)| {
+                let mut field = field.clone();
+                field.attrs.retain(|attr| !fields::is_field_attr(attr));
+                if let Some(rename) = transform.as_ref().and_then(FieldTransform::rename) {
+                    field.ident = Some(rename.clone());
+                }
+                field.ty = match transform {
+                    Some(transform) => transform.ty(),
+                    None => syn::Type::Reference(syn::TypeReference {
+                        and_token: Token![&](field.span()),
+                        lifetime: Some(self.lifetime.clone()),
+                        mutability: None,
+                        elem: field.ty.into(),
+                    }),
+                };
+                field
+            })
+            .collect()
+    }
+
+    /// The struct with `fields` that derives `askama::Template` with the template of `attrs`
+    pub(crate) fn item(
+        &self,
+        fields: syn::Fields,
+        template_attrs: Vec<syn::Attribute>,
+        is_keyed: bool,
+    ) -> syn::DeriveInput {
+        let lifetime = self.lifetime;
+        let variant_span = self.variant.ident.span();
+        let mut generics = self.ast.generics.clone();
+        generics.params.push(parse_quote!(#lifetime));
+        // No other derives: their implicit bounds could not be satisfied for fields like
+        // `&'lt <T as Trait>::Assoc<'a>`, and the structs are private anyway.
+        let mut attrs = vec![parse_quote!(#[::std::prelude::v1::derive(askama::Template)])];
+        if is_keyed {
+            // the fallback template of `key = "…"` usually has no use for the key field
+            attrs.push(parse_quote!(#[allow(dead_code)]));
+        }
+        attrs.extend(template_attrs);
+        syn::DeriveInput {
+            attrs,
+            vis: syn::Visibility::Inherited,
+            ident: self.name.clone(),
+            generics,
+            data: syn::Data::Struct(syn::DataStruct {
+                struct_token: Token![struct](variant_span),
+                fields,
+                semi_token: None,
+            }),
+        }
+    }
+}
+
+/// A copy of the struct `item` named `ident`, with another template, e.g. the one of a locale
+///
+/// `allow_dead_code` is for templates that usually use only some of the fields.
+pub(crate) fn sibling(
+    item: &syn::DeriveInput,
+    ident: syn::Ident,
+    allow_dead_code: bool,
+    template_attrs: Vec<syn::Attribute>,
+) -> syn::DeriveInput {
+    let mut sibling = item.clone();
+    sibling.ident = ident;
+    sibling.attrs.truncate(1);
+    if allow_dead_code {
+        sibling.attrs.push(parse_quote!(#[allow(dead_code)]));
+    }
+    sibling.attrs.extend(template_attrs);
+    sibling
+}
+
+/// Computed values must not shadow the variant's fields
+pub(crate) fn check_computed_collisions<'a>(
+    computed: &[Computed],
+    names: impl IntoIterator<Item = &'a syn::Ident>,
+) -> syn::Result<()> {
+    for name in names {
+        if let Some(computed) = computed.iter().find(|computed| computed.name == *name) {
+            let msg = format!("computed value `{}` collides with a field", name);
+            let mut err = syn::Error::new(computed.name.span(), &msg);
+            err.combine(syn::Error::new(name.span(), &msg));
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// `outer: &'lt MyEnum<…>`, the field of `#[template(expose_enum = "outer")]`
+pub(crate) fn outer_field(
+    outer: &syn::Ident,
+    lifetime: &syn::Lifetime,
+    ast: &syn::DeriveInput,
+) -> syn::Field {
+    let enum_name = &ast.ident;
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    syn::Field {
+        attrs: vec![],
+        vis: syn::Visibility::Inherited,
+        ident: Some(outer.clone()),
+        colon_token: Some(Token![:](outer.span())),
+        ty: parse_quote!(&#lifetime #enum_name #ty_generics),
+    }
+}
+
+/// The field of `expose_enum` must not shadow the variant's fields or computed values
+pub(crate) fn check_outer_collision<'a>(
+    outer: &syn::Ident,
+    names: impl IntoIterator<Item = &'a syn::Ident>,
+) -> syn::Result<()> {
+    for name in names {
+        if name == outer {
+            let msg = format!("`expose_enum = \"{}\"` collides with a field", outer);
+            let mut err = syn::Error::new(outer.span(), &msg);
+            err.combine(syn::Error::new(name.span(), &msg));
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// The field of `expose_discriminant` must not shadow the variant's fields or other added fields
+fn check_discriminant_collision<'a>(
+    discriminant: &syn::Ident,
+    names: impl IntoIterator<Item = &'a syn::Ident>,
+) -> syn::Result<()> {
+    for name in names {
+        if name == discriminant {
+            return Err(syn::Error::new(
+                name.span(),
+                "`expose_discriminant` collides with a field named `discriminant`, \
+                use `expose_discriminant = false` in the variant's #[template] to opt out",
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! `hot-reload`: render changed `path` templates at runtime in debug builds
+//!
+//! The runtime only understands raw text and `{{ name }}` expressions. Everything else makes the
+//! generated code fall back to the template that was compiled into the binary.
+
+use quote::quote;
+
+use crate::{make_static_ty_generics, match_variants, unraw, EnumOptions, VariantDefinition};
+
+/// The module with the runtime, needs to be put into the `const _` block once
+pub(crate) fn make_runtime() -> proc_macro2::TokenStream {
+    quote! {
+        #[cfg(debug_assertions)]
+        mod __askama_enum_hot_reload {
+            /// Stringifies `T` if it implements `Display`, see [`ViaDisplay`] and [`Fallback`]
+            pub(super) struct Value<'a, T: ?::std::marker::Sized>(pub(super) &'a T);
+
+            pub(super) trait ViaDisplay {
+                fn hot_reload_value(&self) -> ::std::option::Option<::std::string::String>;
+            }
+
+            impl<T: ::std::fmt::Display + ?::std::marker::Sized> ViaDisplay for &Value<'_, T> {
+                fn hot_reload_value(&self) -> ::std::option::Option<::std::string::String> {
+                    ::std::option::Option::Some(::std::string::ToString::to_string(self.0))
+                }
+            }
+
+            pub(super) trait Fallback {
+                fn hot_reload_value(&self) -> ::std::option::Option<::std::string::String>;
+            }
+
+            impl<T: ?::std::marker::Sized> Fallback for Value<'_, T> {
+                fn hot_reload_value(&self) -> ::std::option::Option<::std::string::String> {
+                    ::std::option::Option::None
+                }
+            }
+
+            /// Render `path` if it is newer than the running binary
+            pub(super) fn render(
+                path: &::std::primitive::str,
+                escape: ::std::option::Option<::std::primitive::bool>,
+                lookup: impl ::std::ops::Fn(
+                    &::std::primitive::str,
+                ) -> ::std::option::Option<::std::string::String>,
+            ) -> ::std::option::Option<::std::string::String> {
+                let escape = escape?;
+                let root = match ::std::env::var_os("ASKAMA_ENUM_TEMPLATE_DIR") {
+                    ::std::option::Option::Some(root) => ::std::path::PathBuf::from(root),
+                    ::std::option::Option::None => {
+                        ::std::path::Path::new(::std::env!("CARGO_MANIFEST_DIR")).join("templates")
+                    }
+                };
+                let path = root.join(path);
+                if ::std::env::var_os("ASKAMA_ENUM_HOT_RELOAD_ALWAYS").is_none() {
+                    let modified = ::std::fs::metadata(&path).ok()?.modified().ok()?;
+                    let exe = ::std::env::current_exe().ok()?;
+                    let compiled = ::std::fs::metadata(exe).ok()?.modified().ok()?;
+                    if modified <= compiled {
+                        return ::std::option::Option::None;
+                    }
+                }
+                let mut source = ::std::fs::read_to_string(&path).ok()?;
+                if source.ends_with('\n') {
+                    source.pop();
+                }
+                interpret(&source, escape, lookup)
+            }
+
+            fn interpret(
+                source: &::std::primitive::str,
+                escape: ::std::primitive::bool,
+                lookup: impl ::std::ops::Fn(
+                    &::std::primitive::str,
+                ) -> ::std::option::Option<::std::string::String>,
+            ) -> ::std::option::Option<::std::string::String> {
+                let is_raw = |text: &::std::primitive::str| {
+                    !text.contains("{%") && !text.contains("{#") && !text.contains("}}")
+                };
+                let mut output = ::std::string::String::with_capacity(source.len());
+                let mut rest = source;
+                while let ::std::option::Option::Some(start) = rest.find("{{") {
+                    let (text, expr) = rest.split_at(start);
+                    if !is_raw(text) {
+                        return ::std::option::Option::None;
+                    }
+                    output.push_str(text);
+                    let end = expr.find("}}")?;
+                    let value = lookup(expr[2..end].trim())?;
+                    match escape {
+                        true => {
+                            for c in value.chars() {
+                                match c {
+                                    '<' => output.push_str("&lt;"),
+                                    '>' => output.push_str("&gt;"),
+                                    '&' => output.push_str("&amp;"),
+                                    '"' => output.push_str("&quot;"),
+                                    '\'' => output.push_str("&#x27;"),
+                                    c => output.push(c),
+                                }
+                            }
+                        }
+                        false => output.push_str(&value),
+                    }
+                    rest = &expr[end + 2..];
+                }
+                if !is_raw(rest) {
+                    return ::std::option::Option::None;
+                }
+                output.push_str(rest);
+                ::std::option::Option::Some(output)
+            }
+        }
+    }
+}
+
+/// Whether `render()` reads the variant's template at runtime
+///
+/// The runtime only replaces the compiled template, so variants whose output is chosen or changed
+/// by anything else, e.g. `if = "…"` guards, `trim` or `newlines`, always use the compiled template.
+pub(crate) fn reloads(options: &EnumOptions, definition: &VariantDefinition) -> bool {
+    cfg!(feature = "hot-reload")
+        && definition.template_path.is_some()
+        && definition.guards.is_empty()
+        && definition.cfg_alternatives.is_empty()
+        && definition.alternate.is_none()
+        && !definition.trim
+        && !definition.minify
+        && definition.postprocess.is_none()
+        && definition.memoize.is_none()
+        && options.newlines.is_none()
+        && options.memoize.is_none()
+        && options.metrics.is_none()
+        && !options.coverage()
+        && !options.error_context
+        && options.check_size_hint.is_none()
+        && !options.validate_json
+        && options.default_locale.is_none()
+        && !options.unescaped
+}
+
+/// An expression of type `Option<String>`, `Some(…)` if a changed template could be rendered
+pub(crate) fn make_hot_reload(
+    ast: &syn::DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> proc_macro2::TokenStream {
+    let static_ty_generics = make_static_ty_generics(ast);
+    let paths = match_variants(data, variant_definitions, |definition| {
+        let path = match &definition.template_path {
+            Some(path) if reloads(options, definition) => {
+                quote!(::std::option::Option::Some(#path))
+            }
+            _ => return quote!((::std::option::Option::None, ::std::option::Option::None)),
+        };
+        let escape = match definition
+            .escape
+            .as_ref()
+            .map(syn::LitStr::value)
+            .as_deref()
+        {
+            Some("none") => quote!(::std::option::Option::Some(false)),
+            Some("html") => quote!(::std::option::Option::Some(true)),
+            Some(_) => quote!(::std::option::Option::None),
+            None => {
+                let ty = definition.template_type(&static_ty_generics);
+                quote!(::std::option::Option::Some(::std::matches!(
+                    <#ty as askama::Template>::EXTENSION,
+                    ::std::option::Option::Some("html" | "htm" | "xml"),
+                )))
+            }
+        };
+        quote!((#path, #escape))
+    });
+
+    let arms = data
+        .variants
+        .iter()
+        .zip(variant_definitions)
+        .map(|(variant, definition)| {
+            let ident = &variant.ident;
+            let names = match (&options.untagged, &definition.item) {
+                (Some(untagged), None)
+                    if definition.transparent.is_none() && definition.with.is_none() => {
+                    let value = unraw(&untagged.value);
+                    let label = unraw(ident);
+                    return quote! {
+                        Self::#ident(_0) => match name {
+                            #value => (&&__askama_enum_hot_reload::Value(_0)).hot_reload_value(),
+                            "__variant" => ::std::option::Option::Some(::std::string::ToString::to_string(#label)),
+                            _ => ::std::option::Option::None,
+                        },
+                    };
+                }
+                _ => variant
+                    .fields
+                    .iter()
+                    .zip(&definition.field_transforms)
+                    .enumerate()
+                    .filter(|(_, (_, transform))| transform.is_none())
+                    .map(|(index, (field, _))| match &field.ident {
+                        Some(ident) => {
+                            let name = unraw(ident);
+                            let self_name = format!("self.{}", name);
+                            (index, quote!(#name | #self_name))
+                        }
+                        None => {
+                            let self_name = format!("self.{}", index);
+                            (index, quote!(#self_name))
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            };
+            let tmp = names
+                .iter()
+                .map(|(index, _)| syn::Ident::new(&format!("_{}", index), ident.span()))
+                .collect::<Vec<_>>();
+            let members = names.iter().map(|(index, _)| {
+                match &variant.fields.iter().nth(*index).unwrap().ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed((*index).into()),
+                }
+            });
+            let patterns = names.iter().map(|(_, pattern)| pattern);
+            quote! {
+                Self::#ident { #(#members: #tmp,)* .. } => match name {
+                    #(
+                        #patterns => (&&__askama_enum_hot_reload::Value(#tmp)).hot_reload_value(),
+                    )*
+                    _ => ::std::option::Option::None,
+                },
+            }
+        });
+
+    quote! {{
+        #[allow(unused_imports)]
+        use __askama_enum_hot_reload::{Fallback as _, ViaDisplay as _};
+
+        let (path, escape): (
+            ::std::option::Option<&::std::primitive::str>,
+            ::std::option::Option<::std::primitive::bool>,
+        ) = #paths;
+        match path {
+            ::std::option::Option::Some(path) => {
+                __askama_enum_hot_reload::render(path, escape, |name| match self {
+                    #(#arms)*
+                })
+            }
+            ::std::option::Option::None => ::std::option::Option::None,
+        }
+    }}
+}
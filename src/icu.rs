@@ -0,0 +1,143 @@
+//! `#[enum_template(icu)]`: locale-aware formatting of numbers and dates in the templates
+
+use quote::quote;
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::Computed;
+
+/// The name of the formatter in the templates
+const NAME: &str = "fmt";
+
+/// The formatter, stored in every generated struct like a computed value
+pub(crate) fn computed(path: &syn::Path) -> Computed {
+    Computed {
+        name: syn::Ident::new(NAME, path.span()),
+        expr: parse_quote!(icu_formatter()?),
+        ty: Some(parse_quote!(::std::rc::Rc<IcuFormatter>)),
+    }
+}
+
+/// The formatter type and the current formatter, need to be put into the `const _` block once
+pub(crate) fn make_runtime() -> proc_macro2::TokenStream {
+    let integers = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ]
+    .iter()
+    .map(|name| syn::Ident::new(name, proc_macro2::Span::call_site()));
+    quote! {
+        /// Formats numbers and dates for one locale, available as `fmt` in the templates
+        struct IcuFormatter {
+            decimal: icu_decimal::DecimalFormatter,
+            date: icu_datetime::DateTimeFormatter<icu_datetime::fieldsets::YMD>,
+        }
+
+        impl IcuFormatter {
+            fn new(locale: &icu_locale_core::Locale) -> askama::Result<Self> {
+                let error = |err: &dyn ::std::fmt::Display| {
+                    askama::Error::Custom(::std::convert::From::from(::std::format!(
+                        "could not load the formatters for {}: {}",
+                        locale,
+                        err,
+                    )))
+                };
+                let decimal = icu_decimal::DecimalFormatter::try_new(
+                    ::std::convert::From::from(locale),
+                    ::std::default::Default::default(),
+                )
+                .map_err(|err| error(&err))?;
+                let date = icu_datetime::DateTimeFormatter::try_new(
+                    ::std::convert::From::from(locale),
+                    icu_datetime::fieldsets::YMD::medium(),
+                )
+                .map_err(|err| error(&err))?;
+                ::std::result::Result::Ok(Self { decimal, date })
+            }
+
+            /// `{{ fmt.decimal(self.count) }}`, e.g. "1,234,567" or "1.234.567"
+            #[allow(dead_code)]
+            fn decimal<T: IcuInteger + ?::std::marker::Sized>(
+                &self,
+                value: &T,
+            ) -> ::std::string::String {
+                let value = value.to_icu_decimal();
+                ::std::string::ToString::to_string(&self.decimal.format(&value))
+            }
+
+            /// `{{ fmt.date(self.when) }}`, e.g. "Mar 5, 2024" or "05.03.2024"
+            #[allow(dead_code)]
+            fn date(
+                &self,
+                value: &icu_calendar::Date<icu_calendar::Iso>,
+            ) -> ::std::string::String {
+                ::std::string::ToString::to_string(&self.date.format(value))
+            }
+        }
+
+        /// An integer, or a reference to one, since askama passes the fields by reference
+        trait IcuInteger {
+            fn to_icu_decimal(&self) -> icu_decimal::input::Decimal;
+        }
+
+        impl<T: IcuInteger + ?::std::marker::Sized> IcuInteger for &T {
+            fn to_icu_decimal(&self) -> icu_decimal::input::Decimal {
+                T::to_icu_decimal(self)
+            }
+        }
+
+        #(
+            impl IcuInteger for ::std::primitive::#integers {
+                fn to_icu_decimal(&self) -> icu_decimal::input::Decimal {
+                    ::std::convert::From::from(*self)
+                }
+            }
+        )*
+
+        ::std::thread_local! {
+            /// The formatter of `render_in()`, or the formatter for the unknown locale
+            static ICU_FORMATTER: ::std::cell::RefCell<
+                ::std::option::Option<::std::rc::Rc<IcuFormatter>>,
+            > = ::std::cell::RefCell::new(::std::option::Option::None);
+        }
+
+        fn icu_formatter() -> askama::Result<::std::rc::Rc<IcuFormatter>> {
+            ICU_FORMATTER.with(|current| {
+                let mut current = current.borrow_mut();
+                if let ::std::option::Option::Some(formatter) = &*current {
+                    return ::std::result::Result::Ok(::std::rc::Rc::clone(formatter));
+                }
+                let formatter = ::std::rc::Rc::new(IcuFormatter::new(
+                    &icu_locale_core::Locale::UNKNOWN,
+                )?);
+                *current = ::std::option::Option::Some(::std::rc::Rc::clone(&formatter));
+                ::std::result::Result::Ok(formatter)
+            })
+        }
+    }
+}
+
+/// `fn render_in(&self, locale) -> askama::Result<String>`
+pub(crate) fn make_render_in(vis: &syn::Visibility) -> syn::ImplItem {
+    parse_quote! {
+        /// Render the template with number and date formatting for `locale`
+        #vis fn render_in(
+            &self,
+            locale: &icu_locale_core::Locale,
+        ) -> askama::Result<::std::string::String> {
+            struct Restore(::std::option::Option<::std::rc::Rc<IcuFormatter>>);
+
+            impl ::std::ops::Drop for Restore {
+                fn drop(&mut self) {
+                    let previous = self.0.take();
+                    ICU_FORMATTER.with(|current| *current.borrow_mut() = previous);
+                }
+            }
+
+            let formatter = ::std::rc::Rc::new(IcuFormatter::new(locale)?);
+            let _restore = Restore(ICU_FORMATTER.with(|current| {
+                current.replace(::std::option::Option::Some(formatter))
+            }));
+            askama::Template::render(self)
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! `#[template(key = "theme", arms(Light(…), Dark(…), _(…)))]`: choose a template by a field's value
+//!
+//! The arms are turned into conditional templates, i.e. `Light(…)` is handled like
+//! `if = "matches!(theme, Theme::Light)"`, and the `_` arm is the fallback.
+
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+
+use crate::args::{self, TemplateArg, TemplateArgs};
+use crate::guard::Guards;
+use crate::is_template_attr;
+
+/// Split the `#[template]` of a variant with `key = "…"` into its arms
+///
+/// Returns `None` if the variant has no `key`. The keys of the attribute besides `key` and `arms(…)`
+/// are shared by all arms.
+pub(crate) fn split(variant: &syn::Variant) -> syn::Result<Option<Guards>> {
+    let mut attrs = variant.attrs.iter().filter(|attr| is_template_attr(attr));
+    let mut meta = match attrs.next() {
+        Some(attr) => TemplateArgs::from_attr(attr)?,
+        None => return Ok(None),
+    };
+    let key = match meta.take_key("key") {
+        Some(key) => args::lit_str(&key)?,
+        None if meta.has_key("arms") => {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "`arms(…)` needs a `key = \"field\"` to match on",
+            ));
+        }
+        None => return Ok(None),
+    };
+    if let Some(attr) = attrs.next() {
+        return Err(syn::Error::new_spanned(
+            &attr.path,
+            "a variant with `key` can have only one #[template], use `arms(…)` instead",
+        ));
+    }
+    let mut lists = meta.take_lists("arms").into_iter();
+    let list = match (lists.next(), lists.next()) {
+        (Some(list), None) => list,
+        (_, Some(other)) => {
+            return Err(syn::Error::new(
+                other.key.span(),
+                "`arms(…)` can only be used once",
+            ));
+        }
+        (None, None) => {
+            return Err(syn::Error::new(
+                key.span(),
+                "`key` needs `arms(Name(…), …, _(…))`, the templates for the values of the field",
+            ));
+        }
+    };
+
+    let (binding, ty) = find_field(variant, &key)?;
+    let ty_path = enum_path(ty)?;
+    let mut default = None;
+    let mut others = Vec::<(syn::LitStr, TemplateArgs)>::new();
+    let mut names = Vec::<syn::Ident>::new();
+    for arg in list.parse_args()? {
+        let (name, tokens) = match arg {
+            TemplateArg::List { key, tokens, .. } => (key, tokens),
+            arg => {
+                return Err(syn::Error::new(
+                    arg.key().span(),
+                    "expected `Name(key = value, …)` or `_(key = value, …)`",
+                ));
+            }
+        };
+        if let Some(first) = names.iter().find(|first| **first == name) {
+            let mut err = syn::Error::new(name.span(), "duplicate arm");
+            err.combine(syn::Error::new(first.span(), "first used here"));
+            return Err(err);
+        }
+        names.push(name.clone());
+        let arm = TemplateArgs {
+            path: meta.path.clone(),
+            args: args::merge(
+                &meta.args,
+                args::TemplateList {
+                    key: name.clone(),
+                    tokens,
+                }
+                .parse_args()?,
+            ),
+        };
+        if name.unraw() == "_" {
+            default = Some(arm);
+            continue;
+        }
+        if default.is_some() {
+            return Err(syn::Error::new(
+                name.span(),
+                "the arm `_` must be the last one",
+            ));
+        }
+        let pattern = {
+            let mut path = ty_path.clone();
+            path.segments.push(name.clone().into());
+            path
+        };
+        let condition = quote!(::std::matches!(#binding, #pattern)).to_string();
+        others.push((syn::LitStr::new(&condition, name.span()), arm));
+    }
+    match default {
+        Some(default) => Ok(Some(Guards { default, others })),
+        None => Err(syn::Error::new(
+            list.key.span(),
+            "`arms(…)` needs a last arm `_(…)`, which is used for the other values of the field",
+        )),
+    }
+}
+
+/// The name the field `key` is bound to in `make_render_impl()`, and its type
+fn find_field<'a>(
+    variant: &'a syn::Variant,
+    key: &syn::LitStr,
+) -> syn::Result<(syn::Ident, &'a syn::Type)> {
+    let value = key.value();
+    for (index, field) in variant.fields.iter().enumerate() {
+        let found = match &field.ident {
+            Some(ident) => ident.unraw() == value,
+            None => value == index.to_string(),
+        };
+        if found {
+            let binding = match &field.ident {
+                Some(ident) => ident.clone(),
+                None => format_ident!("_{}", index),
+            };
+            return Ok((binding, &field.ty));
+        }
+    }
+    let msg = format!("`{}` has no field `{}`", variant.ident, value);
+    Err(syn::Error::new(key.span(), msg))
+}
+
+/// The path of the key field's enum without generics, e.g. `Theme` for `&'a Theme`
+fn enum_path(ty: &syn::Type) -> syn::Result<syn::Path> {
+    match ty {
+        syn::Type::Reference(reference) => enum_path(&reference.elem),
+        syn::Type::Group(group) => enum_path(&group.elem),
+        syn::Type::Paren(paren) => enum_path(&paren.elem),
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            let mut path = path.clone();
+            if let Some(last) = path.segments.last_mut() {
+                last.arguments = syn::PathArguments::None;
+            }
+            Ok(path)
+        }
+        ty => Err(syn::Error::new_spanned(
+            ty,
+            "the type of the `key` field needs to be an enum, e.g. `theme: Theme`",
+        )),
+    }
+}
@@ -0,0 +1,148 @@
+//! `#[enum_template(kind_enum)]`: a fieldless mirror of the enum
+
+use quote::{format_ident, quote, ToTokens};
+
+use crate::case::RenameRule;
+use crate::{fail_at, make_static_ty_generics, unraw, VariantDefinition};
+
+/// `#[enum_template(kind_enum(rename_all = "…"))]`
+pub(crate) struct KindEnum {
+    /// `kind_enum`, used in error messages
+    pub(crate) path: syn::Path,
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+/// The definition of `{EnumName}Kind`, and its implementations
+pub(crate) struct KindItems {
+    /// Needs to be nameable by the user
+    pub(crate) definition: proc_macro2::TokenStream,
+    /// References the generated structs, so it has to be put into the `const _` block
+    pub(crate) impls: proc_macro2::TokenStream,
+}
+
+fn kind_ident(ast: &syn::DeriveInput) -> syn::Ident {
+    format_ident!("{}Kind", ast.ident)
+}
+
+pub(crate) fn make_kind_enum(
+    kind: &KindEnum,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> Result<KindItems, proc_macro::TokenStream> {
+    let has_type_generics = ast
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, syn::GenericParam::Lifetime(_)));
+    if has_type_generics {
+        return Err(fail_at(
+            &kind.path,
+            "`kind_enum` cannot be used for enums with type or const generics",
+        ));
+    }
+
+    let vis = &ast.vis;
+    let enum_name = &ast.ident;
+    let kind_name = kind_ident(ast);
+    let static_ty_generics = make_static_ty_generics(ast);
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let names = variants.iter().map(|ident| {
+        let name = unraw(ident);
+        match kind.rename_all {
+            Some(rule) => rule.apply(&name),
+            None => name,
+        }
+    });
+    let extensions = variant_definitions.iter().map(|definition| {
+        let ty = definition.template_type(&static_ty_generics);
+        quote!(<#ty as askama::Template>::EXTENSION)
+    });
+    let mime_types = variant_definitions
+        .iter()
+        .map(|definition| match &definition.mime {
+            Some(mime) => mime.to_token_stream(),
+            None => {
+                let ty = definition.template_type(&static_ty_generics);
+                quote!(<#ty as askama::Template>::MIME_TYPE)
+            }
+        });
+    let paths = variant_definitions
+        .iter()
+        .map(|definition| match &definition.template_path {
+            Some(path) => quote!(::std::option::Option::Some(#path)),
+            None => quote!(::std::option::Option::None),
+        });
+
+    let doc = format!("The variants of [`{}`], without their data", enum_name);
+    let kind_doc = format!("Which variant of [`{}`] this is", kind_name);
+    let definition = quote! {
+        #[doc = #doc]
+        #[derive(
+            ::std::clone::Clone,
+            ::std::marker::Copy,
+            ::std::fmt::Debug,
+            ::std::cmp::PartialEq,
+            ::std::cmp::Eq,
+            ::std::hash::Hash,
+            ::std::cmp::PartialOrd,
+            ::std::cmp::Ord,
+        )]
+        #vis enum #kind_name {
+            #(#variants,)*
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let impls = quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #[doc = #kind_doc]
+            #vis fn kind(&self) -> #kind_name {
+                match self {
+                    #(Self::#variants { .. } => #kind_name::#variants,)*
+                }
+            }
+        }
+
+        impl #kind_name {
+            /// The name of the variant
+            #vis fn name(self) -> &'static ::std::primitive::str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+
+            /// The file extension of the variant's template
+            #vis fn extension(self) -> ::std::option::Option<&'static ::std::primitive::str> {
+                match self {
+                    #(Self::#variants => #extensions,)*
+                }
+            }
+
+            /// The MIME type of the variant's template
+            #vis fn mime_type(self) -> &'static ::std::primitive::str {
+                match self {
+                    #(Self::#variants => #mime_types,)*
+                }
+            }
+
+            /// The `path` of the variant's template, if it is not an inline `source`
+            #vis fn template_path(self) -> ::std::option::Option<&'static ::std::primitive::str> {
+                match self {
+                    #(Self::#variants => #paths,)*
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for #kind_name {
+            #[inline]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    };
+    Ok(KindItems { definition, impls })
+}
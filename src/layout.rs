@@ -0,0 +1,72 @@
+//! `#[template(layout = "base.html", slot = "content")]`: render the variant inside a layout
+//!
+//! The variant's template becomes the block `slot` of a template that extends `layout`. A `path`
+//! is included in the block, so askama still reads the file itself.
+
+use syn::spanned::Spanned;
+
+use crate::args::{self, TemplateArgs};
+
+/// The layout of `layout = "…"`, or `None` for `layout = false`
+pub(crate) fn parse(value: &syn::Expr) -> syn::Result<Option<syn::LitStr>> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(syn::LitBool { value: false, .. }),
+            ..
+        }) => Ok(None),
+        value => match args::lit_str(value) {
+            Ok(layout) => Ok(Some(layout)),
+            Err(_) => Err(syn::Error::new(
+                value.span(),
+                "expected the path of a layout template, or `false` to opt out",
+            )),
+        },
+    }
+}
+
+/// Wrap the `source` or `path` of `meta` into `{% extends layout %}{% block slot %}…{% endblock %}`
+pub(crate) fn apply(
+    meta: &mut TemplateArgs,
+    layout: &syn::LitStr,
+    slot: Option<&syn::LitStr>,
+) -> syn::Result<()> {
+    let slot = match slot {
+        Some(slot) => slot.value(),
+        None => "content".to_owned(),
+    };
+    let content = if let Some(path) = meta.take_key("path") {
+        let path = args::lit_str(&path)?;
+        let ext = std::path::Path::new(&path.value())
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned());
+        if let (false, Some(ext)) = (meta.has_key("ext"), ext) {
+            let ext = syn::LitStr::new(&ext, path.span());
+            meta.push_value(syn::Ident::new("ext", path.span()), syn::parse_quote!(#ext));
+        }
+        let source = format!("{{% include \"{}\" %}}", path.value());
+        meta.push_value(
+            syn::Ident::new("source", path.span()),
+            syn::parse_quote!(""),
+        );
+        source
+    } else {
+        match meta.value("source") {
+            Some(source) => args::lit_str(source)?.value(),
+            None => return Ok(()),
+        }
+    };
+    if let Some(syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(source),
+        ..
+    })) = meta.value_mut("source")
+    {
+        let value = format!(
+            "{{% extends \"{}\" %}}{{% block {} %}}{}{{% endblock %}}",
+            layout.value(),
+            slot,
+            content,
+        );
+        *source = syn::LitStr::new(&value, source.span());
+    }
+    Ok(())
+}
@@ -27,7 +27,36 @@
 //!
 //! You can add a default `#[template]` for variants that don't have a specific `#[template]` attribute.
 //! If omitted, then every variant needs its own `#[template]` attribute.
+//! A variant's own `#[template]` inherits the keys `ext`, `escape`, `syntax`, `print`, `config`,
+//! `expose_enum`, `expose_discriminant`, `prepend_source`, `append_source`, `layout`, `slot`, `trim`,
+//! `minify`, `postprocess`, `source`, `source_file`, `source_env`, `path`, `path_pattern`, `dir`, `case` and `doc` of the default `#[template]`, unless it sets them itself, so
+//! `#[template(source = "B")]` is enough if the enum has `#[template(ext = "html", escape = "none")]`.
+//! `source`, `source_file`, `source_env`, `path`, `path_pattern` and `doc` replace each other. Other keys, e.g. `mime` or `cfg(…)`, are not inherited.
+//! E.g. the enum's `config = "other.toml"` is used by all variants, even by variants with their own `#[template]`,
+//! unless they set `config` themselves. askama understands `config` since version 0.12.
 //! The `#[template]` attribute is not interpreted, but simply copied to be used by askama.
+//! Values are copied verbatim, except for `source = include_str!("…")` and `source = concat!(…)`,
+//! which are expanded to a string literal, because askama only accepts literals. The path of
+//! `include_str!` is relative to the directory of the crate's `Cargo.toml`.
+//!
+//! Instead of spelling out the default `#[template]`, `#[template(default_variant = "Fallback")]` uses the
+//! `#[template]` of the variant `Fallback` for every variant that has none, much like a `like = "Fallback"`
+//! on each of them. The other keys of the enum's `#[template]` still apply. If `Fallback` has fields, the
+//! variants without a `#[template]` need fields with the same names.
+//!
+//! `#[template(deny_default)]`, or `strict`, does the opposite: every variant needs its own `#[template]`,
+//! so a new variant cannot silently render the default, and all variants without one are listed in one
+//! error. The enum's `#[template]` can still provide the inherited keys.
+//!
+//! `#[template(enforce_escape = "html")]` in the enum's `#[template]` makes it a compile error if a
+//! variant's `#[template]`, including its `alternate(…)`, `cfg(…)`, `debug(…)` and `release(…)` keys, sets
+//! another `escape` mode, e.g. to make sure that a page enum cannot render user input unescaped by accident.
+//! A `#[template]` that needs another mode has to say so with `allow_unescaped`. Variants that don't set
+//! `escape` use the mode that askama chooses for their extension.
+//!
+//! With `print = "code"` or `print = "all"` in the enum's `#[template]`, the complete code that
+//! askama-enum generates, i.e. the helper structs and the `Template` and `Display` impls of the enum, is
+//! printed to stderr during the compilation, in addition to the output of askama for each helper struct.
 //!
 //! ```rust
 //! # #[cfg(feature = "askama")] fn main() {
@@ -73,21 +102,543 @@
 //! # }
 //! ```
 //!
+//! If another derive macro on the same item uses `#[template]`, too, you can spell the attribute
+//! `#[enum_template_attr(…)]` instead. An item must not use both spellings.
+//!
+//! Variants that share one template can be listed in a `#[template_for(variants(A, B, …), key = value, …)]`
+//! on the enum. The listed variants get `#[template(key = value, …)]`, as if it was written on each of them,
+//! so e.g. `source = "{variant}"` renders the name of every listed variant. There can be any number of
+//! groups, but a variant can only be in one group, and not if it has a `#[template]` of its own.
+//!
+//! A template that only fits some variants, e.g. `source = "{{ self.0 }}"`, can be the default for one
+//! shape of variants: in `#[template_default(unit(key = value, …), fields(key = value, …))]`, the keys
+//! of `unit(…)` are used for unit variants and the keys of `fields(…)` for tuple and struct variants
+//! without a `#[template]` of their own. A variant that lacks a `self.field` of an inline `source` is a
+//! compile error.
+//!
+//! ### Additional `#[template]` keys
+//!
+//! The following keys are interpreted by askama-enum, and not forwarded to askama:
+//!
+//! * `deprecated = "note"`: rendering the variant emits a deprecation warning with the given note.
+//!   The warning points to the variant in the enum definition, so it is emitted
+//!   in the crate that defines the enum, not where the variant gets constructed.
+//! * `mime = "type/subtype"`: the MIME type returned by `mime_type()` and used by the web integrations,
+//!   if the MIME type derived from the extension does not fit, e.g. `mime = "application/problem+json"`.
+//!   `content_type = "image/svg+xml"` is another name for `mime`.
+//! * `status = 404`: the HTTP status code returned by `status()` and used by the web integrations.
+//!   It must be in `100..=599`. Variants without a `status` have the status code 200.
+//! * `header("Cache-Control" = "no-store")`: an extra HTTP header, returned by `headers()` and added to the
+//!   responses of the web integrations. It can be used more than once. The name and the value are checked
+//!   when the crate is compiled.
+//! * `cfg(predicate, key = value, …)`: the keys are only forwarded to askama if the predicate is
+//!   active, e.g. `cfg(feature = "whitelabel", path = "wl/header.html")`.
+//!   Keys outside of `cfg(…)` are shared by all alternatives. Exactly one alternative must be active.
+//! * `feature = "whitelabel"`: a second `#[template]` of the variant that is used if the cargo feature is
+//!   enabled, e.g. `#[template(feature = "whitelabel", path = "wl/button.html")]` after `#[template(path = "button.html")]`.
+//!   Its keys override the ones of the `#[template]` without a `feature`, which is used if none of the features
+//!   is enabled. If more than one feature is enabled, the first `#[template]` wins. A feature can only be used once
+//!   per variant, and `feature` cannot be combined with `cfg(…)`, whose alternatives it is turned into.
+//! * `size_hint = 8192`: the expected length of the rendered variant, if askama's estimate is too low,
+//!   e.g. because of loops over big collections. It is used to preallocate the string in `render()`,
+//!   and returned by `size_hint()`.
+//! * `path = ["themes/custom/x.html", "themes/default/x.html"]`: the first candidate that exists in the
+//!   `templates` directory of your crate is used, e.g. for optional theme overrides. It is a compile error
+//!   if none of the candidates exist. Other template directories configured in `askama.toml` are not searched.
+//! * `source = "…"` may contain the placeholder `{variant}`, which is replaced with the name of the
+//!   variant, e.g. in a shared `#[template]` on the enum. `{variant:kebab-case}` converts the name
+//!   using the same rules as serde's `rename_all`. A placeholder directly after `{` is not replaced,
+//!   so `{{variant}}` is still an askama expression. For a literal `{variant}`, write `{{"{"}}variant}`.
+//!   Untagged variants share one template, so use `{{__variant}}` there.
+//! * `path_pattern = "pages/{variant}.html"`: the `path` of every variant, with the same placeholders as
+//!   `source`, e.g. in the enum's `#[template]`. A variant with its own `source`, `path` or `doc` overrides it.
+//!   It is a compile error if the file does not exist in the `templates` directory of your crate.
+//!   `case = "snake"`, `"kebab"`, `"lower"` or `"SCREAMING_SNAKE"` converts the name of the variant first.
+//!   Acronyms stay one word, so `HTTPError` becomes `http_error`.
+//! * `source_file = "src/mails/welcome.html"`: read the file into an inline `source`, e.g. for templates
+//!   that live next to the module of the enum instead of in `templates/`. Its extension is used as `ext` if
+//!   the variant has none. The path is relative to the directory of the crate's `Cargo.toml`, because a
+//!   derive macro cannot find out which file it was invoked from with the supported Rust versions.
+//! * `source_env = "BANNER_TEMPLATE"`: use the value of an environment variable as the `source`, e.g. a
+//!   snippet that the build pipeline generates. The variable is read when the crate is compiled, and it is an
+//!   error if it is not set. The generated code uses `env!("BANNER_TEMPLATE")`, so Cargo rebuilds the crate if
+//!   the variable changes. Unlike `source_file`, the variable has no extension, so the variant needs an `ext`.
+//! * `dir = "mails/"`: prepend a directory to the `path` of the variant, or in the enum's `#[template]`, of
+//!   every variant, e.g. `path = "welcome.txt"` becomes `"mails/welcome.txt"`. The results of `path_pattern` are
+//!   prefixed, too, but neither `source` nor a list of path candidates. A `path` starting with `/` ignores the
+//!   directory, and is relative to `templates/` as usual. Repeated `/` are collapsed. A variant opts out of
+//!   an inherited `dir` with `dir = ""`.
+//! * `alternate(key = value, …)`: a second template for the variant, e.g.
+//!   `alternate(ext = "txt", source = "…")` next to an HTML template. `format!("{:#}", value)` renders
+//!   the alternate template, `format!("{}", value)` and `render()` the primary one. Variants without
+//!   an alternate template ignore the `#` flag. The keys override shared keys.
+//! * `example = "Self::Variant { some: \"hi\", more: 1 }"`: the instance of the variant in `examples()`, see below.
+//! * `route = "/path"`: the route of a unit variant in `router()`, see below.
+//! * `output = "blog/{slug}/index.html"`: the path of the variant in a statically generated site, returned by
+//!   `output_path()` and used by `write_to()`. The placeholders name fields of the variant, like in `fmt`.
+//!   If one variant has an `output`, every variant needs one. Needs the feature `std`.
+//! * `delegate`: for a variant like `Home(HomeTemplate)` whose only field implements `Template`, too.
+//!   The field is rendered directly, and the enum's constants use the field's `EXTENSION`, `SIZE_HINT`
+//!   and `MIME_TYPE` if the variant is the default. `delegate = "body"` selects a field by name or index
+//!   in variants with more fields. `path` and `doc` cannot be used then.
+//!   With an inline `source`, the field of a newtype variant is rendered first, and available as
+//!   `{{ inner }}`, e.g. `source = "<li>{{ inner }}</li>"`. It is not escaped a second time.
+//! * `doc`: use the variant's `///` doc comment as its `source`, with the leading space of each line
+//!   removed, e.g. for error catalogs. The comment may contain askama expressions like `{{ code }}`.
+//!   If the variant has no `ext`, the `ext` of the enum's `#[template]` is used.
+//!   `source_from_doc` is accepted as another name for `doc`.
+//! * `block = "settings"`: render only the `{% block settings %}` of the template in `path`, e.g. with the
+//!   `path` in the enum's `#[template]` and one block per variant. The block is taken out of the file and
+//!   used as the variant's `source`, so it cannot use macros or other blocks that are defined outside of it.
+//!   If the variant has no `ext`, the extension of `path` is used.
+//! * `blocks("header", "body")`: take these `{% block %}`s out of the template in `path`, so `render_block("body")`
+//!   renders only that block, e.g. for HTMX-style partial updates. The variant's own template is rendered as usual.
+//!   Like with `block`, a block cannot use macros or other blocks that are defined outside of it.
+//! * `meta(title = "Password reset", team = "auth")`: arbitrary static metadata of the variant, returned by
+//!   `metadata()`. Any key can be used, but only once per variant.
+//! * `like = "NotFound"`: use the `#[template]` of the variant `NotFound`, e.g. if two variants render the
+//!   same page. Both variants need fields with the same names. Other keys override the copied ones, and
+//!   `route`, `output`, `example`, `meta(…)` and `deprecated` are not copied. `like` cannot point to a variant
+//!   that uses `like` itself.
+//! * `delegate_with = "path::to::adapter"`: render the output of `adapter(&field0, &field1, …)`, which
+//!   returns a `Template`, e.g. to render an `Error(io::Error)` variant with an `ErrorPage` template.
+//!   The variant needs an `ext`, which is used for its constants and `mime_type()`,
+//!   but no `source` or `path`.
+//! * `with = "InvoiceMail"`: build and render an existing template struct from the variant's fields,
+//!   e.g. to unit test the struct on its own. Named fields are passed by name, tuple fields by position,
+//!   and a variant field that the struct does not have is a compile error. The struct gets references to
+//!   the fields, and its constants are used for the variant.
+//! * `meta_default`: the enum's `EXTENSION`, `SIZE_HINT` and `MIME_TYPE` are taken from this variant,
+//!   instead of the default template or the first variant. Only one variant can be marked.
+//! * `locale = "de"`: one `#[template]` per locale, e.g. `#[template(locale = "en", path = "hello.en.html")]`
+//!   and `#[template(locale = "de", path = "hello.de.html")]` on the same variant. Needs
+//!   `#[enum_template(default_locale = "…")]`, and a template for the default locale, which is used by
+//!   `render()`. The other locales' keys override the default locale's keys.
+//! * `format = "txt"`: more than one `#[template]` on the same variant, e.g. `#[template(ext = "html", …)]`
+//!   and `#[template(format = "txt", ext = "txt", …)]`. `render()` uses the `#[template]` without a `format`,
+//!   or the first one if all have a `format`. `render_as("txt")` renders the variant's format, or returns `None`
+//!   if the variant has no such format, and `formats()` lists them. The other formats' keys override the keys
+//!   of the `#[template]` that `render()` uses.
+//! * `if = "items.is_empty()"`: choose between the `#[template]`s of a variant when it is rendered, e.g.
+//!   `#[template(if = "items.is_empty()", path = "empty-cart.html")]` before `#[template(path = "cart.html")]`.
+//!   The conditions are checked in declaration order, and the `#[template]` without an `if` must come last;
+//!   it is used if no condition holds. Named fields can be used by their name, all fields as `_0`, `_1`, ….
+//!   Only `render()` and `render_into()` check the conditions, everything else uses the last `#[template]`.
+//! * `key = "theme", arms(Light(path = "light.html"), Dark(path = "dark.html"), _(path = "page.html"))`:
+//!   choose the template of a variant by the value of its field `theme`, e.g. an `enum Theme`. Each arm is
+//!   named after a variant of the field's enum, and its keys are added to the other keys of the `#[template]`.
+//!   The last arm `_(…)` is used for the other values. Like `if`, only `render()` and `render_into()` match on
+//!   the field, and tuple variants use the index of the field, e.g. `key = "0"`.
+//! * `expose_enum = "outer"`: add the field `outer: &MyEnum` to the variant's struct, so the template can
+//!   call methods of the enum, e.g. `{{ outer.css_class() }}`. Choose a name that is not used by the
+//!   variant's fields or computed values. Tuple variants cannot have the field, so a variant can opt out of
+//!   an inherited `expose_enum` with `expose_enum = ""`.
+//! * `expose_discriminant`: add the field `discriminant` to the variant's struct, with the value of the
+//!   variant's discriminant, e.g. `404` for `NotFound = 404`. Variants without an explicit discriminant get
+//!   the same value that the compiler assigns. The type is the enum's `#[repr(…)]`, or `isize`. Like
+//!   `expose_enum`, it cannot be used in tuple variants; opt out with `expose_discriminant = false`.
+//! * `prepend_source = "…"`, `append_source = "…"`: a header and a footer around the variant's template,
+//!   e.g. a shared `{% import "macros.html" as m %}` or `{% include "footer.html" %}`. In the enum's
+//!   `#[template]`, they are used for every variant, and a variant opts out with `prepend_source = ""`
+//!   or `append_source = ""`. The template file of a `path` is read and turned into an inline `source`,
+//!   its extension is used as `ext` if the variant has none.
+//! * `prepend = "…"`, `append = "…"`: in a variant without a template of its own, add a line before or after
+//!   the template of the enum's `#[template]`, e.g. `#[template(prepend = "<h1>Welcome back!</h1>")]`
+//!   to reuse the default page with a heading. They cannot be combined with a `source` or `path` of the
+//!   variant; use `prepend_source` and `append_source` there.
+//! * `layout = "base.html"`, `slot = "content"`: render the variant's template inside a layout, i.e. as
+//!   `{% extends "base.html" %}{% block content %}…{% endblock %}`. The `slot` defaults to `"content"`.
+//!   A `path` is included into the block with `{% include %}`. In the enum's `#[template]`, the layout
+//!   is used for every variant, and a variant opts out with `layout = false`.
+//! * `trim`: remove leading and trailing ASCII whitespace from the rendered variant, e.g. the newlines
+//!   around an inline `source` of a plain text email. Whitespace inside the output is kept. `render_into()`
+//!   renders into a temporary string first. A variant opts out of an inherited `trim` with `trim = false`.
+//! * `minify`: minify the rendered HTML, e.g. to send smaller pages. Comments, and the indentation next to the
+//!   tags of block elements like `<p>` or `<li>` are removed. Other runs of whitespace, e.g. a line break
+//!   between text and `<b>`, become a single space. Quoted attribute values and the content of `<pre>`,
+//!   `<textarea>`, `<script>` and `<style>` are kept. Only variants whose `ext` is `html` can use it, and a
+//!   variant opts out of an inherited `minify` with `minify = false`. The output is minified after `trim`,
+//!   and before `postprocess`. Needs the feature `minify`.
+//! * `postprocess = "path::to::function"`: pass the rendered variant to a `fn(String) -> askama::Result<String>`,
+//!   e.g. to replace quotes with typographic ones, or to add a version to URLs. With `trim`, the output is
+//!   trimmed first. Like `trim`, `render_into()` renders into a temporary string first. A variant opts out
+//!   of an inherited `postprocess` with `postprocess = ""`.
+//! * `memoize = "chart.id"`: cache the rendered variant, keyed by the value of the expression, e.g. for an
+//!   expensive chart that rarely changes. The value is cloned into the cache, so it needs to implement
+//!   `Clone`, `Eq`, `Hash`, `Send` and `Sync`, and must not borrow, e.g. `name.to_string()` instead of
+//!   `name` for a `&str`. A cached output is only reused if the keys are equal. Named fields can be used by
+//!   their name, all fields as `_0`, `_1`, …. `render()`, `render_into()` and `Display` share the cache. It
+//!   cannot be combined with `#[enum_template(memoize)]`. Needs the feature `memoize`. The cache is a
+//!   `static` `Mutex`, so the generated code needs Rust 1.63.
+//!
+//!   **The cache is unbounded:** it is only emptied by `clear_memoized()`, so every distinct key stays in
+//!   memory until then. Use a key with only a few distinct values, which changes whenever the output does,
+//!   or call `clear_memoized()` regularly.
+//! * `skip`: the variant renders nothing, e.g. for internal states. It cannot have a `source` or `path`,
+//!   and it does not supply the enum's `EXTENSION`, `SIZE_HINT` or `MIME_TYPE`. Its `mime_type()` is
+//!   derived from its `ext`, or `"txt"`.
+//! * `redirect = "/auth/login"`: the variant is not a page, but a redirect, e.g. `Login` for a page that
+//!   needs a session. `redirect_target()` returns the URL, and the web integrations answer with a 303 redirect
+//!   instead of rendering. `render()` fails for the variant. It cannot have a `source` or `path`.
+//! * `display = "field"`: write the field, e.g. `display = "0"` or `display = "name"`, with `Display`
+//!   instead of rendering a template. The output is not escaped. Like `skip` variants, the variant does
+//!   not supply the enum's `EXTENSION`, `SIZE_HINT` or `MIME_TYPE`, and its `mime_type()` is derived
+//!   from its `ext`, or `"txt"`.
+//! * `fmt = "user {name} not found"`: write the variant with `write!()` instead of rendering a template.
+//!   The placeholders name the fields of the variant, e.g. `{name}`, or `{0}` in tuple variants, and may
+//!   have a format spec like `{0:>8}`. The output is not escaped, and the constants are handled like
+//!   the ones of `display` variants.
+//! * `debug(key = value, …)`, `release(key = value, …)`: keys only used in debug or release builds,
+//!   i.e. if `debug_assertions` are enabled or disabled. The keys override shared keys.
+//!
+//! ### `#[enum_template(…)]` options
+//!
+//! * `feature_gate = "templates"`: all generated code is only compiled if the feature `templates`
+//!   of your crate is enabled. Without the feature, the enum does not need askama at all.
+//!   For other predicates, use e.g. `#[template(cfg = r#"all(feature = "html", not(test))"#)]` in the
+//!   enum's `#[template]`. If both are used, the generated code needs both.
+//! * `untagged(value = "inner")`: all newtype variants without a specific `#[template]` attribute
+//!   share one struct that uses the default template. The variant's content is available as
+//!   `{{inner}}`, and the variant's name as `{{__variant}}`. The newtype variants need to
+//!   have the same field type.
+//! * `check_size_hint`, `check_size_hint(factor = 4)`: in debug builds, `render()` panics if the
+//!   output is more than `factor` times longer than the variant's `SIZE_HINT`. The default factor is 4.
+//! * `metrics`, `metrics(prefix = "tpl")`: if the feature `metrics` is enabled, rendering increments the
+//!   counters `{prefix}_render_total` and, on failure, `{prefix}_render_errors_total` with the labels
+//!   `enum` and `variant`. The default prefix is `askama_enum`.
+//! * `extension = "json"`, `mime = "application/json"`, `size_hint = 4096`: override the enum's
+//!   `EXTENSION`, `MIME_TYPE` and `SIZE_HINT`, respectively. Otherwise they are taken from the
+//!   variant with `#[template(meta_default)]`, the default template, or the first variant's template.
+//!   If a variant has a `#[template(size_hint = …)]`, `SIZE_HINT` is the maximum of all variants.
+//! * `compute(name = "expr", …)`: evaluate `expr` once per render, and make its result available to
+//!   all templates as `{{name}}`. The value is converted with `ToString`. The expressions cannot
+//!   access the variant's fields, and cannot be used with tuple variants.
+//! * `variant_name`, `variant_name = "kind"`: make the name of the variant available to all templates as
+//!   `{{variant_name}}`, or under the given name, e.g. `<div class="{{ variant_name }}">` in the default
+//!   template. A raw identifier is used without its `r#`. Like computed values, the name must not be used by
+//!   a field, and cannot be used with tuple variants.
+//! * `expose_index`: make the position of the variant, starting at 0, and the number of variants available
+//!   to all templates as `{{variant_index}}` and `{{variant_count}}`, e.g. for
+//!   `step {{ variant_index + 1 }} of {{ variant_count }}`. All variants are counted, including `skip`
+//!   and `delegate` variants. Variants removed by `#[cfg(…)]` are not seen by the derive macro, so the
+//!   following variants move up.
+//! * `memoize`, `memoize(capacity = 256)`: `render()` caches its output, keyed by the variant and clones of
+//!   its fields. The fields need to implement `Clone`, `Eq`, `Hash`, `Send` and `Sync`, and must not borrow,
+//!   except for references like `&'a str`, which are keyed by an owned copy, e.g. a `String`. A cached
+//!   output is only reused if the fields are equal. If the cache is full, the oldest entry is evicted.
+//!   Computed values are not part of the key. `render_into()` bypasses the cache. Needs the feature
+//!   `memoize`. The cache is a `static` `Mutex`, so the generated code needs Rust 1.63.
+//! * `kind_enum`, `kind_enum(rename_all = "snake_case")`: generate a fieldless `{EnumName}Kind` enum
+//!   with the same variants, and `fn kind(&self) -> {EnumName}Kind`. The kind enum implements
+//!   `Copy`, `Eq`, `Ord`, `Hash` and `Display`, and has the methods `name()`, `extension()`,
+//!   `mime_type()` and `template_path()`. `rename_all` uses the same rules as serde, and changes
+//!   the result of `name()`. Cannot be used for enums with type or const generics.
+//! * `sanitize(config = "crate::AMMONIA")`: the `ammonia::Builder` that `#[template_field(sanitize)]`
+//!   uses instead of `ammonia::clean()`, e.g. a `static` in a `once_cell::sync::Lazy`. Needs the feature `ammonia`.
+//! * `validate_json`: in debug builds, `render()` panics if a variant with the extension `json`
+//!   produces invalid JSON. The message contains the variant and the position of the error.
+//!   Needs the feature `serde_json`.
+//! * `newlines = "crlf"`, `newlines = "lf"`, `newlines(style = "crlf", extensions("txt", "eml"))`:
+//!   convert the line endings of variants with the given extensions, `txt` and `eml` by default.
+//!   `render_into()` and `Display` convert the output while it is written.
+//! * `check_unused_templates = "mails"`, `check_unused_templates(dir = "mails", ignore("base.txt"))`:
+//!   fail to compile if a file in `templates/mails` or its subdirectories is not used as `path` by any
+//!   variant of the enum, including `cfg(…)` and `alternate(…)` paths. The files in `ignore(…)` are
+//!   relative to `dir`, e.g. for templates that are only included or extended.
+//! * `examples`, `examples(strict)`: generate `fn examples() -> Vec<Self>` with an instance of every
+//!   unit variant, and of every variant with an `example`, e.g. to render all of them in a test.
+//!   Variants with fields and without an example are left out, or fail to compile with `strict`.
+//! * `methods(byte_len, render_cow, …)`: generate the listed accessors, even if no variant uses their key,
+//!   see [Generated methods](#generated-methods). `byte_len` and `render_cow` have no key, so they are only
+//!   generated if they are listed.
+//! * `router`: generate `fn router<S>() -> axum::Router<S>` with a `GET` route for every unit variant,
+//!   at `/kebab-case-name`, or at `/` for the variant that uses the enum's `#[template]`.
+//!   The response has the content type, the status code and the headers of the variant. Variants with fields are skipped.
+//!   Needs the feature `axum`.
+//! * `conditional`: generate `fn etag(&self) -> askama::Result<String>`, a hash of the rendered template,
+//!   and `fn to_axum_conditional_response(&self, if_none_match: Option<&str>) -> axum::response::Response`
+//!   or `fn to_rouille_conditional_response(…) -> rouille::Response`. The response has an `ETag` header, and if
+//!   the variant's status code is 2xx and the value of the request's `If-None-Match` header matches the ETag,
+//!   the status code 304, the variant's extra headers and no body. The ETag may change with the Rust version.
+//!   Needs the feature `axum` or `rouille`.
+//! * `rouille`: generate `fn to_rouille_response(&self) -> rouille::Response` with the content type,
+//!   the status code and the headers of the rendered variant. Variants with a `redirect` are answered with
+//!   a 303 redirect. If rendering fails, the response has status 500 and contains the error.
+//!   Needs the feature `rouille`.
+//! * `ws_axum`: generate `fn to_axum_ws_message(&self) -> askama::Result<axum::extract::ws::Message>`,
+//!   a text message with the rendered template. Needs the feature `ws-axum`.
+//! * `ws_tungstenite`: generate `fn to_tungstenite_message(&self) -> askama::Result<tokio_tungstenite::tungstenite::Message>`,
+//!   a text message with the rendered template. Needs the feature `ws-tungstenite`.
+//! * `teloxide`: generate `fn to_telegram_text(&self) -> askama::Result<(String, Option<teloxide::types::ParseMode>)>`.
+//!   The parse mode is `ParseMode::Html` for variants with an `html` or `htm` template, and `None`
+//!   otherwise. Templates with `escape = "none"` are sent as plain text, because their values are
+//!   not escaped for Telegram's HTML parser. Needs the feature `teloxide`.
+//! * `wasm`: generate `fn render_js(&self) -> Result<js_sys::JsString, wasm_bindgen::JsValue>`.
+//!   The error message contains the name of the variant. Needs the feature `wasm`.
+//! * `schema`: generate `fn context_schema() -> &'static str`, a JSON description of the variables
+//!   each variant's template can use: `{"enum": …, "variants": [{"name": …, "fields": [{"name": …,
+//!   "type": …, "origin": …}]}]}`. The origin is `"field"`, `"computed"`, or `"value"` and `"variant"` in untagged mode.
+//!   Needs the feature `schema`.
+//! * `write_to_file`: generate `fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), {EnumName}WriteError>`,
+//!   and the error type `{EnumName}WriteError` next to the enum. Needs the feature `std`.
+//! * `unescaped`: generate `fn render_unescaped(&self) -> askama::Result<String>`, which renders the same
+//!   templates with `escape = "none"`, e.g. for trusted previews. `render()` and `Display` are unchanged.
+//!   Variants whose `#[template]` sets `escape` keep it, and variants that render another template
+//!   through `delegate`, `delegate_with` or `with` are rendered as usual.
+//! * `default_locale = "en"`: the locale that `render()` and `Display` use for variants with
+//!   `#[template(locale = "…")]`, and generate `fn render_locale(&self, locale: &str) -> askama::Result<String>`.
+//!   Variants without a template for `locale` render their default template.
+//! * `coverage`: if the feature `coverage` is enabled, rendering a variant sets a flag, and
+//!   `fn render_coverage() -> Vec<(&'static str, bool)>` returns the names of all variants and their
+//!   flags, e.g. for a test that fails if a variant was never rendered during the test run.
+//! * `icu`: make a locale-aware formatter available as `fmt` in every template, e.g.
+//!   `{{ fmt.decimal(self.count) }}` for integers or `{{ fmt.date(self.since) }}` for an `icu_calendar::Date<Iso>`,
+//!   and generate `fn render_in(&self, locale: &icu_locale_core::Locale) -> askama::Result<String>`.
+//!   `render()` formats for the root locale. Tuple variants are not supported, and `icu` cannot be
+//!   combined with `memoize`. Needs the feature `icu`.
+//! * `error_context`: errors of a variant are wrapped in `askama::Error::Custom(…)`, containing a
+//!   `{EnumName}RenderError` that is generated next to the enum. It has the fields `variant`, e.g.
+//!   `"MyEnum::Variant"`, and `error`, the original error. Its `Display` output contains both.
+//!
+//! ### `#[template_field(…)]` options
+//!
+//! Fields with this attribute are converted before they are handed to the template:
+//!
+//! * `base64`, `base64(url_safe)`: encode a `Vec<u8>`, `&[u8]` or `[u8; N]` field as base64 string,
+//!   optionally using the URL-safe alphabet, e.g. for `data:image/png;base64,{{ png }}`.
+//!   Needs the feature `base64`.
+//! * `time_format = "…"`: format a date or time field. For chrono types like `DateTime<Tz>` or
+//!   `NaiveDate`, the format uses chrono's `%Y-%m-%d` syntax, and an invalid format fails at render time.
+//!   For time types like `OffsetDateTime` or `Date`, the format uses time's `[year]-[month]-[day]` syntax,
+//!   and is checked at compile time. Needs the feature `chrono` or `time`, respectively.
+//! * `sanitize`: clean up a `String` or `&str` field of user-supplied HTML with `ammonia`, e.g.
+//!   to remove `<script>` tags. The cleaned field is meant to be used with the `safe` filter:
+//!   `{{ comment|safe }}`. Needs the feature `ammonia`.
+//! * `markdown`, `markdown(tables, footnotes, rename = "body")`: convert a `String` or `&str` field
+//!   from Markdown to HTML with `pulldown-cmark`, optionally enabling tables and footnotes. The HTML is
+//!   stored under the field's name, or as `rename`, and is meant to be used with the `safe` filter.
+//!   The Markdown is not sanitized. Needs the feature `pulldown-cmark`.
+//! * `example = "42"`: the value of the field in `examples()`, if the variant has no `example`.
+//!   All fields of the variant need an example then. This key does not convert the field.
+//! * `render`: render a field that implements `Template`, e.g. a sidebar, and store the output under
+//!   the field's name, to be used with the `safe` filter: `{{ sidebar|safe }}`. An error of the field's
+//!   template is returned by the enum's `render()`.
+//!
+//! ### Generated methods
+//!
+//! The `Display` implementation honors width, fill, alignment and precision like `str` does, e.g.
+//! `format!("{:>40}", value)`. Only then the template is rendered into a temporary string first.
+//!
+//! Besides implementing `askama::Template` and `Display`, the derive macro adds these methods to the enum:
+//!
+//! * `fn render_as(&self, format: &str) -> Option<askama::Result<String>>` and
+//!   `fn formats(&self) -> &'static [&'static str]`: if a variant has a `#[template(format = "…")]`, see above
+//! * `fn render_block(&self, block: &str) -> askama::Result<String>` and
+//!   `fn blocks(&self) -> &'static [&'static str]`: if a variant has a `#[template(blocks(…))]`, see above.
+//!   Fails for blocks that the variant does not declare.
+//! * `fn output_path(&self) -> PathBuf` and `fn write_to(&self, root: &Path) -> io::Result<()>`: if the
+//!   variants have a `#[template(output = "…")]`. `write_to()` renders the variant into `root.join(self.output_path())`,
+//!   and creates missing parent directories. If the path is absolute or contains `..`, e.g. because of a
+//!   field's value, it fails with an `io::Error` of the kind `InvalidInput` instead of writing outside of `root`.
+//! * `fn clear_memoized()`: if a variant has a `#[template(memoize = "…")]`, remove all cached outputs
+//!
+//! The following accessors are only generated if a variant uses the key in parentheses, or if they are listed
+//! in `#[enum_template(methods(…))]`:
+//!
+//! * `fn byte_len(&self) -> askama::Result<usize>`: the length of the rendered template in bytes,
+//!   calculated without allocating a string
+//! * `fn mime_type(&self) -> &'static str` (`mime`): the MIME type of the variant's template
+//! * `fn content_type(&self) -> &'static str` (`mime`): the same as `mime_type()`
+//! * `fn status(&self) -> u16` (`status`): the variant's `#[template(status = …)]`, or 200
+//! * `fn headers(&self) -> &'static [(&'static str, &'static str)]` (`header`): the variant's `#[template(header(…))]`s
+//! * `fn redirect_target(&self) -> Option<&'static str>` (`redirect`): the variant's `#[template(redirect = "…")]`
+//! * `fn metadata(&self) -> &'static [(&'static str, &'static str)]` (`meta`): the pairs of the variant's
+//!   `#[template(meta(…))]`, in their order, or an empty slice
+//! * `fn size_hint(&self) -> usize` (`size_hint`): the `SIZE_HINT` of the variant's template, or its `size_hint`
+//! * `fn render_cow(&self) -> askama::Result<Cow<'static, str>>`: like `render()`, but unit variants
+//!   are only rendered once, and borrow the cached output afterwards. Variants with fields, unit variants
+//!   with `if = "…"` guards, and all variants of enums with type or const generics, `compute(…)`,
+//!   `metrics` or `coverage` return an owned string. The cached outputs are stored in `static` `OnceLock`s,
+//!   so the generated code needs Rust 1.70.
+//!
+//! ### Test helpers
+//!
+//! * `assert_renders_eq!(value, "expected")`: render `value` and compare the output. If rendering fails,
+//!   the panic message contains the type of `value` and the error. If the output differs, it contains
+//!   the lines of both, marked with `-` for the expected and `+` for the rendered output.
+//! * `assert_renders_ok!(value)`: render `value`, panic like above if rendering fails, and return the output.
+//!
+//! ### Optional features
+//!
+//! * `std`: allow `#[enum_template(write_to_file)]`.
+//! * `ammonia`: allow `#[template_field(sanitize)]`.
+//! * `axum`: allow `#[enum_template(router)]` and `#[enum_template(conditional)]`.
+//! * `base64`: allow `#[template_field(base64)]`.
+//! * `chrono`, `time`: allow `#[template_field(time_format = "…")]` for chrono or time types.
+//! * `coverage`: generate `render_coverage()` for enums with `#[enum_template(coverage)]`.
+//! * `hot-reload`: in debug builds, `render()` reads the file of a variant with `#[template(path = "…")]`
+//!   if it was modified after the binary was built, or always if the environment variable
+//!   `ASKAMA_ENUM_HOT_RELOAD_ALWAYS` is set. The files are looked up in `$ASKAMA_ENUM_TEMPLATE_DIR`,
+//!   or in the `templates` directory of your crate. Only raw text and `{{ field }}` or `{{ self.field }}`
+//!   expressions are understood, and only fields that implement `Display` and have no `#[template_field]`.
+//!   The values are escaped like askama does for `html`, `htm` and `xml` templates. If the file contains
+//!   anything else, e.g. filters, blocks or comments, the compiled template is used. It is used, too, if
+//!   anything else chooses or changes the variant's output, e.g. `if = "…"`, `key`, `trim`, `minify`,
+//!   `postprocess`, `memoize`, or the enum's `newlines`, `memoize`, `metrics`, `coverage` or
+//!   `error_context`. Release builds are not affected.
+//! * `icu`: allow `#[enum_template(icu)]`. Your crate needs `icu_calendar`, `icu_datetime`, `icu_decimal`
+//!   and `icu_locale_core` 2.x as dependencies.
+//! * `memoize`: allow `#[enum_template(memoize)]` and `#[template(memoize = "…")]`.
+//! * `minify`: allow `#[template(minify)]`.
+//! * `metrics`: count renders of enums with `#[enum_template(metrics)]` using the `metrics` crate.
+//! * `pulldown-cmark`: allow `#[template_field(markdown)]`.
+//! * `rouille`: allow `#[enum_template(rouille)]`.
+//! * `serde_json`: allow `#[enum_template(validate_json)]`.
+//! * `schema`: allow `#[enum_template(schema)]`.
+//! * `teloxide`: allow `#[enum_template(teloxide)]`.
+//! * `wasm`: allow `#[enum_template(wasm)]`.
+//! * `ws-axum`: allow `#[enum_template(ws_axum)]`.
+//! * `ws-tungstenite`: allow `#[enum_template(ws_tungstenite)]`.
+//!
+//! The generated code refers to the respective crates, so your crate has to depend on them.
+//!
+
+mod args;
+mod assert;
+mod block;
+mod case;
+mod checks;
+mod conditional;
+mod consts;
+mod coverage;
+mod delegate;
+mod dir;
+mod discriminant;
+mod display;
+mod doc;
+mod enforce_escape;
+mod examples;
+mod expand;
+mod features;
+mod fields;
+mod fmt;
+mod format;
+mod groups;
+mod guard;
+mod helper;
+mod hot_reload;
+mod icu;
+mod keyed;
+mod kind;
+mod layout;
+mod like;
+mod locale;
+mod memoize;
+mod methods;
+mod minify;
+mod newlines;
+mod output;
+mod placeholder;
+mod postprocess;
+mod response;
+mod router;
+mod schema;
+mod shape_defaults;
+mod skip;
+mod surround;
+mod template_meta;
+mod unescaped;
+mod unused;
+mod variant_vars;
+mod with;
 
 use std::iter::FromIterator;
 
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{parse_quote, DeriveInput, Token};
 
+use crate::args::TemplateArgs;
+use crate::case::RenameRule;
+use crate::examples::Examples;
+use crate::fields::{FieldContext, FieldTransform};
+use crate::kind::KindEnum;
+use crate::newlines::Newlines;
+use crate::template_meta::TemplateMeta;
+use crate::unused::UnusedTemplates;
+
+/// Assert that a template renders to the expected output
+///
+/// ```rust
+/// # #[cfg(feature = "askama")] fn main() {
+/// # use askama_enum::{assert_renders_eq, EnumTemplate};
+/// #[derive(EnumTemplate)]
+/// enum Greeting<'a> {
+///     #[template(ext = "html", source = "Hello, <b>{{ self.0 }}</b>!")]
+///     Bold(&'a str),
+/// }
+///
+/// assert_renders_eq!(Greeting::Bold("<World>"), "Hello, <b>&lt;World&gt;</b>!");
+/// # }
+/// ```
+///
+/// If rendering fails, the panic message contains the type of the value and the error.
+/// If the output differs, it contains the differing lines.
+#[proc_macro]
+pub fn assert_renders_eq(input: TokenStream) -> TokenStream {
+    match assert::make_assert_renders_eq(input.into()) {
+        Ok(output) => output.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Assert that a template renders without an error, and return the output
+///
+/// ```rust
+/// # #[cfg(feature = "askama")] fn main() {
+/// # use askama_enum::{assert_renders_ok, EnumTemplate};
+/// #[derive(EnumTemplate)]
+/// enum Greeting<'a> {
+///     #[template(ext = "html", source = "Hello, <b>{{ self.0 }}</b>!")]
+///     Bold(&'a str),
+/// }
+///
+/// let html = assert_renders_ok!(Greeting::Bold("World"));
+/// assert!(html.contains("World"));
+/// # }
+/// ```
+#[proc_macro]
+pub fn assert_renders_ok(input: TokenStream) -> TokenStream {
+    match assert::make_assert_renders_ok(input.into()) {
+        Ok(output) => output.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
 /// Implement different Askama templates for different enum variants
 ///
 /// Please see the [crate] documentation for more examples.
-#[proc_macro_derive(EnumTemplate, attributes(template))]
+#[proc_macro_derive(
+    EnumTemplate,
+    attributes(
+        template,
+        template_for,
+        template_default,
+        enum_template,
+        enum_template_attr,
+        template_field
+    )
+)]
 pub fn derive_enum_template(input: TokenStream) -> TokenStream {
-    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let mut ast: syn::DeriveInput = syn::parse(input).unwrap();
+    if let Err(err) = groups::apply(&mut ast) {
+        return into_error(err);
+    }
+    if let Err(err) = shape_defaults::apply(&mut ast) {
+        return into_error(err);
+    }
 
     let data = match &ast.data {
         syn::Data::Enum(data) => data,
@@ -104,82 +655,138 @@ pub fn derive_enum_template(input: TokenStream) -> TokenStream {
             );
         }
     };
+    match derive_enum(&ast, data) {
+        Ok(result) => result.into(),
+        Err(err) => err,
+    }
+}
 
-    let mut global_meta = None;
-    for attr in &ast.attrs {
-        let meta_list = match attr.parse_meta() {
-            Ok(syn::Meta::List(attr)) => attr,
-            _ => continue,
-        };
-        if meta_list.path.is_ident("template") {
-            if global_meta.is_some() {
-                return fail_at(
-                    meta_list.path,
-                    "cannot have more than one #[template] attribute for a type",
-                );
-            }
-            global_meta = Some(attr);
-        }
+fn derive_enum(
+    ast: &DeriveInput,
+    data: &syn::DataEnum,
+) -> Result<proc_macro2::TokenStream, TokenStream> {
+    let mut global_meta = match find_template_attr(&ast.attrs, "a type")? {
+        Some(global_meta) => Some(resolve_default_variant(global_meta, data)?),
+        None => None,
+    };
+    let print_code = parse_print(global_meta.as_ref())?;
+
+    let options = parse_enum_options(&ast.attrs, data)?;
+    if let Some(global_meta) = &mut global_meta {
+        check_deny_default(global_meta, &options, data)?;
     }
+    let cfg_gate = match global_meta.as_mut().and_then(|meta| meta.take_key("cfg")) {
+        Some(value) => Some(parse_cfg_gate(&value).map_err(into_error)?),
+        None => None,
+    };
+    let untagged_definition = make_untagged_definition(&options, global_meta.as_ref(), ast, data)?;
 
     let mut default_variant_name = None;
-    let variant_definitions =
-        make_variant_definitions(global_meta, &ast, data, &mut default_variant_name);
-    let variant_definitions = match variant_definitions {
-        Ok(variant_definitions) => variant_definitions,
-        Err(err) => return err,
-    };
-    let match_render_impl = make_render_impl(&ast, data, "render", Punctuated::new());
-    let match_render_into_impl = make_render_impl(
-        &ast,
+    let mut constants_variant_name = None;
+    let variant_definitions = make_variant_definitions(
+        global_meta.as_ref(),
+        untagged_definition.as_ref().map(|item| &item.ident),
+        &options,
+        ast,
         data,
-        "render_into",
-        Punctuated::from_iter([syn::Expr::Path(parse_quote!(writer))]),
-    );
-    let dflt_or_fst_variant_name =
-        default_variant_name.unwrap_or_else(|| variant_definitions[0].ident.clone());
-
-    let mut static_ty_generics = quote!(::<);
-    for g in ast.generics.params.iter() {
-        match g {
-            syn::GenericParam::Type(param) => {
-                param.ident.to_tokens(&mut static_ty_generics);
-            }
-            syn::GenericParam::Const(param) => {
-                param.ident.to_tokens(&mut static_ty_generics);
-            }
-            _ => (),
-        }
+        &mut default_variant_name,
+        &mut constants_variant_name,
+    )?;
+    if let Some(check) = &options.check_unused_templates {
+        let items = untagged_definition.iter().chain(
+            variant_definitions
+                .iter()
+                .flat_map(VariantDefinition::items),
+        );
+        check
+            .check(items.flat_map(|item| &item.attrs))
+            .map_err(into_error)?;
     }
-    static_ty_generics.extend(quote!(>));
+    let static_ty_generics = make_static_ty_generics(ast);
+    let router = router::make(
+        &options,
+        ast,
+        data,
+        &variant_definitions,
+        default_variant_name.as_ref(),
+        &static_ty_generics,
+    )?;
+    let (render_impl, render_into_impl, reloads) =
+        make_render_bodies(ast, &options, data, &variant_definitions);
+    let display_impl = display::make_fmt(ast, &options, data, &variant_definitions);
+    let consts = consts::make_consts(
+        &options,
+        &variant_definitions,
+        constants_variant_name
+            .map(|(name, _)| name)
+            .or(default_variant_name),
+        &static_ty_generics,
+    );
 
     let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
     let enum_name = &ast.ident;
     let mut result = quote! {
         impl #impl_generics askama::Template for #enum_name #ty_generics #where_clause {
             fn render(&self) -> askama::Result<::std::string::String> {
-                #match_render_impl
+                #render_impl
             }
 
             fn render_into(
                 &self,
                 writer: &mut (impl ::std::fmt::Write + ?::std::marker::Sized),
             ) -> askama::Result<()> {
-                #match_render_into_impl
+                #render_into_impl
             }
 
-            const EXTENSION: ::std::option::Option<&'static str> =
-                <#dflt_or_fst_variant_name #static_ty_generics as askama::Template>::EXTENSION;
-            const SIZE_HINT: ::std::primitive::usize =
-                <#dflt_or_fst_variant_name #static_ty_generics as askama::Template>::SIZE_HINT;
-            const MIME_TYPE: &'static ::std::primitive::str =
-                <#dflt_or_fst_variant_name #static_ty_generics as askama::Template>::MIME_TYPE;
+            #consts
         }
     };
-    for variant_definition in variant_definitions {
+    result.extend(make_runtime(&options, data, &variant_definitions, reloads));
+    untagged_definition.to_tokens(&mut result);
+    for variant_definition in &variant_definitions {
         variant_definition.to_tokens(&mut result);
     }
+    if options.unescaped {
+        let items = untagged_definition
+            .iter()
+            .chain(variant_definitions.iter().flat_map(|d| &d.item));
+        for item in items {
+            unescaped::make_item(item)
+                .map_err(into_error)?
+                .to_tokens(&mut result);
+        }
+    }
+    let examples = examples::make(&options, ast, data, &variant_definitions)?;
+    let mut inherent_methods = make_inherent_methods(ast, &options, data, &variant_definitions);
+    inherent_methods
+        .extend(output::make_methods(&ast.vis, data, &variant_definitions).map_err(into_error)?);
+    inherent_methods.extend(make_flavored_methods(
+        ast,
+        &options,
+        data,
+        &variant_definitions,
+    ));
+    inherent_methods.extend(router);
+    inherent_methods.extend(examples);
+    if !inherent_methods.is_empty() {
+        result.extend(quote! {
+            impl #impl_generics #enum_name #ty_generics #where_clause {
+                #(#inherent_methods)*
+            }
+        });
+    }
+    let mut outer_items = make_outer_items(ast, &options);
+    if let Some(kind) = &options.kind_enum {
+        let items = kind::make_kind_enum(kind, ast, data, &variant_definitions)?;
+        outer_items.extend(items.definition);
+        result.extend(items.impls);
+    }
+    let feature_gate = make_feature_gate(&options, cfg_gate);
+    let outer_items = gate_items(outer_items, feature_gate.as_ref())?;
     let result = quote! {
+        #(#outer_items)*
+
+        #feature_gate
         #[allow(non_camel_case_types, non_snake_case, unused_qualifications)]
         const _: () = {
             #result
@@ -187,19 +794,608 @@ pub fn derive_enum_template(input: TokenStream) -> TokenStream {
             impl #impl_generics ::std::fmt::Display for #enum_name #ty_generics #where_clause {
                 #[inline]
                 fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                    askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+                    #display_impl
                 }
             }
         };
     };
-    result.into()
+    if print_code {
+        eprintln!("{}", result);
+    }
+    Ok(result)
+}
+
+/// Whether `#[template(print = "code")]` or `print = "all"` asks to print the generated code
+fn parse_print(global_meta: Option<&TemplateArgs>) -> Result<bool, TokenStream> {
+    let lit = match global_meta.and_then(|meta| meta.value("print")) {
+        Some(value) => args::lit_str(value).map_err(into_error)?,
+        None => return Ok(false),
+    };
+    match lit.value().as_str() {
+        "none" | "ast" => Ok(false),
+        "code" | "all" => Ok(true),
+        _ => Err(fail_at(
+            lit,
+            "expected `print = \"none\"`, `\"ast\"`, `\"code\"` or `\"all\"`",
+        )),
+    }
+}
+
+/// The bodies of `render()` and `render_into()`, and whether they reload changed templates
+fn make_render_bodies(
+    ast: &DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream, bool) {
+    let static_ty_generics = make_static_ty_generics(ast);
+    let mut render_impl = make_render_impl(
+        ast,
+        options,
+        data,
+        variant_definitions,
+        "render",
+        Punctuated::new(),
+        Flavor::Primary,
+    )
+    .into_token_stream();
+    if let Some(factor) = options.check_size_hint {
+        render_impl = checks::check_size_hint(
+            factor,
+            data,
+            variant_definitions,
+            &static_ty_generics,
+            render_impl,
+        );
+    }
+    if options.validate_json {
+        render_impl =
+            checks::validate_json(data, variant_definitions, &static_ty_generics, render_impl);
+    }
+    if let Some(newlines) = &options.newlines {
+        render_impl = newlines::make_render(newlines, ast, data, variant_definitions, render_impl);
+    }
+    if let Some(capacity) = options.memoize {
+        render_impl = memoize::make_render(data, render_impl, capacity);
+    }
+
+    let mut render_into_impl = make_render_impl(
+        ast,
+        options,
+        data,
+        variant_definitions,
+        "render_into",
+        Punctuated::from_iter([syn::Expr::Path(parse_quote!(writer))]),
+        Flavor::Primary,
+    )
+    .into_token_stream();
+    if let Some(newlines) = &options.newlines {
+        render_into_impl =
+            newlines::make_render_into(newlines, ast, data, variant_definitions, render_into_impl);
+    }
+
+    let reloads = variant_definitions
+        .iter()
+        .any(|definition| hot_reload::reloads(options, definition));
+    if reloads {
+        let hot_reload = hot_reload::make_hot_reload(ast, options, data, variant_definitions);
+        render_impl = quote! {
+            #[cfg(debug_assertions)]
+            if let ::std::option::Option::Some(rendered) = #hot_reload {
+                return ::std::result::Result::Ok(rendered);
+            }
+            #render_impl
+        };
+        render_into_impl = quote! {
+            #[cfg(debug_assertions)]
+            if let ::std::option::Option::Some(rendered) = #hot_reload {
+                return ::std::result::Result::Ok(writer.write_str(&rendered)?);
+            }
+            #render_into_impl
+        };
+    }
+    (render_impl, render_into_impl, reloads)
+}
+
+/// The runtime items that the options and variants need, to be put into the `const _` block once
+fn make_runtime(
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    reloads: bool,
+) -> proc_macro2::TokenStream {
+    let mut result = quote!();
+    if reloads {
+        result.extend(hot_reload::make_runtime());
+    }
+    if options.newlines.is_some() {
+        result.extend(newlines::make_writer());
+    }
+    if variant_definitions
+        .iter()
+        .any(|definition| definition.minify)
+    {
+        result.extend(minify::make_runtime());
+    }
+    if options.memoize.is_some()
+        || variant_definitions
+            .iter()
+            .any(|definition| definition.memoize.is_some())
+    {
+        result.extend(memoize::make_key());
+    }
+    if options.coverage() {
+        result.extend(coverage::make_table(data));
+    }
+    if options.conditional {
+        result.extend(conditional::make_helpers());
+    }
+    if options.icu {
+        result.extend(icu::make_runtime());
+    }
+    result
+}
+
+/// `render_unescaped()`, `render_locale()`, `render_as()` and `render_block()`, if they are used
+fn make_flavored_methods(
+    ast: &DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> Vec<syn::ImplItem> {
+    let render_impl = |flavor| {
+        make_render_impl(
+            ast,
+            options,
+            data,
+            variant_definitions,
+            "render",
+            Punctuated::new(),
+            flavor,
+        )
+    };
+    let mut methods = Vec::new();
+    if options.unescaped {
+        methods.push(unescaped::make_method(
+            &ast.vis,
+            render_impl(Flavor::Unescaped),
+        ));
+    }
+    if options.default_locale.is_some() {
+        let locales = variant_definitions
+            .iter()
+            .flat_map(|definition| definition.locales.iter().map(|(locale, _)| locale));
+        let render_impls =
+            render_impls_by_name(locales, |locale| render_impl(Flavor::Locale(locale)));
+        methods.push(locale::make_method(&ast.vis, render_impls));
+    }
+    let formats = variant_definitions
+        .iter()
+        .flat_map(|definition| &definition.format_names);
+    let render_impls = render_impls_by_name(formats, |format| render_impl(Flavor::Format(format)));
+    if !render_impls.is_empty() {
+        let names = match_variants(data, variant_definitions, |definition| {
+            let names = &definition.format_names;
+            quote!(&[#(#names),*])
+        });
+        methods.extend(format::make_methods(&ast.vis, names, render_impls));
+    }
+    let blocks = variant_definitions
+        .iter()
+        .flat_map(|definition| definition.blocks.iter().map(|(name, _)| name));
+    let render_impls = render_impls_by_name(blocks, |block| render_impl(Flavor::Block(block)));
+    if !render_impls.is_empty() {
+        let names = match_variants(data, variant_definitions, |definition| {
+            let names = definition.blocks.iter().map(|(name, _)| name);
+            quote!(&[#(#names),*])
+        });
+        methods.extend(block::make_methods(&ast.vis, names, render_impls));
+    }
+    methods
+}
+
+/// One render implementation per distinct name, e.g. per locale, sorted by the name
+fn render_impls_by_name<'a>(
+    names: impl Iterator<Item = &'a String>,
+    render_impl: impl Fn(&'a str) -> syn::ExprMatch,
+) -> Vec<(String, syn::ExprMatch)> {
+    let mut names = names.collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .map(|name| (name.clone(), render_impl(name)))
+        .collect()
+}
+
+/// The `#[cfg(…)]` of `#[enum_template(feature_gate = "…")]` and `#[template(cfg = "…")]`
+fn make_feature_gate(options: &EnumOptions, cfg_gate: Option<syn::Meta>) -> Option<syn::Attribute> {
+    let gates = options
+        .feature_gate
+        .iter()
+        .map(|feature| -> syn::Meta { parse_quote!(feature = #feature) })
+        .chain(cfg_gate)
+        .collect::<Vec<_>>();
+    match gates.as_slice() {
+        [] => None,
+        [gate] => Some(parse_quote!(#[cfg(#gate)])),
+        gates => Some(parse_quote!(#[cfg(all(#(#gates),*))])),
+    }
+}
+
+/// The items of `make_outer_items()`, each with the `feature_gate`
+fn gate_items(
+    items: proc_macro2::TokenStream,
+    feature_gate: Option<&syn::Attribute>,
+) -> Result<Vec<syn::Item>, TokenStream> {
+    let file = syn::parse2::<syn::File>(items).map_err(into_error)?;
+    Ok(file
+        .items
+        .into_iter()
+        .map(|mut item| {
+            if let Some(feature_gate) = feature_gate {
+                item_attrs(&mut item).insert(0, feature_gate.clone());
+            }
+            item
+        })
+        .collect())
+}
+
+/// `::<T, U, …>`, the type generics without lifetimes
+fn make_static_ty_generics(ast: &DeriveInput) -> proc_macro2::TokenStream {
+    let static_ty_generics = ast
+        .generics
+        .params
+        .iter()
+        .filter_map(|g| match g {
+            syn::GenericParam::Type(param) => Some(&param.ident),
+            syn::GenericParam::Const(param) => Some(&param.ident),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Punctuated<_, Token![,]>>();
+    quote!(::<#static_ty_generics>)
+}
+
+/// `match self { Self::Variant { .. } => f(definition), … }`
+fn match_variants(
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    mut f: impl FnMut(&VariantDefinition) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let arms = data
+        .variants
+        .iter()
+        .zip(variant_definitions)
+        .map(|(variant, definition)| {
+            let self_variant_name = &variant.ident;
+            let expr = f(definition);
+            quote!(Self::#self_variant_name { .. } => #expr,)
+        });
+    quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+/// Items that need to be nameable by the user, so they cannot be put into the `const _` block
+fn make_outer_items(ast: &DeriveInput, options: &EnumOptions) -> proc_macro2::TokenStream {
+    let vis = &ast.vis;
+    let mut items = quote!();
+    if options.write_to_file {
+        let write_error = write_error_ident(ast);
+        let doc = format!("An error returned by [`{}::write_to_file()`]", ast.ident);
+        items.extend(quote! {
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug)]
+            #vis enum #write_error {
+                /// The template could not be rendered
+                Render(askama::Error),
+                /// The file could not be written
+                Io(::std::io::Error),
+            }
+
+            impl ::std::fmt::Display for #write_error {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    match self {
+                        Self::Render(_) => f.write_str("could not render template"),
+                        Self::Io(_) => f.write_str("could not write file"),
+                    }
+                }
+            }
+
+            impl ::std::error::Error for #write_error {
+                fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                    match self {
+                        Self::Render(err) => ::std::option::Option::Some(err),
+                        Self::Io(err) => ::std::option::Option::Some(err),
+                    }
+                }
+            }
+
+            impl ::std::convert::From<askama::Error> for #write_error {
+                #[inline]
+                fn from(err: askama::Error) -> Self {
+                    Self::Render(err)
+                }
+            }
+
+            impl ::std::convert::From<::std::io::Error> for #write_error {
+                #[inline]
+                fn from(err: ::std::io::Error) -> Self {
+                    Self::Io(err)
+                }
+            }
+        });
+    }
+    if options.error_context {
+        let render_error = render_error_ident(ast);
+        let doc = format!(
+            "An error of a variant of [`{}`], wrapped in `askama::Error::Custom`",
+            ast.ident,
+        );
+        items.extend(quote! {
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug)]
+            #vis struct #render_error {
+                /// The variant that could not be rendered, e.g. `"MyEnum::Variant"`
+                pub variant: &'static ::std::primitive::str,
+                /// The original error
+                pub error: askama::Error,
+            }
+
+            impl ::std::fmt::Display for #render_error {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::write!(f, "could not render {}: {}", self.variant, self.error)
+                }
+            }
+
+            impl ::std::error::Error for #render_error {
+                fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                    ::std::option::Option::Some(&self.error)
+                }
+            }
+        });
+    }
+    items
+}
+
+/// The attributes of the items that `make_outer_items()` and `make_kind_enum()` generate
+fn item_attrs(item: &mut syn::Item) -> &mut Vec<syn::Attribute> {
+    match item {
+        syn::Item::Enum(item) => &mut item.attrs,
+        syn::Item::Struct(item) => &mut item.attrs,
+        syn::Item::Impl(item) => &mut item.attrs,
+        item => unreachable!("unexpected item {}", item.to_token_stream()),
+    }
+}
+
+fn write_error_ident(ast: &DeriveInput) -> syn::Ident {
+    syn::Ident::new(&format!("{}WriteError", ast.ident), ast.ident.span())
+}
+
+fn render_error_ident(ast: &DeriveInput) -> syn::Ident {
+    syn::Ident::new(&format!("{}RenderError", ast.ident), ast.ident.span())
+}
+
+fn make_inherent_methods(
+    ast: &DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> Vec<syn::ImplItem> {
+    let vis = &ast.vis;
+    let mut methods = methods::make_accessors(ast, options, data, variant_definitions);
+    let static_ty_generics = make_static_ty_generics(ast);
+    let response_parts = response::Parts::of_self(data, variant_definitions, &static_ty_generics);
+    if options.write_to_file {
+        let write_error = write_error_ident(ast);
+        methods.push(parse_quote! {
+            /// Render the template into a file
+            ///
+            /// Missing parent directories are created.
+            /// The output is streamed into the file, so if rendering fails,
+            /// the file may contain partial output.
+            #vis fn write_to_file(
+                &self,
+                path: impl ::std::convert::AsRef<::std::path::Path>,
+            ) -> ::std::result::Result<(), #write_error> {
+                struct Adapter<W> {
+                    inner: W,
+                    error: ::std::option::Option<::std::io::Error>,
+                }
+
+                impl<W: ::std::io::Write> ::std::fmt::Write for Adapter<W> {
+                    fn write_str(&mut self, s: &::std::primitive::str) -> ::std::fmt::Result {
+                        match self.inner.write_all(s.as_bytes()) {
+                            ::std::result::Result::Ok(()) => ::std::result::Result::Ok(()),
+                            ::std::result::Result::Err(err) => {
+                                self.error = ::std::option::Option::Some(err);
+                                ::std::result::Result::Err(::std::fmt::Error)
+                            }
+                        }
+                    }
+                }
+
+                let path = path.as_ref();
+                if let ::std::option::Option::Some(parent) = path.parent() {
+                    ::std::fs::create_dir_all(parent)?;
+                }
+                let file = ::std::fs::File::create(path)?;
+                let mut adapter = Adapter {
+                    inner: ::std::io::BufWriter::new(file),
+                    error: ::std::option::Option::None,
+                };
+                if let ::std::result::Result::Err(err) =
+                    askama::Template::render_into(self, &mut adapter)
+                {
+                    return ::std::result::Result::Err(match adapter.error {
+                        ::std::option::Option::Some(err) => #write_error::Io(err),
+                        ::std::option::Option::None => #write_error::Render(err),
+                    });
+                }
+                ::std::io::Write::flush(&mut adapter.inner)?;
+                adapter.inner.get_ref().sync_all()?;
+                ::std::result::Result::Ok(())
+            }
+        });
+    }
+    if options.conditional {
+        methods.extend(conditional::make_methods(vis, &response_parts));
+    }
+    methods.extend(memoize::make_clear(vis, variant_definitions));
+    if options.icu {
+        methods.push(icu::make_render_in(vis));
+    }
+    if options.coverage() {
+        let report = coverage::make_report(data);
+        methods.push(parse_quote! {
+            /// The names of all variants, and whether they were rendered since the program started
+            #vis fn render_coverage(
+            ) -> ::std::vec::Vec<(&'static ::std::primitive::str, ::std::primitive::bool)> {
+                #report
+            }
+        });
+    }
+    if options.schema {
+        let schema = schema::context_schema(ast, options, data);
+        methods.push(parse_quote! {
+            /// A JSON description of the variables that each variant's template can use
+            #vis fn context_schema() -> &'static ::std::primitive::str {
+                #schema
+            }
+        });
+    }
+    if options.rouille {
+        let response = response::rouille(&response_parts, quote!(self), None);
+        methods.push(parse_quote! {
+            /// Render the template into a rouille response
+            ///
+            /// The content type, the status code and the extra headers are the ones of the rendered
+            /// variant. Variants with a `redirect` are answered with a 303 redirect instead.
+            /// If rendering fails, the response has the status code 500, and contains the error message.
+            #vis fn to_rouille_response(&self) -> rouille::Response {
+                #response
+            }
+        });
+    }
+    if options.teloxide {
+        let is_html = match_variants(data, variant_definitions, |definition| {
+            match definition
+                .escape
+                .as_ref()
+                .map(syn::LitStr::value)
+                .as_deref()
+            {
+                Some("html") => quote!(true),
+                Some(_) => quote!(false),
+                None => {
+                    let ty = definition.template_type(&static_ty_generics);
+                    quote!(::std::matches!(
+                        <#ty as askama::Template>::EXTENSION,
+                        ::std::option::Option::Some("html" | "htm"),
+                    ))
+                }
+            }
+        });
+        methods.push(parse_quote! {
+            /// Render the template into a Telegram message and its parse mode
+            ///
+            /// The parse mode is `Some(ParseMode::Html)` if the variant's template is HTML escaped,
+            /// and `None` for plain text.
+            #vis fn to_telegram_text(
+                &self,
+            ) -> askama::Result<(
+                ::std::string::String,
+                ::std::option::Option<teloxide::types::ParseMode>,
+            )> {
+                let text = askama::Template::render(self)?;
+                let is_html: ::std::primitive::bool = #is_html;
+                let parse_mode = match is_html {
+                    true => ::std::option::Option::Some(teloxide::types::ParseMode::Html),
+                    false => ::std::option::Option::None,
+                };
+                ::std::result::Result::Ok((text, parse_mode))
+            }
+        });
+    }
+    if options.wasm {
+        let variant_path = match_variants(data, variant_definitions, |definition| {
+            definition.variant_path.to_token_stream()
+        });
+        methods.push(parse_quote! {
+            /// Render the template into a JavaScript string
+            ///
+            /// The error message contains the name of the variant that could not be rendered.
+            #vis fn render_js(
+                &self,
+            ) -> ::std::result::Result<js_sys::JsString, wasm_bindgen::JsValue> {
+                match askama::Template::render(self) {
+                    ::std::result::Result::Ok(text) => ::std::result::Result::Ok(text.into()),
+                    ::std::result::Result::Err(err) => {
+                        let variant: &::std::primitive::str = #variant_path;
+                        ::std::result::Result::Err(wasm_bindgen::JsValue::from_str(&::std::format!(
+                            "could not render {}: {}",
+                            variant,
+                            err,
+                        )))
+                    }
+                }
+            }
+        });
+    }
+    if options.ws_axum {
+        methods.push(parse_quote! {
+            /// Render the template into an axum websocket text message
+            #vis fn to_axum_ws_message(
+                &self,
+            ) -> askama::Result<axum::extract::ws::Message> {
+                let text = askama::Template::render(self)?;
+                ::std::result::Result::Ok(axum::extract::ws::Message::Text(text.into()))
+            }
+        });
+    }
+    if options.ws_tungstenite {
+        methods.push(parse_quote! {
+            /// Render the template into a tungstenite websocket text message
+            #vis fn to_tungstenite_message(
+                &self,
+            ) -> askama::Result<tokio_tungstenite::tungstenite::Message> {
+                let text = askama::Template::render(self)?;
+                ::std::result::Result::Ok(tokio_tungstenite::tungstenite::Message::Text(
+                    text.into(),
+                ))
+            }
+        });
+    }
+    methods
+}
+
+/// Which of the generated structs of a variant renders it
+#[derive(Clone, Copy)]
+enum Flavor<'a> {
+    /// The struct of the variant's `#[template]`
+    Primary,
+    /// The struct of `#[template(alternate(…))]`, if the variant has one
+    Alternate,
+    /// The struct of `#[enum_template(unescaped)]`
+    Unescaped,
+    /// The struct of `#[template(locale = "…")]`, if the variant has one for the locale
+    Locale(&'a str),
+    /// The struct of `#[template(format = "…")]`, if the variant has one for the format
+    Format(&'a str),
+    /// The struct of a block in `#[template(blocks(…))]`, if the variant has the block
+    Block(&'a str),
 }
 
 fn make_render_impl(
     ast: &DeriveInput,
+    options: &EnumOptions,
     data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
     meth_name: &'static str,
     args: Punctuated<syn::Expr, syn::token::Comma>,
+    flavor: Flavor<'_>,
 ) -> syn::ExprMatch {
     let mut generics = ast.generics.clone();
     generics.params.push(parse_quote!('_));
@@ -209,15 +1405,135 @@ fn make_render_impl(
     let match_render_impl = data
         .variants
         .iter()
+        .zip(variant_definitions)
         .enumerate()
-        .map(|(index, variant)| {
+        .map(|(index, (variant, definition))| {
             let self_variant_name = &variant.ident;
+            // `trim`, `minify`, `postprocess` and `memoize` render into a buffer first, and write the
+            // changed buffer
+            let is_memoized = definition.memoize.is_some() && matches!(flavor, Flavor::Primary);
+            let is_buffered = definition.trim
+                || definition.minify
+                || definition.postprocess.is_some()
+                || is_memoized;
+            let target = &args;
+            let buffer_args = postprocess::buffer_args();
+            let args = match is_buffered && meth_name != "render" {
+                true => &buffer_args,
+                false => &args,
+            };
 
-            let variant_name = &format!("_{}_{}_{}", &ast.ident, index, variant.ident);
             let variant_span = variant.ident.span();
-            let variant_name = syn::Ident::new(variant_name, variant_span);
+            let variant_name = &definition.helper;
+            let struct_name = match (flavor, &definition.alternate) {
+                (Flavor::Alternate, Some(item)) => item.ident.clone(),
+                (Flavor::Unescaped, _) => format_ident!("{}_unescaped", variant_name),
+                (Flavor::Locale(locale), _) => definition
+                    .locales
+                    .iter()
+                    .find(|(other, _)| other == locale)
+                    .map_or_else(|| variant_name.clone(), |(_, item)| item.ident.clone()),
+                (Flavor::Format(format), _) => definition
+                    .formats
+                    .iter()
+                    .find(|(other, _)| other == format)
+                    .map_or_else(|| variant_name.clone(), |(_, item)| item.ident.clone()),
+                (Flavor::Block(block), _) => definition
+                    .blocks
+                    .iter()
+                    .find(|(other, _)| other == block)
+                    .map_or_else(|| variant_name.clone(), |(_, item)| item.ident.clone()),
+                _ => variant_name.clone(),
+            };
 
+            let make_call = |struct_name: syn::Ident| {
             let (pat, base) = match &variant.fields {
+                _ if definition.skip => {
+                    let pat = parse_quote!(Self::#self_variant_name { .. });
+                    (pat, parse_quote!(()))
+                }
+                _ if definition.adapter.is_some() => {
+                    let members = variant.fields.iter().enumerate().map(|(index, field)| {
+                        match &field.ident {
+                            Some(ident) => syn::Member::Named(ident.clone()),
+                            None => syn::Member::Unnamed(index.into()),
+                        }
+                    });
+                    let tmp_names = (0..variant.fields.len())
+                        .map(|index| format_ident!("_{}", index))
+                        .collect::<Vec<_>>();
+                    let adapter = definition.adapter.as_ref().unwrap();
+                    let (path, span) = (&adapter.path, adapter.span);
+                    let pat = parse_quote!(Self::#self_variant_name { #(#members: #tmp_names),* });
+                    let base = quote_spanned!(span=> #path(#(#tmp_names),*));
+                    (pat, syn::Expr::Verbatim(base))
+                }
+                _ if definition.with.is_some() => {
+                    let tmp_names = (0..variant.fields.len())
+                        .map(|index| format_ident!("_{}", index))
+                        .collect::<Vec<_>>();
+                    let with = definition.with.as_ref().unwrap();
+                    let (path, span) = (&with.expr, with.span);
+                    match &variant.fields {
+                        syn::Fields::Named(fields) => {
+                            let names = fields.named.iter().map(|field| &field.ident);
+                            let names = names.collect::<Vec<_>>();
+                            let pat = parse_quote!(Self::#self_variant_name {
+                                #(#names: #tmp_names),*
+                            });
+                            let base = quote_spanned!(span=> #path { #(#names: #tmp_names),* });
+                            (pat, syn::Expr::Verbatim(base))
+                        }
+                        syn::Fields::Unnamed(_) => {
+                            let pat = parse_quote!(Self::#self_variant_name(#(#tmp_names),*));
+                            let base = quote_spanned!(span=> #path(#(#tmp_names),*));
+                            (pat, syn::Expr::Verbatim(base))
+                        }
+                        syn::Fields::Unit => {
+                            let pat = parse_quote!(Self::#self_variant_name);
+                            (pat, syn::Expr::Verbatim(quote_spanned!(span=> #path)))
+                        }
+                    }
+                }
+                _ if definition.transparent.is_some() => {
+                    let member = &definition.transparent.as_ref().unwrap().member;
+                    let pat = parse_quote!(Self::#self_variant_name { #member: _0, .. });
+                    (pat, parse_quote!(_0))
+                }
+                _ if definition.display.is_some() => {
+                    let member = definition.display.as_ref().unwrap();
+                    let pat = parse_quote!(Self::#self_variant_name { #member: _0, .. });
+                    (pat, parse_quote!(_0))
+                }
+                _ if definition.fmt.is_some() => {
+                    let fields = &definition.fmt.as_ref().unwrap().fields;
+                    let (members, names): (Vec<_>, Vec<_>) = fields.iter().cloned().unzip();
+                    let pat = parse_quote!(Self::#self_variant_name { #(#members: #names,)* .. });
+                    (pat, parse_quote!(()))
+                }
+                syn::Fields::Unnamed(_) if definition.item.is_none() => {
+                    let pat = parse_quote!(Self::#self_variant_name(_0));
+                    let value = &options.untagged.as_ref().unwrap().value;
+                    let label = unraw(&variant.ident);
+                    let computed = options.compute.iter().map(Computed::field_value);
+                    let outer = definition.outer.iter();
+                    let base = parse_quote!(#struct_name #inst_ty_generics {
+                        #value: _0,
+                        __variant: #label,
+                        #(#computed,)*
+                        #(#outer: self,)*
+                        #variant_name: ::std::marker::PhantomData,
+                    });
+                    (pat, base)
+                }
+                syn::Fields::Unnamed(_) if definition.delegate => {
+                    let pat = parse_quote!(Self::#self_variant_name(_0));
+                    let base = parse_quote!(#struct_name #inst_ty_generics {
+                        inner: askama::Template::render(_0)?,
+                        #variant_name: ::std::marker::PhantomData,
+                    });
+                    (pat, base)
+                }
                 syn::Fields::Named(fields) => {
                     let tmp_names = fields
                         .named
@@ -247,17 +1563,41 @@ fn make_render_impl(
                     let mut fields = tmp_names
                         .iter()
                         .zip(fields.named.iter())
-                        .map(|(tmp, source)| syn::FieldValue {
+                        .zip(&definition.field_transforms)
+                        .map(|((tmp, source), transform)| syn::FieldValue {
                             attrs: vec![],
-                            member: syn::Member::Named(source.ident.clone().unwrap()),
+                            member: syn::Member::Named(
+                                transform
+                                    .as_ref()
+                                    .and_then(FieldTransform::rename)
+                                    .unwrap_or_else(|| source.ident.as_ref().unwrap())
+                                    .clone(),
+                            ),
                             colon_token: Some(Token![:](variant_span)),
-                            expr: parse_quote!(#tmp),
+                            expr: match transform {
+                                Some(transform) => transform.convert(
+                                    tmp,
+                                    &FieldContext {
+                                        options,
+                                        variant_path: &definition.variant_path,
+                                        field: unraw(source.ident.as_ref().unwrap()),
+                                    },
+                                ),
+                                None => parse_quote!(#tmp),
+                            },
                         })
                         .collect::<Punctuated<syn::FieldValue, Token![,]>>();
+                    fields.extend(options.compute.iter().map(Computed::field_value));
+                    if let Some(outer) = &definition.outer {
+                        fields.push(parse_quote!(#outer: self));
+                    }
+                    if let Some(discriminant) = &definition.discriminant {
+                        fields.push(parse_quote!(discriminant: #discriminant));
+                    }
                     fields.push(parse_quote!(#variant_name: ::std::marker::PhantomData));
                     let base = syn::Expr::Struct(syn::ExprStruct {
                         attrs: vec![],
-                        path: parse_quote!(#variant_name #inst_ty_generics),
+                        path: parse_quote!(#struct_name #inst_ty_generics),
                         brace_token: syn::token::Brace(variant_span),
                         fields,
                         dot2_token: None,
@@ -298,42 +1638,198 @@ fn make_render_impl(
 
                     let mut args = tmp_names
                         .iter()
-                        .map(|field_name| {
-                            let expr: syn::Expr = parse_quote!(#field_name);
-                            expr
+                        .zip(&definition.field_transforms)
+                        .enumerate()
+                        .map(|(index, (field_name, transform))| match transform {
+                            Some(transform) => transform.convert(
+                                field_name,
+                                &FieldContext {
+                                    options,
+                                    variant_path: &definition.variant_path,
+                                    field: index.to_string(),
+                                },
+                            ),
+                            None => parse_quote!(#field_name),
                         })
                         .collect::<Punctuated<syn::Expr, Token![,]>>();
                     args.push(parse_quote!(::std::marker::PhantomData));
                     let base = syn::Expr::Call(syn::ExprCall {
                         attrs: vec![],
-                        func: parse_quote!(#variant_name #inst_ty_generics),
+                        func: parse_quote!(#struct_name #inst_ty_generics),
                         paren_token: syn::token::Paren(variant_span),
                         args,
                     });
 
                     (pat, base)
                 }
+                syn::Fields::Unit
+                    if !options.compute.is_empty()
+                        || definition.outer.is_some()
+                        || definition.discriminant.is_some() =>
+                {
+                    let pat = parse_quote!(Self :: #self_variant_name);
+                    let computed = options.compute.iter().map(Computed::field_value);
+                    let outer = definition.outer.iter();
+                    let discriminant = definition.discriminant.iter();
+                    let base = parse_quote!(#struct_name #inst_ty_generics {
+                        #(#computed,)*
+                        #(#outer: self,)*
+                        #(discriminant: #discriminant,)*
+                        #variant_name: ::std::marker::PhantomData,
+                    });
+                    (pat, base)
+                }
                 syn::Fields::Unit => {
                     let pat = parse_quote!(Self :: #self_variant_name);
                     let base =
-                        parse_quote!(#variant_name #inst_ty_generics(::std::marker::PhantomData));
+                        parse_quote!(#struct_name #inst_ty_generics(::std::marker::PhantomData));
                     (pat, base)
                 }
             };
-            let field = syn::Expr::Field(syn::ExprField {
-                attrs: vec![],
-                base: Box::new(base),
-                dot_token: Token![.](variant_span),
-                member: syn::Member::Named(syn::Ident::new(meth_name, variant_span)),
-            });
-            let call = syn::Expr::Call(syn::ExprCall {
-                attrs: vec![],
-                func: field.into(),
-                paren_token: syn::token::Paren(variant_span),
-                args: args.clone(),
-            });
-            syn::Arm {
-                attrs: vec![],
+            let call = match (&definition.size_hint, meth_name) {
+                _ if definition.redirect.is_some() => {
+                    let msg = format!(
+                        "`{}` redirects to {:?}, so it has no body",
+                        definition.variant_path,
+                        definition.redirect.as_ref().unwrap().value(),
+                    );
+                    parse_quote!(::std::result::Result::Err(askama::Error::Custom(
+                        ::std::convert::From::from(#msg)
+                    )))
+                }
+                _ if definition.skip => match meth_name {
+                    "render" => {
+                        parse_quote!(::std::result::Result::Ok(::std::string::String::new()))
+                    }
+                    _ => parse_quote!(::std::result::Result::Ok(())),
+                },
+                _ if definition.transparent.is_some() => {
+                    let meth_name = syn::Ident::new(meth_name, variant_span);
+                    let field = match definition.transparent.as_ref().unwrap().is_reference {
+                        true => quote!(*#base),
+                        false => quote!(#base),
+                    };
+                    parse_quote!(askama::Template::#meth_name(#field, #args))
+                }
+                _ if definition.display.is_some() => match meth_name {
+                    "render" => parse_quote!(::std::result::Result::Ok(
+                        ::std::string::ToString::to_string(#base)
+                    )),
+                    _ => {
+                        parse_quote!(::std::write!(#args, "{}", #base).map_err(askama::Error::from))
+                    }
+                },
+                _ if definition.fmt.is_some() => {
+                    let fmt = definition.fmt.as_ref().unwrap();
+                    let format = &fmt.format;
+                    let names = fmt.fields.iter().map(|(_, name)| name);
+                    match meth_name {
+                        "render" => parse_quote!(::std::result::Result::Ok(::std::format!(
+                            #format,
+                            #(#names = #names,)*
+                        ))),
+                        _ => parse_quote!(::std::write!(#args, #format, #(#names = #names,)*)
+                            .map_err(askama::Error::from)),
+                    }
+                }
+                _ if definition.with.is_some() => {
+                    let span = definition.with.as_ref().unwrap().span;
+                    let meth_name = syn::Ident::new(meth_name, span);
+                    syn::Expr::Verbatim(
+                        quote_spanned!(span=> askama::Template::#meth_name(&#base, #args)),
+                    )
+                }
+                _ if definition.adapter.is_some() => {
+                    let span = definition.adapter.as_ref().unwrap().span;
+                    let meth_name = syn::Ident::new(meth_name, span);
+                    syn::Expr::Verbatim(
+                        quote_spanned!(span=> askama::Template::#meth_name(&#base, #args)),
+                    )
+                }
+                (Some(size_hint), "render") => parse_quote!({
+                    let mut buf = ::std::string::String::with_capacity(#size_hint);
+                    askama::Template::render_into(&#base, &mut buf).map(|()| buf)
+                }),
+                _ => {
+                    let field = syn::Expr::Field(syn::ExprField {
+                        attrs: vec![],
+                        base: Box::new(base),
+                        dot_token: Token![.](variant_span),
+                        member: syn::Member::Named(syn::Ident::new(meth_name, variant_span)),
+                    });
+                    syn::Expr::Call(syn::ExprCall {
+                        attrs: vec![],
+                        func: field.into(),
+                        paren_token: syn::token::Paren(variant_span),
+                        args: args.clone(),
+                    })
+                }
+            };
+            (pat, call)
+            };
+            let (pat, mut call) = make_call(struct_name);
+            if let Flavor::Primary = flavor {
+                // evaluate the conditions in declaration order, so the chain is built from the back
+                for (condition, item) in definition.guards.iter().rev() {
+                    let (_, then) = make_call(item.ident.clone());
+                    call = guard::make_branch(variant, condition, then, call);
+                }
+            }
+            if is_buffered {
+                let memoized = match is_memoized {
+                    true => Some(variant),
+                    false => None,
+                };
+                call = postprocess::wrap(call, meth_name, definition, memoized, target);
+            }
+            if definition.deprecated.is_some() {
+                let marker = definition.deprecation_marker();
+                call = parse_quote!({
+                    #marker();
+                    #call
+                });
+            }
+            if options.error_context {
+                let render_error = render_error_ident(ast);
+                let variant_path = &definition.variant_path;
+                call = parse_quote! {
+                    #call.map_err(|error| {
+                        askama::Error::Custom(::std::boxed::Box::new(#render_error {
+                            variant: #variant_path,
+                            error,
+                        }))
+                    })
+                };
+            }
+            if let (Some(prefix), true) = (&options.metrics, cfg!(feature = "metrics")) {
+                let total = format!("{}_render_total", prefix.value());
+                let errors = format!("{}_render_errors_total", prefix.value());
+                let enum_label = unraw(&ast.ident);
+                let variant_label = unraw(&variant.ident);
+                call = parse_quote!({
+                    metrics::counter!(
+                        #total, "enum" => #enum_label, "variant" => #variant_label,
+                    )
+                    .increment(1);
+                    let result = #call;
+                    if result.is_err() {
+                        metrics::counter!(
+                            #errors, "enum" => #enum_label, "variant" => #variant_label,
+                        )
+                        .increment(1);
+                    }
+                    result
+                });
+            }
+            if options.coverage() {
+                let mark = coverage::make_mark(index);
+                call = parse_quote!({
+                    #mark;
+                    #call
+                });
+            }
+            syn::Arm {
+                attrs: vec![],
                 pat,
                 guard: None,
                 fat_arrow_token: Token![=>](variant_span),
@@ -351,142 +1847,1752 @@ fn make_render_impl(
     }
 }
 
-fn make_variant_definitions(
-    global_meta: Option<&syn::Attribute>,
-    ast: &DeriveInput,
-    data: &syn::DataEnum,
-    default_variant_name: &mut Option<syn::Ident>,
-) -> Result<Vec<syn::DeriveInput>, TokenStream> {
-    data.variants
-        .iter()
-        .enumerate()
-        .map(|(index, variant)| {
-            let variant_name = &format!("_{}_{}_{}", &ast.ident, index, variant.ident);
-            let variant_span = variant.ident.span();
-            let variant_lifetime = syn::Lifetime::new(&format!("'{}", variant_name), variant_span);
-            let variant_name = syn::Ident::new(variant_name, variant_span);
-
-            let mut local_meta = None;
-            for attr in &variant.attrs {
-                let meta_list = match attr.parse_meta() {
-                    Ok(syn::Meta::List(attr)) => attr,
-                    _ => continue,
-                };
-                if meta_list.path.is_ident("template") {
-                    if local_meta.is_some() {
-                        return Err(fail_at(
-                            meta_list.path,
-                            "cannot have more than one #[template] attribute for a variant",
-                        ));
-                    }
-                    local_meta = Some(attr);
+struct VariantDefinition {
+    /// `_EnumName_index_VariantName`
+    name: syn::Ident,
+    /// The struct that implements the variant's template
+    helper: syn::Ident,
+    /// The definition of `helper`, or `None` if the variant uses the shared untagged struct
+    item: Option<syn::DeriveInput>,
+    /// `EnumName::VariantName`, used in error messages
+    variant_path: String,
+    /// `#[template(deprecated = "…")]`
+    deprecated: Option<syn::LitStr>,
+    /// `#[template(mime = "…")]`
+    mime: Option<syn::LitStr>,
+    /// `#[template(status = …)]`, the HTTP status code returned by `status()`
+    status: Option<syn::LitInt>,
+    /// `#[template(header("Name" = "value"))]`, the extra headers returned by `headers()`
+    headers: Vec<(syn::LitStr, syn::LitStr)>,
+    /// `#[template(path = "…")]`
+    template_path: Option<syn::LitStr>,
+    /// `#[template(escape = "…")]`
+    escape: Option<syn::LitStr>,
+    /// `#[template(size_hint = …)]`, overrides the `SIZE_HINT` of `helper`
+    size_hint: Option<syn::LitInt>,
+    /// The predicates of `#[template(cfg(predicate, …), …)]`
+    cfg_alternatives: Vec<syn::Meta>,
+    /// The struct that implements `#[template(alternate(…))]`, same fields as `item`
+    alternate: Option<syn::DeriveInput>,
+    /// `#[template(delegate, source = "…")]`, the rendered field is stored as `inner`
+    delegate: bool,
+    /// `#[template(delegate)]` without a `source`, the field is rendered directly
+    transparent: Option<delegate::Transparent>,
+    /// `#[template(delegate_with = "…")]`, the output of the adapter is rendered
+    adapter: Option<delegate::Adapter>,
+    /// `#[template(skip)]`, the variant renders nothing, and `helper` only supplies constants
+    skip: bool,
+    /// `#[template(redirect = "…")]`, rendering fails, and the web integrations redirect instead
+    redirect: Option<syn::LitStr>,
+    /// `#[template(display = "…")]`, the field is written with `Display`, and `helper` only
+    /// supplies constants
+    display: Option<syn::Member>,
+    /// `#[template(fmt = "…")]`, the fields are written with `write!()`, and `helper` only supplies
+    /// constants
+    fmt: Option<fmt::Fmt>,
+    /// `#[template(expose_enum = "…")]`, the name of the field that references the whole enum
+    outer: Option<syn::Ident>,
+    /// `#[template(expose_discriminant)]`, the value of the field `discriminant`
+    discriminant: Option<syn::Expr>,
+    /// `#[template(trim)]`, leading and trailing ASCII whitespace is removed from the output
+    trim: bool,
+    /// `#[template(minify)]`, the HTML output is minified
+    minify: bool,
+    /// `#[template(postprocess = "…")]`, the function that changes the output
+    postprocess: Option<syn::Path>,
+    /// `#[template(memoize = "…")]`, the key of the variant's cached output
+    memoize: Option<syn::Expr>,
+    /// `#[template(with = "…")]`, the variant's fields are rendered by an existing template struct
+    with: Option<with::With>,
+    /// `#[template(route = "…")]`, the path of the variant in `router()`
+    route: Option<syn::LitStr>,
+    /// `#[template(output = "…")]`, the path of the variant in `output_path()`
+    output: Option<fmt::Fmt>,
+    /// `#[template(example = "…")]` or the examples of the fields, used in `examples()`
+    example: Option<syn::Expr>,
+    /// `#[template(meta(key = "value", …))]`, returned by `metadata()`
+    metadata: Vec<(syn::Ident, syn::LitStr)>,
+    /// The `#[template_field(…)]` conversions of the variant's fields
+    field_transforms: Vec<Option<FieldTransform>>,
+    /// The files and environment variables the templates were read from, the crate is rebuilt if they change
+    includes: Vec<expand::Include>,
+    /// The structs of `#[template(locale = "…")]` for the locales other than the default one
+    locales: Vec<(String, syn::DeriveInput)>,
+    /// The structs of `#[template(format = "…")]` for the formats other than the one of `helper`
+    formats: Vec<(String, syn::DeriveInput)>,
+    /// The names of all formats of the variant, returned by `formats()`
+    format_names: Vec<String>,
+    /// The structs of `#[template(blocks(…))]`, used by `render_block()`
+    blocks: Vec<(String, syn::DeriveInput)>,
+    /// The conditions and structs of `#[template(if = "…")]`, in declaration order
+    guards: Vec<(syn::Expr, syn::DeriveInput)>,
+}
+
+impl VariantDefinition {
+    /// All generated structs of the variant
+    fn items(&self) -> impl Iterator<Item = &DeriveInput> {
+        let others = self
+            .locales
+            .iter()
+            .chain(&self.formats)
+            .chain(&self.blocks)
+            .map(|(_, item)| item)
+            .chain(self.guards.iter().map(|(_, item)| item));
+        self.item.iter().chain(&self.alternate).chain(others)
+    }
+
+    fn deprecation_marker(&self) -> syn::Ident {
+        let name = format!("{}_deprecated", self.name);
+        syn::Ident::new(&name, self.name.span())
+    }
+
+    /// Whether the variant renders a template, i.e. it can supply the enum's constants
+    fn has_template(&self) -> bool {
+        !self.skip && self.display.is_none() && self.fmt.is_none()
+    }
+
+    /// The type that implements the variant's template: `helper`, or the struct of `with`
+    fn template_type(
+        &self,
+        static_ty_generics: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match &self.with {
+            Some(with) => with.ty.to_token_stream(),
+            None => {
+                let helper = &self.helper;
+                quote!(#helper #static_ty_generics)
+            }
+        }
+    }
+
+    /// The overridden size hint, or the `SIZE_HINT` that askama calculated
+    fn size_hint(&self, static_ty_generics: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if self.skip {
+            return quote!(0);
+        }
+        match &self.size_hint {
+            Some(size_hint) => size_hint.to_token_stream(),
+            None => {
+                let ty = self.template_type(static_ty_generics);
+                quote!(<#ty as askama::Template>::SIZE_HINT)
+            }
+        }
+    }
+}
+
+impl ToTokens for VariantDefinition {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.item.to_tokens(tokens);
+        if let Some(transparent) = &self.transparent {
+            transparent.alias.to_tokens(tokens);
+        }
+        if let Some(adapter) = &self.adapter {
+            adapter.check.to_tokens(tokens);
+        }
+        for include in &self.includes {
+            include.to_tokens(tokens);
+        }
+        self.alternate.to_tokens(tokens);
+        for (_, item) in self.locales.iter().chain(&self.formats).chain(&self.blocks) {
+            item.to_tokens(tokens);
+        }
+        for (_, item) in &self.guards {
+            item.to_tokens(tokens);
+        }
+        if self.memoize.is_some() {
+            tokens.extend(memoize::make_cache(&self.helper));
+        }
+        if let Some(note) = &self.deprecated {
+            let marker = self.deprecation_marker();
+            tokens.extend(quote! {
+                #[deprecated(note = #note)]
+                #[inline(always)]
+                fn #marker() {}
+            });
+        }
+        if !self.cfg_alternatives.is_empty() {
+            let predicates = &self.cfg_alternatives;
+            let msg = format!(
+                "no #[template(cfg(…))] alternative is active for {}",
+                self.variant_path,
+            );
+            let span = self.name.span();
+            tokens.extend(quote_spanned! {span=>
+                #[cfg(not(any(#(#predicates),*)))]
+                ::std::compile_error!(#msg);
+            });
+            for (index, a) in predicates.iter().enumerate() {
+                for b in &predicates[index + 1..] {
+                    let msg = format!(
+                        "more than one #[template(cfg(…))] alternative is active for {}",
+                        self.variant_path,
+                    );
+                    tokens.extend(quote_spanned! {span=>
+                        #[cfg(all(#a, #b))]
+                        ::std::compile_error!(#msg);
+                    });
                 }
             }
-            if local_meta.is_none() && default_variant_name.is_none() {
-                *default_variant_name = Some(variant_name.clone());
+        }
+    }
+}
+
+/// Options in `#[enum_template(…)]`
+#[derive(Default)]
+struct EnumOptions {
+    /// `#[enum_template(untagged(value = "…"))]`
+    untagged: Option<Untagged>,
+    /// `#[enum_template(check_size_hint(factor = …))]`, the allowed factor
+    check_size_hint: Option<usize>,
+    /// `#[enum_template(metrics(prefix = "…"))]`, the prefix of the counters' names
+    metrics: Option<syn::LitStr>,
+    /// `#[enum_template(extension = "…")]`, overrides `EXTENSION`
+    extension: Option<syn::LitStr>,
+    /// `#[enum_template(mime = "…")]`, overrides `MIME_TYPE`
+    mime: Option<syn::LitStr>,
+    /// `#[enum_template(size_hint = …)]`, overrides `SIZE_HINT`
+    size_hint: Option<syn::LitInt>,
+    /// `#[enum_template(compute(name = "expr", …))]`
+    compute: Vec<Computed>,
+    /// `#[enum_template(memoize(capacity = …))]`, the number of cached renders
+    memoize: Option<usize>,
+    /// `#[enum_template(kind_enum(rename_all = "…"))]`
+    kind_enum: Option<KindEnum>,
+    /// `#[enum_template(sanitize(config = "…"))]`, the ammonia `Builder` to use
+    sanitize_config: Option<syn::Expr>,
+    /// `#[enum_template(validate_json)]`
+    validate_json: bool,
+    /// `#[enum_template(error_context)]`
+    error_context: bool,
+    /// `#[enum_template(feature_gate = "…")]`, the feature that enables all generated code
+    feature_gate: Option<syn::LitStr>,
+    /// `#[enum_template(newlines = "…")]`
+    newlines: Option<Newlines>,
+    /// `#[enum_template(coverage)]`
+    coverage: bool,
+    /// `#[enum_template(check_unused_templates(dir = "…", ignore(…)))]`
+    check_unused_templates: Option<UnusedTemplates>,
+    /// `#[enum_template(router)]`, used in error messages
+    router: Option<syn::Path>,
+    /// `#[enum_template(examples(strict))]`
+    examples: Option<Examples>,
+    /// `#[enum_template(conditional)]`
+    conditional: bool,
+    /// `#[enum_template(rouille)]`
+    rouille: bool,
+    /// `#[enum_template(ws_axum)]`
+    ws_axum: bool,
+    /// `#[enum_template(ws_tungstenite)]`
+    ws_tungstenite: bool,
+    /// `#[enum_template(teloxide)]`
+    teloxide: bool,
+    /// `#[enum_template(wasm)]`
+    wasm: bool,
+    /// `#[enum_template(schema)]`
+    schema: bool,
+    /// `#[enum_template(write_to_file)]`
+    write_to_file: bool,
+    /// `#[enum_template(methods(…))]`
+    methods: Option<methods::Methods>,
+    /// `#[enum_template(icu)]`
+    icu: bool,
+    /// `#[enum_template(unescaped)]`
+    unescaped: bool,
+    /// `#[enum_template(default_locale = "…")]`, the locale of `render()`
+    default_locale: Option<syn::LitStr>,
+}
+
+impl EnumOptions {
+    /// Whether renders are recorded, only if the feature `coverage` is enabled
+    fn coverage(&self) -> bool {
+        self.coverage && cfg!(feature = "coverage")
+    }
+}
+
+/// A value that is computed for every render, and stored in every generated struct
+struct Computed {
+    name: syn::Ident,
+    expr: syn::Expr,
+    /// The type of `expr` if it is stored as is, otherwise it is converted into a `String`
+    ty: Option<syn::Type>,
+}
+
+impl Computed {
+    /// `name: ToString::to_string(&(expr))`, or `name: expr`
+    fn field_value(&self) -> syn::FieldValue {
+        let Self { name, expr, ty } = self;
+        match ty {
+            Some(_) => parse_quote!(#name: #expr),
+            None => parse_quote!(#name: ::std::string::ToString::to_string(&(#expr))),
+        }
+    }
+
+    fn field(&self) -> syn::Field {
+        syn::Field {
+            attrs: vec![],
+            vis: syn::Visibility::Inherited,
+            ident: Some(self.name.clone()),
+            colon_token: Some(Token![:](self.name.span())),
+            ty: match &self.ty {
+                Some(ty) => ty.clone(),
+                None => parse_quote!(::std::string::String),
+            },
+        }
+    }
+}
+
+/// The name of the field of `#[template(expose_enum = "…")]`, or `None` for `expose_enum = ""`
+fn parse_expose_enum(value: &syn::Expr) -> Result<Option<syn::Ident>, TokenStream> {
+    let lit = args::lit_str(value).map_err(into_error)?;
+    match lit.value().is_empty() {
+        true => Ok(None),
+        false => Ok(Some(lit.parse().map_err(into_error)?)),
+    }
+}
+
+/// Render the template of `meta` inside its `#[template(layout = "…", slot = "…")]`
+fn apply_layout(meta: &mut TemplateArgs) -> Result<(), TokenStream> {
+    let slot = match meta.take_key("slot") {
+        Some(value) => Some(args::lit_str(&value).map_err(into_error)?),
+        None => None,
+    };
+    let layout = match meta.take_key("layout") {
+        Some(value) => layout::parse(&value).map_err(into_error)?,
+        None => {
+            if let Some(slot) = slot {
+                return Err(fail_at(slot, "`slot` needs a `layout`"));
             }
-            let meta = match local_meta.or(global_meta) {
-                Some(meta) => meta,
-                None => return Err(fail_at(&variant.ident, "need a #[template] attribute")),
-            };
+            None
+        }
+    };
+    match layout {
+        Some(layout) => layout::apply(meta, &layout, slot.as_ref()).map_err(into_error),
+        None => Ok(()),
+    }
+}
 
-            let (_, ty_generics, _) = ast.generics.split_for_impl();
-            let enum_name = &ast.ident;
-            let phantom_type = parse_quote!(::std::marker::PhantomData::<
-                & #variant_lifetime #enum_name #ty_generics,
-            >);
-            let fields = match &variant.fields {
-                syn::Fields::Named(fields) => {
-                    let mut fields = fields
-                        .named
-                        .iter()
-                        .map(|field| {
-                            let mut field = field.clone();
-                            field.ty = syn::Type::Reference(syn::TypeReference {
-                                and_token: Token![&](field.span()),
-                                lifetime: Some(variant_lifetime.clone()),
-                                mutability: None,
-                                elem: field.ty.into(),
-                            });
-                            field
-                        })
-                        .collect::<Vec<syn::Field>>();
-                    fields.push(syn::Field {
-                        attrs: vec![],
-                        vis: syn::Visibility::Inherited,
-                        ident: Some(variant_name.clone()),
-                        colon_token: Some(Token![:](variant_span)),
-                        ty: phantom_type,
+struct Untagged {
+    /// `#[enum_template(untagged(…))]`, used in error messages
+    path: syn::Path,
+    /// The name of the field that references the variant's content
+    value: syn::Ident,
+}
+
+/// How much longer than its `SIZE_HINT` a rendered template may be with `check_size_hint`
+const DEFAULT_SIZE_HINT_FACTOR: usize = 4;
+
+/// The prefix of the counters' names with `metrics`
+const DEFAULT_METRICS_PREFIX: &str = "askama_enum";
+
+/// The number of cached renders with `memoize`
+const DEFAULT_MEMOIZE_CAPACITY: usize = 256;
+
+fn check_memoize_feature(path: &syn::Path) -> Result<(), TokenStream> {
+    match cfg!(feature = "memoize") {
+        true => Ok(()),
+        false => Err(fail_at(
+            path,
+            "`memoize` needs the feature `memoize` of askama-enum",
+        )),
+    }
+}
+
+fn parse_enum_options(
+    attrs: &[syn::Attribute],
+    data: &syn::DataEnum,
+) -> Result<EnumOptions, TokenStream> {
+    let mut options = EnumOptions::default();
+    let mut icu = None;
+    let mut variant_name = None;
+    let mut expose_index = None;
+    for attr in attrs {
+        if !attr.path.is_ident("enum_template") {
+            continue;
+        }
+        let meta_list = match attr.parse_meta() {
+            Ok(syn::Meta::List(meta_list)) => meta_list,
+            Ok(meta) => return Err(fail_at(meta, "expected #[enum_template(…)]")),
+            Err(err) => return Err(err.into_compile_error().into()),
+        };
+        for nested in meta_list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("untagged") => {
+                    if options.untagged.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    let mut value = None;
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                                if pair.path.is_ident("value") =>
+                            {
+                                value = Some(parse_lit_ident(&pair.lit)?);
+                            }
+                            nested => return Err(fail_at(nested, "expected `value = \"…\"`")),
+                        }
+                    }
+                    let value = match value {
+                        Some(value) => value,
+                        None => return Err(fail_at(list, "expected `untagged(value = \"…\")`")),
+                    };
+                    options.untagged = Some(Untagged {
+                        path: list.path,
+                        value,
                     });
-                    syn::Fields::Named(syn::FieldsNamed {
-                        brace_token: syn::token::Brace(variant_span),
-                        named: Punctuated::from_iter(fields),
-                    })
                 }
-                syn::Fields::Unnamed(fields) => {
-                    let mut fields = fields
-                        .unnamed
-                        .iter()
-                        .map(|field| {
-                            let mut field = field.clone();
-                            field.ty = syn::Type::Reference(syn::TypeReference {
-                                and_token: Token![&](field.span()),
-                                lifetime: Some(variant_lifetime.clone()),
-                                mutability: None,
-                                elem: field.ty.into(),
-                            });
-                            field
-                        })
-                        .collect::<Vec<syn::Field>>();
-                    fields.push(syn::Field {
-                        attrs: vec![],
-                        vis: syn::Visibility::Inherited,
-                        ident: None,
-                        colon_token: None,
-                        ty: phantom_type,
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if path.is_ident("check_size_hint") =>
+                {
+                    if options.check_size_hint.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    options.check_size_hint = Some(DEFAULT_SIZE_HINT_FACTOR);
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list))
+                    if list.path.is_ident("check_size_hint") =>
+                {
+                    if options.check_size_hint.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    let mut factor = DEFAULT_SIZE_HINT_FACTOR;
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                                path,
+                                lit: syn::Lit::Int(lit),
+                                ..
+                            })) if path.is_ident("factor") => {
+                                factor = match lit.base10_parse() {
+                                    Ok(factor) if factor > 0 => factor,
+                                    Ok(_) => return Err(fail_at(lit, "factor must not be zero")),
+                                    Err(err) => return Err(into_error(err)),
+                                };
+                            }
+                            nested => return Err(fail_at(nested, "expected `factor = …`")),
+                        }
+                    }
+                    options.check_size_hint = Some(factor);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("metrics") => {
+                    if options.metrics.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    options.metrics = Some(syn::LitStr::new(DEFAULT_METRICS_PREFIX, path.span()));
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("metrics") => {
+                    if options.metrics.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    let mut prefix = syn::LitStr::new(DEFAULT_METRICS_PREFIX, list.path.span());
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                                path,
+                                lit: syn::Lit::Str(lit),
+                                ..
+                            })) if path.is_ident("prefix") => prefix = lit.clone(),
+                            nested => return Err(fail_at(nested, "expected `prefix = \"…\"`")),
+                        }
+                    }
+                    options.metrics = Some(prefix);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                    if pair.path.is_ident("extension") =>
+                {
+                    if options.extension.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    options.extension = match pair.lit {
+                        syn::Lit::Str(lit) => Some(lit),
+                        lit => return Err(fail_at(lit, "expected a string literal")),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                    if pair.path.is_ident("default_locale") =>
+                {
+                    if options.default_locale.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    options.default_locale = match pair.lit {
+                        syn::Lit::Str(lit) => Some(lit),
+                        lit => return Err(fail_at(lit, "expected a string literal")),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                    if pair.path.is_ident("feature_gate") =>
+                {
+                    if options.feature_gate.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    options.feature_gate = match pair.lit {
+                        syn::Lit::Str(lit) => Some(lit),
+                        lit => return Err(fail_at(lit, "expected a string literal")),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair)) if pair.path.is_ident("mime") => {
+                    if options.mime.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    options.mime = match pair.lit {
+                        syn::Lit::Str(lit) => {
+                            Some(template_meta::parse_mime(lit).map_err(into_error)?)
+                        }
+                        lit => return Err(fail_at(lit, "expected a string literal")),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                    if pair.path.is_ident("size_hint") =>
+                {
+                    if options.size_hint.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    options.size_hint = match pair.lit {
+                        syn::Lit::Int(lit) => {
+                            lit.base10_parse::<usize>().map_err(into_error)?;
+                            Some(lit)
+                        }
+                        lit => return Err(fail_at(lit, "expected an integer literal")),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("compute") => {
+                    for nested in &list.nested {
+                        let pair = match nested {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(pair)) => pair,
+                            nested => return Err(fail_at(nested, "expected `name = \"expr\"`")),
+                        };
+                        let name = match pair.path.get_ident() {
+                            Some(name) => name.clone(),
+                            None => return Err(fail_at(&pair.path, "expected an identifier")),
+                        };
+                        if options.compute.iter().any(|computed| computed.name == name) {
+                            return Err(fail_at(name, "duplicated key"));
+                        }
+                        let expr = match &pair.lit {
+                            syn::Lit::Str(lit) => lit.parse().map_err(into_error)?,
+                            lit => return Err(fail_at(lit, "expected a string literal")),
+                        };
+                        options.compute.push(Computed {
+                            name,
+                            expr,
+                            ty: None,
+                        });
+                    }
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("variant_name") => {
+                    if variant_name.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    variant_name = Some(syn::Ident::new("variant_name", path.span()));
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                    if pair.path.is_ident("variant_name") =>
+                {
+                    if variant_name.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    variant_name = Some(parse_lit_ident(&pair.lit)?);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("expose_index") => {
+                    if expose_index.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    expose_index = Some(path);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("memoize") => {
+                    if options.memoize.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    check_memoize_feature(&path)?;
+                    options.memoize = Some(DEFAULT_MEMOIZE_CAPACITY);
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("memoize") => {
+                    if options.memoize.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    check_memoize_feature(&list.path)?;
+                    let mut capacity = DEFAULT_MEMOIZE_CAPACITY;
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                                path,
+                                lit: syn::Lit::Int(lit),
+                                ..
+                            })) if path.is_ident("capacity") => {
+                                capacity = match lit.base10_parse() {
+                                    Ok(capacity) if capacity > 0 => capacity,
+                                    Ok(_) => return Err(fail_at(lit, "capacity must not be zero")),
+                                    Err(err) => return Err(into_error(err)),
+                                };
+                            }
+                            nested => return Err(fail_at(nested, "expected `capacity = …`")),
+                        }
+                    }
+                    options.memoize = Some(capacity);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("kind_enum") => {
+                    if options.kind_enum.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    options.kind_enum = Some(KindEnum {
+                        path,
+                        rename_all: None,
                     });
-                    syn::Fields::Unnamed(syn::FieldsUnnamed {
-                        paren_token: syn::token::Paren(variant_span),
-                        unnamed: Punctuated::from_iter(fields),
-                    })
                 }
-                syn::Fields::Unit => syn::Fields::Unnamed(syn::FieldsUnnamed {
-                    paren_token: syn::token::Paren(variant_span),
-                    unnamed: Punctuated::from_iter([syn::Field {
-                        attrs: vec![],
-                        vis: syn::Visibility::Inherited,
-                        ident: None,
-                        colon_token: None,
-                        ty: phantom_type,
-                    }]),
-                }),
-            };
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("kind_enum") => {
+                    if options.kind_enum.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    let mut rename_all = None;
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                                path,
+                                lit: syn::Lit::Str(lit),
+                                ..
+                            })) if path.is_ident("rename_all") => {
+                                rename_all = Some(RenameRule::from_lit(lit).map_err(into_error)?);
+                            }
+                            nested => return Err(fail_at(nested, "expected `rename_all = \"…\"`")),
+                        }
+                    }
+                    options.kind_enum = Some(KindEnum {
+                        path: list.path,
+                        rename_all,
+                    });
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                    if pair.path.is_ident("newlines") =>
+                {
+                    if options.newlines.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    options.newlines = match pair.lit {
+                        syn::Lit::Str(style) => {
+                            Some(Newlines::from_style(&style).map_err(into_error)?)
+                        }
+                        lit => return Err(fail_at(lit, "expected a string literal")),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("newlines") => {
+                    if options.newlines.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    options.newlines = Some(Newlines::from_list(&list).map_err(into_error)?);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                    if pair.path.is_ident("check_unused_templates") =>
+                {
+                    if options.check_unused_templates.is_some() {
+                        return Err(fail_at(pair.path, "duplicated key"));
+                    }
+                    let dir = match pair.lit {
+                        syn::Lit::Str(dir) => dir,
+                        lit => return Err(fail_at(lit, "expected a string literal")),
+                    };
+                    options.check_unused_templates = Some(UnusedTemplates {
+                        dir,
+                        ignore: vec![],
+                    });
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list))
+                    if list.path.is_ident("check_unused_templates") =>
+                {
+                    if options.check_unused_templates.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    options.check_unused_templates =
+                        Some(UnusedTemplates::from_list(&list).map_err(into_error)?);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("examples") => {
+                    if options.examples.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    options.examples = Some(Examples { strict: false });
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("examples") => {
+                    if options.examples.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    let mut strict = false;
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::Path(flag))
+                                if flag.is_ident("strict") =>
+                            {
+                                strict = true;
+                            }
+                            nested => return Err(fail_at(nested, "expected `strict`")),
+                        }
+                    }
+                    options.examples = Some(Examples { strict });
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("icu") => {
+                    if icu.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "icu") {
+                        return Err(fail_at(
+                            path,
+                            "`icu` needs the feature `icu` of askama-enum",
+                        ));
+                    }
+                    icu = Some(path);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("unescaped") => {
+                    if options.unescaped {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    options.unescaped = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("conditional") => {
+                    if options.conditional {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "axum") && !cfg!(feature = "rouille") {
+                        return Err(fail_at(
+                            path,
+                            "`conditional` needs the feature `axum` or `rouille` of askama-enum",
+                        ));
+                    }
+                    options.conditional = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("rouille") => {
+                    if options.rouille {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "rouille") {
+                        return Err(fail_at(
+                            path,
+                            "`rouille` needs the feature `rouille` of askama-enum",
+                        ));
+                    }
+                    options.rouille = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("ws_axum") => {
+                    if options.ws_axum {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "ws-axum") {
+                        return Err(fail_at(
+                            path,
+                            "`ws_axum` needs the feature `ws-axum` of askama-enum",
+                        ));
+                    }
+                    options.ws_axum = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("ws_tungstenite") => {
+                    if options.ws_tungstenite {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "ws-tungstenite") {
+                        return Err(fail_at(
+                            path,
+                            "`ws_tungstenite` needs the feature `ws-tungstenite` of askama-enum",
+                        ));
+                    }
+                    options.ws_tungstenite = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("teloxide") => {
+                    if options.teloxide {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "teloxide") {
+                        return Err(fail_at(
+                            path,
+                            "`teloxide` needs the feature `teloxide` of askama-enum",
+                        ));
+                    }
+                    options.teloxide = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("wasm") => {
+                    if options.wasm {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "wasm") {
+                        return Err(fail_at(
+                            path,
+                            "`wasm` needs the feature `wasm` of askama-enum",
+                        ));
+                    }
+                    options.wasm = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("schema") => {
+                    if options.schema {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "schema") {
+                        return Err(fail_at(
+                            path,
+                            "`schema` needs the feature `schema` of askama-enum",
+                        ));
+                    }
+                    options.schema = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("write_to_file") => {
+                    if options.write_to_file {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "std") {
+                        return Err(fail_at(
+                            path,
+                            "`write_to_file` needs the feature `std` of askama-enum",
+                        ));
+                    }
+                    options.write_to_file = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("methods") => {
+                    if options.methods.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    options.methods = Some(methods::Methods::from_list(&list).map_err(into_error)?);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("router") => {
+                    if options.router.is_some() {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "axum") {
+                        return Err(fail_at(
+                            path,
+                            "`router` needs the feature `axum` of askama-enum",
+                        ));
+                    }
+                    options.router = Some(path);
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("coverage") => {
+                    if options.coverage {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    options.coverage = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("error_context") => {
+                    if options.error_context {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    options.error_context = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("validate_json") => {
+                    if options.validate_json {
+                        return Err(fail_at(path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "serde_json") {
+                        return Err(fail_at(
+                            path,
+                            "`validate_json` needs the feature `serde_json` of askama-enum",
+                        ));
+                    }
+                    options.validate_json = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("sanitize") => {
+                    if options.sanitize_config.is_some() {
+                        return Err(fail_at(list.path, "duplicated key"));
+                    }
+                    if !cfg!(feature = "ammonia") {
+                        return Err(fail_at(
+                            list.path,
+                            "`sanitize` needs the feature `ammonia` of askama-enum",
+                        ));
+                    }
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                                path,
+                                lit: syn::Lit::Str(lit),
+                                ..
+                            })) if path.is_ident("config") => {
+                                options.sanitize_config = Some(lit.parse().map_err(into_error)?);
+                            }
+                            nested => return Err(fail_at(nested, "expected `config = \"…\"`")),
+                        }
+                    }
+                }
+                nested => return Err(fail_at(nested, "unknown key")),
+            }
+        }
+    }
+    if let Some(path) = icu {
+        if options.memoize.is_some() {
+            return Err(fail_at(path, "cannot combine `icu` with `memoize`"));
+        }
+        let computed = icu::computed(&path);
+        if options.compute.iter().any(|c| c.name == computed.name) {
+            return Err(fail_at(
+                path,
+                "`icu` needs the name `fmt`, but it is computed, too",
+            ));
+        }
+        options.compute.push(computed);
+        options.icu = true;
+    }
+    if let Some(name) = variant_name {
+        let computed = variant_vars::variant_name(name, data);
+        variant_vars::push(&mut options.compute, computed, "variant_name")?;
+    }
+    if let Some(path) = expose_index {
+        for computed in variant_vars::index(&path, data) {
+            variant_vars::push(&mut options.compute, computed, "expose_index")?;
+        }
+    }
+    Ok(options)
+}
 
-            let mut generics = ast.generics.clone();
-            generics.params.push(parse_quote!(#variant_lifetime));
-            Ok(syn::DeriveInput {
-                attrs: vec![
-                    parse_quote!(#[::std::prelude::v1::derive(
-                        askama::Template,
-                        ::std::prelude::v1::Clone,
-                        ::std::prelude::v1::Copy,
-                        ::std::prelude::v1::Debug,
-                    )]),
-                    meta.clone(),
-                ],
-                vis: syn::Visibility::Inherited,
-                ident: variant_name,
-                generics,
-                data: syn::Data::Struct(syn::DataStruct {
-                    struct_token: Token![struct](variant_span),
-                    fields,
-                    semi_token: None,
-                }),
-            })
+fn parse_lit_ident(lit: &syn::Lit) -> Result<syn::Ident, TokenStream> {
+    match lit {
+        syn::Lit::Str(s) => s.parse().map_err(into_error),
+        lit => Err(fail_at(lit, "expected a string literal")),
+    }
+}
+
+/// Make a lifetime `'name` that does not collide with a lifetime in `generics`
+fn unique_lifetime(generics: &syn::Generics, name: &str, span: proc_macro2::Span) -> syn::Lifetime {
+    let is_taken = |name: &str| {
+        generics
+            .lifetimes()
+            .any(|param| param.lifetime.ident == name)
+    };
+    let mut candidate = name.to_owned();
+    let mut counter = 0;
+    while is_taken(&candidate) {
+        counter += 1;
+        candidate = format!("{}_{}", name, counter);
+    }
+    syn::Lifetime::new(&format!("'{}", candidate), span)
+}
+
+/// `#[enum_template_attr(…)]` can be used instead of `#[template(…)]`,
+/// if another derive macro on the same item uses `#[template]`, too
+fn is_template_attr(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident("template") || attr.path.is_ident("enum_template_attr")
+}
+
+/// The name of an identifier, without a leading `r#`
+fn unraw(ident: &syn::Ident) -> String {
+    let ident = ident.to_string();
+    match ident.strip_prefix("r#") {
+        Some(ident) => ident.to_owned(),
+        None => ident,
+    }
+}
+
+fn has_template_attr(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(is_template_attr)
+}
+
+/// Find the `#[template]` attribute of a type or variant, in either spelling
+/// The predicate of `cfg = r#"feature = "html""#` in the enum's `#[template]`
+fn parse_cfg_gate(value: &syn::Expr) -> syn::Result<syn::Meta> {
+    let predicate = args::lit_str(value)?;
+    predicate.parse().map_err(|err| {
+        let msg = format!(
+            "expected a cfg predicate, e.g. `cfg = r#\"feature = \"html\"\"#`: {}",
+            err,
+        );
+        syn::Error::new(predicate.span(), msg)
+    })
+}
+
+/// Replace `default_variant = "Fallback"` in the enum's `#[template]` with the `#[template]` of `Fallback`
+fn resolve_default_variant(
+    mut global_meta: TemplateArgs,
+    data: &syn::DataEnum,
+) -> Result<TemplateArgs, TokenStream> {
+    let name = match global_meta.take_key("default_variant") {
+        Some(name) => name,
+        None => return Ok(global_meta),
+    };
+    let fallback = like::find_variant(data, &name).map_err(into_error)?;
+    let fallback_meta = match find_template_attr(&fallback.attrs, "a variant")? {
+        Some(fallback_meta) if fallback_meta.has_key("like") => {
+            return Err(fail_at(
+                &name,
+                "the variant uses `like`, so it cannot be the `default_variant`",
+            ));
+        }
+        Some(fallback_meta) => fallback_meta,
+        None => {
+            return Err(fail_at(
+                &name,
+                "the variant has no #[template] of its own, so it cannot be the `default_variant`",
+            ));
+        }
+    };
+    // a template without fields fits every variant, otherwise the fields must match
+    if !fallback.fields.is_empty() {
+        for variant in &data.variants {
+            if !variant.attrs.iter().any(is_template_attr) {
+                like::check_fields(variant, fallback, &variant.ident).map_err(into_error)?;
+            }
+        }
+    }
+    Ok(like::default_variant(&global_meta, &fallback_meta))
+}
+
+/// `deny_default` or `strict` in the enum's `#[template]`: every variant needs its own `#[template]`
+///
+/// All variants without one are listed in a single error.
+fn check_deny_default(
+    global_meta: &mut TemplateArgs,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+) -> Result<(), TokenStream> {
+    let flag = global_meta
+        .args
+        .iter()
+        .map(args::TemplateArg::key)
+        .find(|key| *key == "deny_default" || *key == "strict")
+        .cloned();
+    let deny_default = global_meta.take_flag("deny_default").map_err(into_error)?;
+    let strict = global_meta.take_flag("strict").map_err(into_error)?;
+    let flag = match (flag, deny_default || strict) {
+        (Some(flag), true) => flag,
+        _ => return Ok(()),
+    };
+    let missing = data
+        .variants
+        .iter()
+        .filter(|variant| !has_template_attr(&variant.attrs))
+        .filter(|variant| options.untagged.is_none() || !is_untagged_variant(variant))
+        .map(|variant| format!("`{}`", unraw(&variant.ident)))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let msg = format!(
+        "`{}` needs a #[template] on every variant, but {} {} none",
+        flag,
+        missing.join(", "),
+        match missing.len() {
+            1 => "has",
+            _ => "have",
+        },
+    );
+    Err(fail_at(&flag, &msg))
+}
+
+fn find_template_attr(
+    attrs: &[syn::Attribute],
+    item: &str,
+) -> Result<Option<TemplateArgs>, TokenStream> {
+    let mut found: Option<&syn::Attribute> = None;
+    for attr in attrs.iter().filter(|attr| is_template_attr(attr)) {
+        let prev = match found {
+            Some(prev) => prev,
+            None => {
+                found = Some(attr);
+                continue;
+            }
+        };
+        if prev.path.is_ident("template") == attr.path.is_ident("template") {
+            let msg = format!(
+                "cannot have more than one #[template] attribute for {}",
+                item
+            );
+            return Err(fail_at(&attr.path, &msg));
+        }
+        let msg = "cannot use both #[template] and #[enum_template_attr] for the same item";
+        let mut err = syn::Error::new(prev.path.span(), msg);
+        err.combine(syn::Error::new(attr.path.span(), msg));
+        return Err(into_error(err));
+    }
+    match found {
+        Some(attr) => {
+            let mut args = TemplateArgs::from_attr(attr).map_err(into_error)?;
+            args.resolve_path_candidates().map_err(into_error)?;
+            Ok(Some(args))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Newtype variants without a specific `#[template]` share one struct in untagged mode
+fn is_untagged_variant(variant: &syn::Variant) -> bool {
+    matches!(&variant.fields, syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+        && !has_template_attr(&variant.attrs)
+}
+
+fn make_untagged_definition(
+    options: &EnumOptions,
+    global_meta: Option<&TemplateArgs>,
+    ast: &DeriveInput,
+    data: &syn::DataEnum,
+) -> Result<Option<syn::DeriveInput>, TokenStream> {
+    let untagged = match &options.untagged {
+        Some(untagged) => untagged,
+        None => return Ok(None),
+    };
+    let mut meta = match global_meta {
+        Some(meta) => meta.clone(),
+        None => {
+            return Err(fail_at(
+                &untagged.path,
+                "untagged mode needs a default #[template] attribute on the enum",
+            ));
+        }
+    };
+    expand::expand_source(&mut meta, &ast.ident.to_string()).map_err(into_error)?;
+    dir::apply(&mut meta).map_err(into_error)?;
+    let owner = format!("the untagged variants of {}", ast.ident);
+    enforce_escape::check(&mut meta, global_meta, &owner).map_err(into_error)?;
+
+    let mut common: Option<(&syn::Ident, &syn::Type)> = None;
+    for variant in &data.variants {
+        if !is_untagged_variant(variant) {
+            continue;
+        }
+        let ty = &variant.fields.iter().next().unwrap().ty;
+        match common {
+            None => common = Some((&variant.ident, ty)),
+            Some((first, common_ty))
+                if quote!(#ty).to_string() != quote!(#common_ty).to_string() =>
+            {
+                let msg = format!(
+                    "all newtype variants in untagged mode need to have the same field type, \
+                    but `{}` contains `{}` and `{}` contains `{}`; \
+                    give the variant its own #[template] attribute to opt out",
+                    first,
+                    quote!(#common_ty),
+                    variant.ident,
+                    quote!(#ty),
+                );
+                return Err(fail_at(ty, &msg));
+            }
+            Some(_) => {}
+        }
+    }
+    let ty = match common {
+        Some((_, ty)) => ty,
+        None => return Ok(None),
+    };
+
+    let name = format!("_{}_untagged", ast.ident);
+    let span = untagged.path.span();
+    let lifetime = unique_lifetime(&ast.generics, &name, span);
+    let name = syn::Ident::new(&name, span);
+    let value = &untagged.value;
+    let outer = match meta.take_key("expose_enum") {
+        Some(value) => parse_expose_enum(&value)?,
+        None => None,
+    };
+    // the untagged variants reject `expose_discriminant` themselves, and change their own output
+    meta.take_flag("expose_discriminant").map_err(into_error)?;
+    meta.take_flag("trim").map_err(into_error)?;
+    meta.take_flag("minify").map_err(into_error)?;
+    meta.take_key("postprocess");
+    let (prepend, append) = surround::take(&mut meta).map_err(into_error)?;
+    // the files are tracked by the definitions of the untagged variants, which read them, too
+    surround::apply(
+        &mut meta,
+        prepend.as_ref(),
+        append.as_ref(),
+        &ast.ident.to_string(),
+    )
+    .map_err(into_error)?;
+    apply_layout(&mut meta)?;
+    let attrs = template_meta::process(meta).map_err(into_error)?.attrs;
+    let variant_ident = syn::Ident::new("__variant", span);
+    helper::check_computed_collisions(&options.compute, [value, &variant_ident])
+        .map_err(into_error)?;
+    if let Some(outer) = &outer {
+        let computed = options.compute.iter().map(|computed| &computed.name);
+        helper::check_outer_collision(
+            outer,
+            [value, &variant_ident].iter().copied().chain(computed),
+        )
+        .map_err(into_error)?;
+    }
+    let computed = options.compute.iter().map(Computed::field);
+    let outer = outer
+        .iter()
+        .map(|outer| helper::outer_field(outer, &lifetime, ast));
+
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let enum_name = &ast.ident;
+    let mut generics = ast.generics.clone();
+    generics.params.push(parse_quote!(#lifetime));
+    let (_, _, where_clause) = generics.split_for_impl();
+    let item = parse_quote! {
+        #[::std::prelude::v1::derive(askama::Template)]
+        #(#attrs)*
+        struct #name #generics #where_clause {
+            #value: &#lifetime #ty,
+            __variant: &'static ::std::primitive::str,
+            #(#computed,)*
+            #(#outer,)*
+            #name: ::std::marker::PhantomData<&#lifetime #enum_name #ty_generics>,
+        }
+    };
+    Ok(Some(item))
+}
+
+/// The keys of the enum's `#[template]` that a variant's own `#[template]` inherits
+///
+/// Keys that only make sense for one variant, like `mime` or `route`, are not inherited,
+/// and neither are lists like `cfg(…)` or `alternate(…)`, which belong to the source they override.
+const INHERITED_KEYS: &[&str] = &[
+    "path",
+    "path_pattern",
+    "dir",
+    "case",
+    "source",
+    "source_file",
+    "source_env",
+    "doc",
+    "ext",
+    "escape",
+    "syntax",
+    "print",
+    "config",
+    "expose_enum",
+    "prepend_source",
+    "append_source",
+    "layout",
+    "slot",
+    "expose_discriminant",
+    "trim",
+    "minify",
+    "postprocess",
+];
+
+/// Take `key = "…"` out of the `#[template]` of a variant, for keys that describe the variant
+/// itself, so they cannot be set in the enum's `#[template]`
+fn take_variant_key(
+    meta: &mut TemplateArgs,
+    key: &str,
+    is_local: bool,
+    variant: &syn::Variant,
+) -> syn::Result<Option<syn::LitStr>> {
+    match meta.take_key(key) {
+        Some(_) if !is_local => {
+            let msg = format!("`{}` can only be used in the #[template] of a variant", key);
+            Err(syn::Error::new(variant.ident.span(), msg))
+        }
+        Some(value) => args::lit_str(&value).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// The enum, and what the definitions of its variants share
+struct EnumContext<'a> {
+    ast: &'a DeriveInput,
+    data: &'a syn::DataEnum,
+    options: &'a EnumOptions,
+    /// The enum's `#[template]`
+    global_meta: Option<&'a TemplateArgs>,
+    /// The helper struct of the untagged variants
+    untagged: Option<&'a syn::Ident>,
+    /// The discriminants of the variants, for `expose_discriminant`
+    discriminants: Vec<syn::Expr>,
+    /// The type of the field `discriminant`
+    discriminant_ty: syn::Type,
+}
+
+/// The other templates of a variant with `locale`, `format`, `if` or `key`
+struct Siblings {
+    /// The key that gives the variant more than one template, e.g. `"locale"`
+    key: &'static str,
+    /// The locale, format or condition of each other template, and the template
+    others: Vec<(syn::LitStr, TemplateArgs)>,
+    /// The names of all formats of `format`
+    format_names: Vec<String>,
+}
+
+fn make_variant_definitions(
+    global_meta: Option<&TemplateArgs>,
+    untagged: Option<&syn::Ident>,
+    options: &EnumOptions,
+    ast: &DeriveInput,
+    data: &syn::DataEnum,
+    default_variant_name: &mut Option<syn::Ident>,
+    constants_variant_name: &mut Option<(syn::Ident, syn::Ident)>,
+) -> Result<Vec<VariantDefinition>, TokenStream> {
+    for key in ["locale", "if", "key"] {
+        if let Some(value) = global_meta.and_then(|meta| meta.value(key)) {
+            let msg = format!("`{}` can only be used in the #[template] of a variant", key);
+            return Err(fail_at(value, &msg));
+        }
+    }
+    let cx = EnumContext {
+        ast,
+        data,
+        options,
+        global_meta,
+        untagged,
+        discriminants: discriminant::values(data),
+        discriminant_ty: discriminant::ty(ast),
+    };
+    data.variants
+        .iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            make_variant_definition(
+                &cx,
+                index,
+                variant,
+                default_variant_name,
+                constants_variant_name,
+            )
         })
         .collect()
 }
 
+/// The `#[template]` of a variant, and its other templates
+///
+/// `locale`, `key`, `if`, `feature` and `format` cannot be combined, and are tried in this order.
+fn split_templates(
+    variant: &syn::Variant,
+    options: &EnumOptions,
+) -> Result<(Option<TemplateArgs>, Option<Siblings>), TokenStream> {
+    let siblings = |key, default, others| {
+        let format_names = Vec::new();
+        (
+            Some(default),
+            Some(Siblings {
+                key,
+                others,
+                format_names,
+            }),
+        )
+    };
+    if let Some(localized) =
+        locale::split(variant, options.default_locale.as_ref()).map_err(into_error)?
+    {
+        return Ok(siblings("locale", localized.default, localized.others));
+    }
+    if let Some(keyed) = keyed::split(variant).map_err(into_error)? {
+        return Ok(siblings("key", keyed.default, keyed.others));
+    }
+    if let Some(guards) = guard::split(variant).map_err(into_error)? {
+        return Ok(siblings("if", guards.default, guards.others));
+    }
+    if let Some(featured) = features::merge(variant).map_err(into_error)? {
+        return Ok((Some(featured), None));
+    }
+    if let Some(formats) = format::split(variant).map_err(into_error)? {
+        let format_names = formats.names();
+        let siblings = Siblings {
+            key: "format",
+            others: formats.others,
+            format_names,
+        };
+        return Ok((Some(formats.default), Some(siblings)));
+    }
+    Ok((find_template_attr(&variant.attrs, "a variant")?, None))
+}
+
+/// The `#[template]` of a variant with the keys it inherits from the enum's `#[template]`, or the
+/// enum's `#[template]` if the variant has none
+fn inherit_meta(
+    local_meta: Option<&TemplateArgs>,
+    global_meta: Option<&TemplateArgs>,
+    variant: &syn::Variant,
+) -> Result<TemplateArgs, TokenStream> {
+    match (local_meta, global_meta) {
+        (Some(local), Some(global)) => Ok(TemplateArgs {
+            path: local.path.clone(),
+            args: args::merge(
+                &global
+                    .args
+                    .iter()
+                    .filter(|arg| INHERITED_KEYS.iter().any(|key| arg.key() == key))
+                    .cloned()
+                    .collect(),
+                local.args.clone(),
+            ),
+        }),
+        (Some(meta), None) | (None, Some(meta)) => Ok(meta.clone()),
+        (None, None) => Err(fail_at(&variant.ident, "need a #[template] attribute")),
+    }
+}
+
+fn make_variant_definition(
+    cx: &EnumContext<'_>,
+    index: usize,
+    variant: &syn::Variant,
+    default_variant_name: &mut Option<syn::Ident>,
+    constants_variant_name: &mut Option<(syn::Ident, syn::Ident)>,
+) -> Result<VariantDefinition, TokenStream> {
+    let EnumContext {
+        ast,
+        data,
+        options,
+        global_meta,
+        ..
+    } = *cx;
+    let computed = &options.compute;
+    let variant_name = &format!("_{}_{}_{}", &ast.ident, index, unraw(&variant.ident));
+    let variant_span = variant.ident.span();
+    let variant_lifetime = unique_lifetime(&ast.generics, variant_name, variant_span);
+    let variant_name = syn::Ident::new(variant_name, variant_span);
+    let owner = format!("{}::{}", ast.ident, variant.ident);
+
+    let (mut local_meta, siblings) = split_templates(variant, options)?;
+    if let Some(local_meta) = &mut local_meta {
+        surround::compose(local_meta, global_meta).map_err(into_error)?;
+    }
+    let template_owner = like::resolve(data, variant, &mut local_meta)?;
+    let untagged = match cx.untagged {
+        Some(untagged) if is_untagged_variant(variant) => Some(untagged),
+        _ => None,
+    };
+    if local_meta.is_none() && default_variant_name.is_none() {
+        *default_variant_name = Some(untagged.unwrap_or(&variant_name).clone());
+    }
+    let is_local = local_meta.is_some();
+    let mut meta = inherit_meta(local_meta.as_ref(), global_meta, variant)?;
+    let mut includes = expand::expand_source(&mut meta, &owner).map_err(into_error)?;
+    let dir = dir::apply(&mut meta).map_err(into_error)?;
+    enforce_escape::check(&mut meta, global_meta, &owner).map_err(into_error)?;
+    consts::take_meta_default(&mut meta, is_local, &variant_name, constants_variant_name)
+        .map_err(into_error)?;
+    let outer = match meta.take_key("expose_enum") {
+        Some(value) => parse_expose_enum(&value)?,
+        None => None,
+    };
+    let trim = meta.take_flag("trim").map_err(into_error)?;
+    let minify = minify::take(&mut meta, &owner).map_err(into_error)?;
+    let postprocess = postprocess::take(&mut meta).map_err(into_error)?;
+    let memoize = memoize::take(&mut meta, options).map_err(into_error)?;
+    let discriminant = match meta.take_flag("expose_discriminant").map_err(into_error)? {
+        true => Some(cx.discriminants[index].clone()),
+        false => None,
+    };
+    let (prepend, append) = surround::take(&mut meta).map_err(into_error)?;
+
+    // the keys that give the variant a template of its own, which most other kinds of variants
+    // cannot have
+    let own_meta = local_meta.as_ref().or(global_meta);
+    let own_source = ["source", "path", "doc", "path_pattern"]
+        .iter()
+        .copied()
+        .find(|&key| matches!(own_meta, Some(meta) if meta.has_key(key)));
+    let delegation = delegate::take(&mut meta, variant, own_source).map_err(into_error)?;
+    let delegated = delegation.is_some();
+    let (skip, redirect) = skip::take(&mut meta, own_source, delegated).map_err(into_error)?;
+    let (display, fmt) =
+        fmt::take(&mut meta, variant, own_source, delegated, skip.is_some()).map_err(into_error)?;
+    let adapter = delegate::take_adapter(&mut meta, own_source, delegated).map_err(into_error)?;
+    let with = with::With::take(
+        &mut meta,
+        own_source,
+        delegated,
+        adapter.is_some(),
+        skip.is_some(),
+    )
+    .map_err(into_error)?;
+    let has_template = skip.is_none() && adapter.is_none() && display.is_none() && fmt.is_none();
+
+    placeholder::take_path_pattern(
+        &mut meta,
+        local_meta.as_ref(),
+        dir.as_deref(),
+        variant,
+        &owner,
+    )
+    .map_err(into_error)?;
+    let block_sources = block::take_blocks(&mut meta, &owner, &mut includes).map_err(into_error)?;
+    block::take_block(&mut meta, &owner, &mut includes).map_err(into_error)?;
+    doc::take(&mut meta, template_owner, variant).map_err(into_error)?;
+    if has_template {
+        includes.extend(
+            surround::apply(&mut meta, prepend.as_ref(), append.as_ref(), &owner)
+                .map_err(into_error)?,
+        );
+        apply_layout(&mut meta)?;
+    } else {
+        meta.take_key("layout");
+        meta.take_key("slot");
+    }
+    if let Some(syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(source),
+        ..
+    })) = meta.value_mut("source")
+    {
+        *source = placeholder::substitute(source, &unraw(&variant.ident)).map_err(into_error)?;
+    }
+    if let Some(wrapper) = &delegation.wrapper {
+        delegate::wrap_source(&mut meta, wrapper, variant, !computed.is_empty())
+            .map_err(into_error)?;
+    }
+    let route = router::take_route(&mut meta, is_local, variant).map_err(into_error)?;
+    let output = output::take(&mut meta, is_local, variant).map_err(into_error)?;
+    let example = examples::take(&mut meta, is_local, variant).map_err(into_error)?;
+
+    let (multiple, others, format_names) = match siblings {
+        Some(siblings) => (Some(siblings.key), siblings.others, siblings.format_names),
+        None => (None, Vec::new(), Vec::new()),
+    };
+    if let Some(multiple) = multiple {
+        let other = match () {
+            _ if untagged.is_some() => Some("untagged variants"),
+            _ if delegated => Some("`delegate`"),
+            _ if adapter.is_some() => Some("`delegate_with`"),
+            _ if skip.is_some() => Some("`skip`"),
+            _ if display.is_some() => Some("`display`"),
+            _ if fmt.is_some() => Some("`fmt`"),
+            _ if with.is_some() => Some("`with`"),
+            _ => None,
+        };
+        if let Some(other) = other {
+            let msg = format!("cannot combine `{}` with {}", multiple, other);
+            return Err(fail_at(&variant.ident, &msg));
+        }
+    }
+    let mut locales = Vec::new();
+    let mut other_formats = Vec::new();
+    let mut guarded = Vec::new();
+    for (name, mut other_meta) in others {
+        includes.extend(expand::expand_source(&mut other_meta, &owner).map_err(into_error)?);
+        if let Some(dir) = &dir {
+            dir::prefix_path(&mut other_meta, dir);
+        }
+        let mut merged = TemplateArgs {
+            path: meta.path.clone(),
+            args: args::merge(&meta.args, other_meta.args),
+        };
+        enforce_escape::check(&mut merged, global_meta, &owner).map_err(into_error)?;
+        let attrs = template_meta::process(merged).map_err(into_error)?.attrs;
+        match multiple {
+            Some("locale") => locales.push((name, attrs)),
+            Some("if") | Some("key") => guarded.push((name, attrs)),
+            _ => other_formats.push((name, attrs)),
+        }
+    }
+    let mut blocks = Vec::new();
+    for (name, source, ext) in block_sources {
+        let mut block_meta = meta.clone();
+        block_meta.take_key("path");
+        block_meta.take_key("source");
+        let span = name.span();
+        block_meta.push_value(syn::Ident::new("source", span), parse_quote!(#source));
+        if let (false, Some(ext)) = (block_meta.has_key("ext"), ext) {
+            block_meta.push_value(syn::Ident::new("ext", span), parse_quote!(#ext));
+        }
+        let attrs = template_meta::process(block_meta)
+            .map_err(into_error)?
+            .attrs;
+        blocks.push((name.value(), attrs));
+    }
+    let str_value = |name| match meta.value(name) {
+        Some(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(value),
+            ..
+        })) => Some(value.clone()),
+        _ => None,
+    };
+    let template_path = str_value("path");
+    let escape = str_value("escape");
+    let TemplateMeta {
+        attrs: template_attrs,
+        deprecated,
+        mime,
+        status,
+        headers,
+        size_hint,
+        cfg_alternatives,
+        alternate,
+        metadata,
+    } = template_meta::process(meta).map_err(into_error)?;
+    if (display.is_some() || fmt.is_some()) && (!cfg_alternatives.is_empty() || alternate.is_some())
+    {
+        let msg = format!(
+            "cannot combine `{}` with `cfg(…)`, `debug(…)`, `release(…)` or `alternate(…)`",
+            if display.is_some() { "display" } else { "fmt" },
+        );
+        return Err(fail_at(&variant.ident, &msg));
+    }
+    let field_transforms = variant
+        .fields
+        .iter()
+        .map(FieldTransform::from_field)
+        .collect::<syn::Result<Vec<_>>>()
+        .map_err(into_error)?;
+
+    // the variants without a helper struct of their own
+    let definition = VariantDefinition {
+        name: variant_name.clone(),
+        helper: variant_name.clone(),
+        item: None,
+        variant_path: owner,
+        deprecated,
+        mime,
+        status,
+        headers,
+        template_path: None,
+        escape: None,
+        size_hint,
+        cfg_alternatives: vec![],
+        alternate: None,
+        delegate: false,
+        transparent: None,
+        adapter: None,
+        skip: false,
+        redirect: None,
+        display: None,
+        fmt: None,
+        outer: None,
+        discriminant: None,
+        trim,
+        minify,
+        postprocess,
+        memoize,
+        with: None,
+        route,
+        output,
+        example,
+        metadata,
+        field_transforms: vec![],
+        includes: vec![],
+        locales: vec![],
+        formats: vec![],
+        format_names: vec![],
+        blocks: vec![],
+        guards: vec![],
+    };
+    if let Some(untagged) = untagged {
+        if discriminant.is_some() {
+            return Err(fail_at(
+                &variant.ident,
+                "`expose_discriminant` cannot be used in untagged variants; \
+                use `expose_discriminant = false` in the variant's #[template] to opt out",
+            ));
+        }
+        fields::reject_field_attrs(variant, "untagged variants").map_err(into_error)?;
+        return Ok(VariantDefinition {
+            helper: untagged.clone(),
+            template_path,
+            escape,
+            outer,
+            includes,
+            ..definition
+        });
+    }
+    if let Some(with) = with {
+        with::With::check(variant, &cfg_alternatives, alternate.is_some()).map_err(into_error)?;
+        return Ok(VariantDefinition {
+            with: Some(with),
+            field_transforms,
+            ..definition
+        });
+    }
+    if let Some(transparent) = delegation.transparent {
+        let transparent = delegate::Transparent::new(
+            ast,
+            variant,
+            &variant_name,
+            &variant_lifetime,
+            transparent,
+            &cfg_alternatives,
+            alternate.is_some(),
+        )
+        .map_err(into_error)?;
+        return Ok(VariantDefinition {
+            transparent: Some(transparent),
+            field_transforms,
+            ..definition
+        });
+    }
+
+    let adapter = match adapter {
+        Some(adapter) => {
+            Some(delegate::Adapter::new(ast, variant, &variant_name, adapter).map_err(into_error)?)
+        }
+        None => None,
+    };
+    // the fields of the variant are only exposed to its own template
+    let (outer, discriminant) = match has_template && delegation.wrapper.is_none() {
+        true => (outer, discriminant),
+        false => (None, None),
+    };
+    let helper = helper::Helper {
+        ast,
+        variant,
+        name: &variant_name,
+        lifetime: &variant_lifetime,
+    };
+    let fields = match &delegation.wrapper {
+        _ if !has_template => helper.empty_fields(),
+        Some(wrapper) => helper
+            .delegate_fields(wrapper, &field_transforms)
+            .map_err(into_error)?,
+        None => {
+            let extra = helper::Extra {
+                computed,
+                outer: outer.as_ref(),
+                discriminant: discriminant.as_ref().map(|_| &cx.discriminant_ty),
+            };
+            helper
+                .fields(&extra, &field_transforms)
+                .map_err(into_error)?
+        }
+    };
+    let item = helper.item(fields, template_attrs, multiple == Some("key"));
+    let alternate = alternate.map(|attr| {
+        let ident = format_ident!("{}_alternate", variant_name);
+        helper::sibling(&item, ident, false, vec![attr])
+    });
+    let locales = locales
+        .into_iter()
+        .map(|(locale, attrs)| {
+            let ident = locale::struct_name(&variant_name, &locale.value());
+            (locale.value(), helper::sibling(&item, ident, false, attrs))
+        })
+        .collect();
+    let formats = other_formats
+        .into_iter()
+        .map(|(format, attrs)| {
+            let ident = format::struct_name(&variant_name, &format.value());
+            (format.value(), helper::sibling(&item, ident, false, attrs))
+        })
+        .collect();
+    // a block usually uses only some of the fields
+    let blocks = blocks
+        .into_iter()
+        .map(|(name, attrs)| {
+            let ident = block::struct_name(&variant_name, &name);
+            (name, helper::sibling(&item, ident, true, attrs))
+        })
+        .collect();
+    // e.g. the template of `if = "items.is_empty()"` has no use for `items`
+    let guards = guarded
+        .into_iter()
+        .enumerate()
+        .map(|(index, (condition, attrs))| {
+            let ident = guard::struct_name(&variant_name, index);
+            Ok((
+                guard::parse(&condition)?,
+                helper::sibling(&item, ident, true, attrs),
+            ))
+        })
+        .collect::<syn::Result<_>>()
+        .map_err(into_error)?;
+    Ok(VariantDefinition {
+        item: Some(item),
+        template_path,
+        escape,
+        cfg_alternatives,
+        alternate,
+        delegate: delegation.wrapper.is_some(),
+        adapter,
+        skip: skip.is_some(),
+        redirect,
+        display,
+        fmt,
+        outer,
+        discriminant,
+        field_transforms,
+        includes,
+        locales,
+        formats,
+        format_names,
+        blocks,
+        guards,
+        ..definition
+    })
+}
+
+fn into_error(err: syn::Error) -> TokenStream {
+    err.into_compile_error().into()
+}
+
 fn fail_at(spanned: impl Spanned, msg: &str) -> TokenStream {
     syn::Error::new(spanned.span(), msg)
         .into_compile_error()
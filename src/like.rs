@@ -0,0 +1,126 @@
+//! `#[template(like = "OtherVariant")]`: reuse the template of another variant
+
+use crate::args::{TemplateArg, TemplateArgs};
+
+/// Keys that describe the other variant itself, not its template, so they are not copied
+const OWN_KEYS: &[&str] = &["like", "route", "output", "example", "meta", "deprecated"];
+
+/// Apply `like = "…"` of the `#[template]` of `variant` to `local_meta`, and return the variant
+/// whose template is used: the other variant, or `variant` itself
+pub(crate) fn resolve<'a>(
+    data: &'a syn::DataEnum,
+    variant: &'a syn::Variant,
+    local_meta: &mut Option<TemplateArgs>,
+) -> Result<&'a syn::Variant, proc_macro::TokenStream> {
+    let meta = match local_meta {
+        Some(meta) => meta,
+        None => return Ok(variant),
+    };
+    let like = match meta.value("like") {
+        Some(like) => like,
+        None => return Ok(variant),
+    };
+    let other = find_variant(data, like).map_err(crate::into_error)?;
+    let other_meta = match crate::find_template_attr(&other.attrs, "a variant")? {
+        Some(other_meta) if other_meta.has_key("like") => {
+            return Err(crate::fail_at(
+                like,
+                "the variant uses `like`, too, but `like` cannot be chained",
+            ));
+        }
+        Some(other_meta) => other_meta,
+        None => {
+            return Err(crate::fail_at(
+                like,
+                "the variant has no #[template] of its own, \
+                so remove the attribute to use the default template",
+            ));
+        }
+    };
+    check_fields(variant, other, like).map_err(crate::into_error)?;
+    *meta = merge(meta, &other_meta);
+    Ok(other)
+}
+
+/// The variant named in `like = "…"`
+pub(crate) fn find_variant<'a>(
+    data: &'a syn::DataEnum,
+    like: &syn::Expr,
+) -> syn::Result<&'a syn::Variant> {
+    let name = crate::args::lit_str(like)?;
+    data.variants
+        .iter()
+        .find(|variant| crate::unraw(&variant.ident) == name.value())
+        .ok_or_else(|| {
+            let msg = format!("there is no variant `{}`", name.value());
+            syn::Error::new(name.span(), msg)
+        })
+}
+
+/// The template of `other`, overridden by the remaining keys of `meta`
+fn merge(meta: &TemplateArgs, other: &TemplateArgs) -> TemplateArgs {
+    let inherited = other
+        .args
+        .iter()
+        .filter(|arg| !OWN_KEYS.iter().any(|key| arg.key() == key))
+        .cloned()
+        .collect();
+    let overrides = meta
+        .args
+        .iter()
+        .filter(|arg| !matches!(arg, TemplateArg::NameValue { key, .. } if key == "like"))
+        .cloned();
+    TemplateArgs {
+        path: meta.path.clone(),
+        args: crate::args::merge(&inherited, overrides),
+    }
+}
+
+/// The default `#[template]` of the enum with `default_variant = "…"` replaced by the `#[template]`
+/// of that variant, whose keys override the enum's
+pub(crate) fn default_variant(meta: &TemplateArgs, other: &TemplateArgs) -> TemplateArgs {
+    let copied = other
+        .args
+        .iter()
+        .filter(|arg| !OWN_KEYS.iter().any(|key| arg.key() == key) && arg.key() != "meta_default")
+        .cloned();
+    TemplateArgs {
+        path: meta.path.clone(),
+        args: crate::args::merge(&meta.args, copied),
+    }
+}
+
+/// Both variants need the same kind of fields with the same names, so the template fits both
+pub(crate) fn check_fields(
+    variant: &syn::Variant,
+    other: &syn::Variant,
+    like: impl quote::ToTokens,
+) -> syn::Result<()> {
+    let names = |variant: &syn::Variant| {
+        let kind = match &variant.fields {
+            syn::Fields::Named(_) => "named",
+            syn::Fields::Unnamed(_) => "unnamed",
+            syn::Fields::Unit => "unit",
+        };
+        let names = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| match &field.ident {
+                Some(ident) => crate::unraw(ident),
+                None => index.to_string(),
+            })
+            .collect::<Vec<_>>();
+        (kind, names)
+    };
+    match names(variant) == names(other) {
+        true => Ok(()),
+        false => {
+            let msg = format!(
+                "`{}` and `{}` need the same fields to share a template",
+                variant.ident, other.ident,
+            );
+            Err(syn::Error::new_spanned(like, msg))
+        }
+    }
+}
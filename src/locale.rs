@@ -0,0 +1,110 @@
+//! `#[template(locale = "de", …)]`: one template per locale, and `render_locale()`
+
+use quote::format_ident;
+use syn::parse_quote;
+
+use crate::args::{self, TemplateArgs};
+use crate::{is_template_attr, unraw};
+
+/// The `#[template(locale = "…", …)]` attributes of a variant
+pub(crate) struct Localized {
+    /// The attribute of the default locale, without its `locale` key
+    pub(crate) default: TemplateArgs,
+    /// The attributes of the other locales, without their `locale` keys
+    pub(crate) others: Vec<(syn::LitStr, TemplateArgs)>,
+}
+
+/// Split the localized `#[template]` attributes of a variant from its other attributes
+///
+/// Returns `None` if the variant has no localized attributes.
+pub(crate) fn split(
+    variant: &syn::Variant,
+    default_locale: Option<&syn::LitStr>,
+) -> syn::Result<Option<Localized>> {
+    let mut localized = Vec::new();
+    for attr in variant.attrs.iter().filter(|attr| is_template_attr(attr)) {
+        let mut meta = TemplateArgs::from_attr(attr)?;
+        if let Some(locale) = meta.take_key("locale") {
+            localized.push((args::lit_str(&locale)?, meta));
+        } else if !localized.is_empty() || variant.attrs.iter().any(is_localized) {
+            return Err(syn::Error::new_spanned(
+                &attr.path,
+                "a variant with localized templates needs a `locale` in every #[template]",
+            ));
+        }
+    }
+    if localized.is_empty() {
+        return Ok(None);
+    }
+    let default_locale = match default_locale {
+        Some(default_locale) => default_locale,
+        None => {
+            return Err(syn::Error::new(
+                localized[0].0.span(),
+                "`locale` needs #[enum_template(default_locale = \"…\")]",
+            ));
+        }
+    };
+    for (index, (locale, _)) in localized.iter().enumerate() {
+        if localized[..index]
+            .iter()
+            .any(|(other, _)| other.value() == locale.value())
+        {
+            let msg = format!("the locale {:?} is used twice", locale.value());
+            return Err(syn::Error::new(locale.span(), msg));
+        }
+    }
+    let position = localized
+        .iter()
+        .position(|(locale, _)| locale.value() == default_locale.value());
+    let default = match position {
+        Some(position) => localized.remove(position).1,
+        None => {
+            let msg = format!(
+                "`{}` needs a #[template] for the default locale {:?}",
+                unraw(&variant.ident),
+                default_locale.value(),
+            );
+            return Err(syn::Error::new(variant.ident.span(), msg));
+        }
+    };
+    Ok(Some(Localized {
+        default,
+        others: localized,
+    }))
+}
+
+fn is_localized(attr: &syn::Attribute) -> bool {
+    is_template_attr(attr)
+        && matches!(TemplateArgs::from_attr(attr), Ok(meta) if meta.has_key("locale"))
+}
+
+/// The struct that renders `locale` for the variant `helper`
+pub(crate) fn struct_name(helper: &syn::Ident, locale: &str) -> syn::Ident {
+    let locale = locale
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    format_ident!("{}_locale_{}", helper, locale)
+}
+
+/// `fn render_locale(&self, locale: &str) -> askama::Result<String>`
+pub(crate) fn make_method(
+    vis: &syn::Visibility,
+    render_impls: Vec<(String, syn::ExprMatch)>,
+) -> syn::ImplItem {
+    let (locales, render_impls): (Vec<_>, Vec<_>) = render_impls.into_iter().unzip();
+    parse_quote! {
+        /// Render the template in `locale`, or in the default locale if the variant has no
+        /// template for `locale`
+        #vis fn render_locale(
+            &self,
+            locale: &::std::primitive::str,
+        ) -> askama::Result<::std::string::String> {
+            match locale {
+                #(#locales => #render_impls,)*
+                _ => askama::Template::render(self),
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! `#[enum_template(memoize)]` and `#[template(memoize = "…")]`: cache the rendered output
+//!
+//! The caches are `static`s, keyed by a `MemoizeKey`, which holds clones of the fields or the key.
+
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::args::{self, TemplateArgs};
+use crate::{guard, EnumOptions, VariantDefinition};
+
+/// Take `memoize = "…"` out of the `#[template]` of a variant
+pub(crate) fn take(
+    meta: &mut TemplateArgs,
+    options: &EnumOptions,
+) -> syn::Result<Option<syn::Expr>> {
+    let value = match meta.take_key("memoize") {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    if options.memoize.is_some() {
+        return Err(syn::Error::new(
+            value.span(),
+            "the enum's #[enum_template(memoize)] already caches the output",
+        ));
+    }
+    if !cfg!(feature = "memoize") {
+        return Err(syn::Error::new(
+            value.span(),
+            "`memoize` needs the feature `memoize` of askama-enum",
+        ));
+    }
+    parse(&value).map(Some)
+}
+
+/// The key of `memoize = "…"`
+fn parse(value: &syn::Expr) -> syn::Result<syn::Expr> {
+    let lit = args::lit_str(value)?;
+    lit.parse().map_err(|err| {
+        let msg = format!("the key of `memoize` is not a valid expression: {}", err);
+        syn::Error::new(lit.span(), msg)
+    })
+}
+
+/// Look up the rendered template in a cache, keyed by the variant and clones of its fields
+pub(crate) fn make_render(
+    data: &syn::DataEnum,
+    render_impl: proc_macro2::TokenStream,
+    capacity: usize,
+) -> proc_macro2::TokenStream {
+    let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+        let self_variant_name = &variant.ident;
+        let (members, names): (Vec<_>, Vec<_>) = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let member = match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(index.into()),
+                };
+                (
+                    member,
+                    syn::Ident::new(&format!("_{}", index), field.span()),
+                )
+            })
+            .unzip();
+        // A borrowed field is keyed by an owned copy of the borrowed value, e.g. a `String` for a
+        // `&str`, because the cache outlives the borrow.
+        let keys = variant
+            .fields
+            .iter()
+            .zip(&names)
+            .map(|(field, name)| match &field.ty {
+                syn::Type::Reference(syn::TypeReference {
+                    mutability: None, ..
+                }) => quote_spanned!(field.ty.span()=> memoize_borrowed_field(#name)),
+                ty => quote_spanned!(ty.span()=> memoize_field(#name)),
+            });
+        quote! {
+            Self::#self_variant_name { #(#members: #names),* } => {
+                (#index, ::std::vec![#(#keys),*])
+            }
+        }
+    });
+    quote! {
+        type Cache = ::std::option::Option<(
+            ::std::collections::HashMap<MemoizeKey, ::std::string::String>,
+            ::std::collections::VecDeque<MemoizeKey>,
+        )>;
+        static CACHE: ::std::sync::Mutex<Cache> =
+            ::std::sync::Mutex::new(::std::option::Option::None);
+
+        fn memoize_field<T>(value: &T) -> MemoizeKey
+        where
+            T: ::std::clone::Clone
+                + ::std::cmp::Eq
+                + ::std::hash::Hash
+                + ::std::marker::Send
+                + ::std::marker::Sync
+                + 'static,
+        {
+            MemoizeKey::new(::std::clone::Clone::clone(value))
+        }
+
+        fn memoize_borrowed_field<T>(value: &&T) -> MemoizeKey
+        where
+            T: ::std::borrow::ToOwned + ?::std::marker::Sized,
+            T::Owned: ::std::cmp::Eq
+                + ::std::hash::Hash
+                + ::std::marker::Send
+                + ::std::marker::Sync
+                + 'static,
+        {
+            MemoizeKey::new(::std::borrow::ToOwned::to_owned(*value))
+        }
+
+        let (index, fields): (::std::primitive::usize, ::std::vec::Vec<MemoizeKey>) = match self {
+            #(#arms)*
+        };
+        let key = MemoizeKey::new((::std::any::type_name::<Self>(), index, fields));
+
+        let cached = CACHE
+            .lock()
+            .unwrap_or_else(::std::sync::PoisonError::into_inner)
+            .as_ref()
+            .and_then(|(map, _)| map.get(&key).cloned());
+        if let ::std::option::Option::Some(rendered) = cached {
+            return ::std::result::Result::Ok(rendered);
+        }
+
+        let rendered = (|| -> askama::Result<::std::string::String> { #render_impl })()?;
+        let mut cache = CACHE.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+        let (map, keys) = cache.get_or_insert_with(::std::default::Default::default);
+        if !map.contains_key(&key) {
+            map.insert(::std::clone::Clone::clone(&key), ::std::clone::Clone::clone(&rendered));
+            keys.push_back(key);
+            if keys.len() > #capacity {
+                if let ::std::option::Option::Some(oldest) = keys.pop_front() {
+                    map.remove(&oldest);
+                }
+            }
+        }
+        ::std::result::Result::Ok(rendered)
+    }
+}
+
+/// The key of a memoized render, needs to be put into the `const _` block once
+///
+/// It holds a cloned value, and compares it with `Eq`, so a cached output is only reused for an
+/// equal value, even if `Hash` only covers a part of it. The type of the value is erased, because
+/// a `static` cannot name the generic parameters of the enum.
+pub(crate) fn make_key() -> proc_macro2::TokenStream {
+    quote! {
+        trait MemoizeKeyValue:
+            ::std::any::Any + ::std::marker::Send + ::std::marker::Sync
+        {
+            fn as_any(&self) -> &dyn ::std::any::Any;
+
+            fn eq_key(&self, other: &dyn MemoizeKeyValue) -> ::std::primitive::bool;
+
+            fn hash_key(&self, state: &mut dyn ::std::hash::Hasher);
+        }
+
+        impl<T> MemoizeKeyValue for T
+        where
+            T: ::std::cmp::Eq
+                + ::std::hash::Hash
+                + ::std::marker::Send
+                + ::std::marker::Sync
+                + 'static,
+        {
+            fn as_any(&self) -> &dyn ::std::any::Any {
+                self
+            }
+
+            fn eq_key(&self, other: &dyn MemoizeKeyValue) -> ::std::primitive::bool {
+                matches!(
+                    other.as_any().downcast_ref::<T>(),
+                    ::std::option::Option::Some(other) if self == other,
+                )
+            }
+
+            fn hash_key(&self, mut state: &mut dyn ::std::hash::Hasher) {
+                ::std::hash::Hash::hash(self, &mut state);
+            }
+        }
+
+        #[derive(::std::clone::Clone)]
+        struct MemoizeKey(::std::sync::Arc<dyn MemoizeKeyValue>);
+
+        impl MemoizeKey {
+            fn new<T>(value: T) -> Self
+            where
+                T: ::std::cmp::Eq
+                    + ::std::hash::Hash
+                    + ::std::marker::Send
+                    + ::std::marker::Sync
+                    + 'static,
+            {
+                Self(::std::sync::Arc::new(value))
+            }
+        }
+
+        impl ::std::cmp::PartialEq for MemoizeKey {
+            fn eq(&self, other: &Self) -> ::std::primitive::bool {
+                self.0.eq_key(&*other.0)
+            }
+        }
+
+        impl ::std::cmp::Eq for MemoizeKey {}
+
+        impl ::std::hash::Hash for MemoizeKey {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash_key(state);
+            }
+        }
+    }
+}
+
+/// The cache of the output of a variant with `memoize = "…"`, put next to the variant's struct
+///
+/// `render()`, `render_into()` and `Display` share the cache.
+pub(crate) fn make_cache(helper: &syn::Ident) -> proc_macro2::TokenStream {
+    let cache = cache_name(helper);
+    quote! {
+        #[allow(non_upper_case_globals)]
+        static #cache: ::std::sync::Mutex<
+            ::std::option::Option<::std::collections::HashMap<MemoizeKey, ::std::string::String>>,
+        > = ::std::sync::Mutex::new(::std::option::Option::None);
+    }
+}
+
+fn cache_name(helper: &syn::Ident) -> syn::Ident {
+    format_ident!("{}_cache", helper)
+}
+
+/// `fn clear_memoized()`, if a variant has `memoize = "…"`
+pub(crate) fn make_clear(
+    vis: &syn::Visibility,
+    variant_definitions: &[VariantDefinition],
+) -> Option<syn::ImplItem> {
+    let caches = variant_definitions
+        .iter()
+        .filter(|definition| definition.memoize.is_some())
+        .map(|definition| cache_name(&definition.helper))
+        .collect::<Vec<_>>();
+    if caches.is_empty() {
+        return None;
+    }
+    Some(parse_quote! {
+        /// Remove all outputs that were cached because of `#[template(memoize = "…")]`
+        ///
+        /// The caches are never cleared otherwise, so call it if the keys can have many values.
+        #vis fn clear_memoized() {
+            #(
+                *#caches.lock().unwrap_or_else(::std::sync::PoisonError::into_inner) =
+                    ::std::option::Option::None;
+            )*
+        }
+    })
+}
+
+/// Look up the output of `call` in the cache of the variant, keyed by a clone of the value of `key`
+///
+/// The cache is only cleared by `clear_memoized()`, so `key` should only have a few distinct values.
+pub(crate) fn wrap(
+    call: syn::Expr,
+    definition: &VariantDefinition,
+    variant: &syn::Variant,
+    key: &syn::Expr,
+) -> syn::Expr {
+    let cache = cache_name(&definition.helper);
+    let bindings = guard::bind_fields(variant);
+    let key = quote_spanned!(key.span()=> {
+        #bindings
+        (#key).clone()
+    });
+    parse_quote!({
+        #[allow(clippy::clone_on_copy)]
+        let memoize_key = MemoizeKey::new((::std::any::type_name::<Self>(), #key));
+
+        let cached = #cache
+            .lock()
+            .unwrap_or_else(::std::sync::PoisonError::into_inner)
+            .as_ref()
+            .and_then(|map| map.get(&memoize_key).cloned());
+        match cached {
+            ::std::option::Option::Some(rendered) => ::std::result::Result::Ok(rendered),
+            ::std::option::Option::None => #call.map(|rendered| {
+                #cache
+                    .lock()
+                    .unwrap_or_else(::std::sync::PoisonError::into_inner)
+                    .get_or_insert_with(::std::default::Default::default)
+                    .insert(memoize_key, ::std::clone::Clone::clone(&rendered));
+                rendered
+            }),
+        }
+    })
+}
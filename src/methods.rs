@@ -0,0 +1,300 @@
+//! The accessor methods of the enum, and `#[enum_template(methods(…))]`
+
+use quote::{quote, ToTokens};
+use syn::parse_quote;
+
+use crate::{hot_reload, make_static_ty_generics, match_variants, unraw};
+use crate::{EnumOptions, VariantDefinition};
+
+/// An accessor that is generated if a variant uses its key, or if it is listed in `methods(…)`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Method {
+    /// `fn byte_len(&self) -> askama::Result<usize>`, has no key
+    ByteLen,
+    /// `fn render_cow(&self) -> askama::Result<Cow<'static, str>>`, has no key
+    RenderCow,
+    /// `fn mime_type(&self) -> &'static str`, for `#[template(mime = "…")]`
+    MimeType,
+    /// `fn content_type(&self) -> &'static str`, for `#[template(mime = "…")]`
+    ContentType,
+    /// `fn status(&self) -> u16`, for `#[template(status = …)]`
+    Status,
+    /// `fn headers(&self) -> &'static [(&'static str, &'static str)]`, for `#[template(header(…))]`
+    Headers,
+    /// `fn redirect_target(&self) -> Option<&'static str>`, for `#[template(redirect = "…")]`
+    RedirectTarget,
+    /// `fn metadata(&self) -> &'static [(&'static str, &'static str)]`, for `#[template(meta(…))]`
+    Metadata,
+    /// `fn size_hint(&self) -> usize`, for `#[template(size_hint = …)]`
+    SizeHint,
+}
+
+impl Method {
+    const ALL: &'static [(&'static str, Method)] = &[
+        ("byte_len", Method::ByteLen),
+        ("render_cow", Method::RenderCow),
+        ("mime_type", Method::MimeType),
+        ("content_type", Method::ContentType),
+        ("status", Method::Status),
+        ("headers", Method::Headers),
+        ("redirect_target", Method::RedirectTarget),
+        ("metadata", Method::Metadata),
+        ("size_hint", Method::SizeHint),
+    ];
+
+    /// Whether `definition` uses the key of the method
+    fn is_used_by(self, definition: &VariantDefinition) -> bool {
+        match self {
+            Method::ByteLen | Method::RenderCow => false,
+            Method::MimeType | Method::ContentType => definition.mime.is_some(),
+            Method::Status => definition.status.is_some(),
+            Method::Headers => !definition.headers.is_empty(),
+            Method::RedirectTarget => definition.redirect.is_some(),
+            Method::Metadata => !definition.metadata.is_empty(),
+            Method::SizeHint => definition.size_hint.is_some(),
+        }
+    }
+}
+
+/// `#[enum_template(methods(byte_len, render_cow, …))]`, the accessors to generate in any case
+pub(crate) struct Methods(Vec<Method>);
+
+impl Methods {
+    pub(crate) fn from_list(list: &syn::MetaList) -> syn::Result<Self> {
+        let mut methods = Vec::new();
+        for nested in &list.nested {
+            let path = match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => path,
+                nested => return Err(syn::Error::new_spanned(nested, "expected a method name")),
+            };
+            let method = Method::ALL
+                .iter()
+                .find(|(name, _)| path.is_ident(name))
+                .map(|&(_, method)| method);
+            let method = match method {
+                Some(method) => method,
+                None => {
+                    let names = Method::ALL
+                        .iter()
+                        .map(|(name, _)| format!("`{}`", name))
+                        .collect::<Vec<_>>();
+                    let msg = format!("unknown method, expected one of {}", names.join(", "));
+                    return Err(syn::Error::new_spanned(path, msg));
+                }
+            };
+            if methods.contains(&method) {
+                return Err(syn::Error::new_spanned(path, "duplicated method"));
+            }
+            methods.push(method);
+        }
+        Ok(Self(methods))
+    }
+}
+
+/// Whether to generate `method`: it is listed in `methods(…)`, or a variant uses its key
+fn wants(
+    methods: Option<&Methods>,
+    method: Method,
+    variant_definitions: &[VariantDefinition],
+) -> bool {
+    matches!(methods, Some(methods) if methods.0.contains(&method))
+        || variant_definitions.iter().any(|d| method.is_used_by(d))
+}
+
+/// The accessors that the enum asked for
+pub(crate) fn make_accessors(
+    ast: &syn::DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> Vec<syn::ImplItem> {
+    let vis = &ast.vis;
+    let wants = |method| wants(options.methods.as_ref(), method, variant_definitions);
+    let static_ty_generics = make_static_ty_generics(ast);
+    let mut methods = Vec::new();
+    if wants(Method::ByteLen) {
+        methods.push(parse_quote! {
+            /// Calculate the length of the rendered template in bytes, without allocating
+            #vis fn byte_len(&self) -> askama::Result<::std::primitive::usize> {
+                struct Counter(::std::primitive::usize);
+
+                impl ::std::fmt::Write for Counter {
+                    #[inline]
+                    fn write_str(&mut self, s: &::std::primitive::str) -> ::std::fmt::Result {
+                        self.0 += s.len();
+                        ::std::result::Result::Ok(())
+                    }
+                }
+
+                let mut counter = Counter(0);
+                askama::Template::render_into(self, &mut counter)?;
+                ::std::result::Result::Ok(counter.0)
+            }
+        });
+    }
+    let mime_type = match_variants(data, variant_definitions, |definition| {
+        match &definition.mime {
+            Some(mime) => mime.to_token_stream(),
+            None => {
+                let ty = definition.template_type(&static_ty_generics);
+                quote!(<#ty as askama::Template>::MIME_TYPE)
+            }
+        }
+    });
+    if wants(Method::MimeType) {
+        methods.push(parse_quote! {
+            /// The MIME type of the variant's template
+            #vis fn mime_type(&self) -> &'static ::std::primitive::str {
+                #mime_type
+            }
+        });
+    }
+    if wants(Method::ContentType) {
+        methods.push(parse_quote! {
+            /// The content type of the variant's template, the same as its MIME type
+            #vis fn content_type(&self) -> &'static ::std::primitive::str {
+                #mime_type
+            }
+        });
+    }
+    if wants(Method::Status) {
+        let status = match_variants(data, variant_definitions, |definition| {
+            match &definition.status {
+                Some(status) => status.to_token_stream(),
+                None => quote!(200),
+            }
+        });
+        methods.push(parse_quote! {
+            /// The HTTP status code of the variant's `#[template(status = …)]`, or 200
+            #vis fn status(&self) -> ::std::primitive::u16 {
+                #status
+            }
+        });
+    }
+    if wants(Method::Headers) {
+        let headers = match_variants(data, variant_definitions, |definition| {
+            let headers = definition
+                .headers
+                .iter()
+                .map(|(name, value)| quote!((#name, #value)));
+            quote!(&[#(#headers),*])
+        });
+        methods.push(parse_quote! {
+            /// The extra HTTP headers of the variant's `#[template(header("Name" = "value"), …)]`
+            #vis fn headers(
+                &self,
+            ) -> &'static [(&'static ::std::primitive::str, &'static ::std::primitive::str)] {
+                #headers
+            }
+        });
+    }
+    if wants(Method::RedirectTarget) {
+        let redirect_target = match_variants(data, variant_definitions, |definition| {
+            match &definition.redirect {
+                Some(target) => quote!(::std::option::Option::Some(#target)),
+                None => quote!(::std::option::Option::None),
+            }
+        });
+        methods.push(parse_quote! {
+            /// The target of the variant's `#[template(redirect = "…")]`
+            #vis fn redirect_target(
+                &self,
+            ) -> ::std::option::Option<&'static ::std::primitive::str> {
+                #redirect_target
+            }
+        });
+    }
+    if wants(Method::Metadata) {
+        let metadata = match_variants(data, variant_definitions, |definition| {
+            let pairs = definition.metadata.iter().map(|(key, value)| {
+                let key = unraw(key);
+                quote!((#key, #value))
+            });
+            quote!(&[#(#pairs),*])
+        });
+        methods.push(parse_quote! {
+            /// The pairs of the variant's `#[template(meta(key = "value", …))]`
+            #vis fn metadata(
+                &self,
+            ) -> &'static [(&'static ::std::primitive::str, &'static ::std::primitive::str)] {
+                #metadata
+            }
+        });
+    }
+    if wants(Method::SizeHint) {
+        let size_hint = match_variants(data, variant_definitions, |definition| {
+            definition.size_hint(&static_ty_generics)
+        });
+        methods.push(parse_quote! {
+            /// The approximate length of the variant's rendered template
+            #vis fn size_hint(&self) -> ::std::primitive::usize {
+                #size_hint
+            }
+        });
+    }
+    if wants(Method::RenderCow) {
+        methods.push(make_render_cow(ast, options, data, variant_definitions));
+    }
+    methods
+}
+
+/// `fn render_cow()`, which renders unit variants only once
+fn make_render_cow(
+    ast: &syn::DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> syn::ImplItem {
+    let vis = &ast.vis;
+    // A static in a method of a generic enum is shared by all its instantiations, and metrics and
+    // coverage need to see every render.
+    let is_generic = ast
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, syn::GenericParam::Lifetime(_)));
+    let caches_cow = !is_generic
+        && options.compute.is_empty()
+        && options.metrics.is_none()
+        && !options.coverage();
+    let cow_arms = data
+        .variants
+        .iter()
+        .zip(variant_definitions)
+        .filter(|(variant, definition)| {
+            caches_cow
+                && matches!(variant.fields, syn::Fields::Unit)
+                && definition.guards.is_empty()
+                && !hot_reload::reloads(options, definition)
+        })
+        .map(|(variant, _)| {
+            let self_variant_name = &variant.ident;
+            quote! {
+                Self::#self_variant_name => {
+                    static RENDERED: ::std::sync::OnceLock<::std::string::String> =
+                        ::std::sync::OnceLock::new();
+                    if let ::std::option::Option::Some(rendered) = RENDERED.get() {
+                        return ::std::result::Result::Ok(::std::borrow::Cow::Borrowed(rendered));
+                    }
+                    let rendered = askama::Template::render(self)?;
+                    ::std::result::Result::Ok(::std::borrow::Cow::Borrowed(
+                        RENDERED.get_or_init(|| rendered),
+                    ))
+                }
+            }
+        });
+    parse_quote! {
+        /// Render the template, borrowing the cached output of unit variants
+        ///
+        /// The output of unit variants does not change, so it is only rendered once.
+        #vis fn render_cow(
+            &self,
+        ) -> askama::Result<::std::borrow::Cow<'static, ::std::primitive::str>> {
+            #[allow(unreachable_patterns)]
+            match self {
+                #(#cow_arms)*
+                _ => askama::Template::render(self).map(::std::borrow::Cow::Owned),
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! `#[template(minify)]`: remove the indentation and collapse the whitespace of rendered HTML
+
+use quote::quote;
+
+use crate::args::{self, TemplateArgs};
+
+/// The extensions of the templates that can be minified
+const EXTENSIONS: &[&str] = &["html", "htm"];
+
+/// Remove `minify` from `meta`, and check that the variant `owner` can be minified
+pub(crate) fn take(meta: &mut TemplateArgs, owner: &str) -> syn::Result<bool> {
+    let key = match meta.args.iter().find(|arg| arg.key() == "minify") {
+        Some(arg) => arg.key().clone(),
+        None => return Ok(false),
+    };
+    if !meta.take_flag("minify")? {
+        return Ok(false);
+    }
+    if !cfg!(feature = "minify") {
+        return Err(syn::Error::new(
+            key.span(),
+            "`minify` needs the feature `minify` of askama-enum",
+        ));
+    }
+    let ext = match meta.value("ext") {
+        Some(ext) => Some(args::lit_str(ext)?.value()),
+        None => match meta.value("path") {
+            Some(path) => std::path::Path::new(&args::lit_str(path)?.value())
+                .extension()
+                .map(|ext| ext.to_string_lossy().into_owned()),
+            None => None,
+        },
+    };
+    match ext {
+        Some(ext) if EXTENSIONS.iter().any(|html| ext.eq_ignore_ascii_case(html)) => Ok(true),
+        ext => {
+            let msg = format!(
+                "{} is not an HTML template (its `ext` is {:?}), so it cannot be minified; \
+                use `minify = false` to opt out of an inherited `minify`",
+                owner,
+                ext.unwrap_or_default(),
+            );
+            Err(syn::Error::new(key.span(), msg))
+        }
+    }
+}
+
+/// The function that minifies the output, needs to be put into the `const _` block once
+///
+/// Whitespace that contains a line break, i.e. indentation, is removed next to the tags of block
+/// elements like `<p>` or `<li>`, and at the start and end of the output. Other runs of whitespace
+/// are collapsed into a single space, so text and inline elements like `<b>` stay apart. Comments
+/// are removed. Attribute values in quotes, and the content of `<pre>`, `<textarea>`, `<script>`
+/// and `<style>` elements are kept as they are.
+pub(crate) fn make_runtime() -> proc_macro2::TokenStream {
+    quote! {
+        fn minify_html(html: ::std::string::String) -> ::std::string::String {
+            const RAW: &[&::std::primitive::str] = &["pre", "textarea", "script", "style"];
+            // the elements whose tags separate blocks, so the whitespace next to them is not rendered
+            const BLOCKS: &[&::std::primitive::str] = &[
+                "!doctype", "address", "article", "aside", "blockquote", "body", "br", "dd",
+                "details", "div", "dl", "dt", "fieldset", "figcaption", "figure", "footer", "form",
+                "h1", "h2", "h3", "h4", "h5", "h6", "head", "header", "hr", "html", "li", "link",
+                "main", "meta", "nav", "ol", "option", "p", "pre", "script", "section", "style",
+                "summary", "table", "tbody", "td", "tfoot", "th", "thead", "title", "tr", "ul",
+            ];
+
+            /// The name of the element of a tag, e.g. `p` for `<p class="a">` or `</p>`
+            fn tag_name(tag: &::std::primitive::str) -> &::std::primitive::str {
+                tag.trim_start_matches('<')
+                    .trim_start_matches('/')
+                    .split(|c: ::std::primitive::char| {
+                        c.is_ascii_whitespace() || c == '>' || c == '/'
+                    })
+                    .next()
+                    .unwrap_or_default()
+            }
+
+            fn is_block(tag: &::std::primitive::str) -> ::std::primitive::bool {
+                let name = tag_name(tag);
+                BLOCKS.iter().any(|block| name.eq_ignore_ascii_case(block))
+            }
+
+            /// Push `text`, which is between two tags, or the start or end of the output
+            fn push_text(
+                out: &mut ::std::string::String,
+                mut text: &::std::primitive::str,
+                after_block: ::std::primitive::bool,
+                before_block: ::std::primitive::bool,
+                at_end: ::std::primitive::bool,
+            ) {
+                let is_space = |c: ::std::primitive::char| c.is_ascii_whitespace();
+                let at_start = out.is_empty();
+                let mut leading = true;
+                while !text.is_empty() {
+                    let rest = text.trim_start_matches(is_space);
+                    let run = &text[..text.len() - rest.len()];
+                    text = rest;
+                    if !run.is_empty() {
+                        let trailing = text.is_empty();
+                        let is_indentation = run.contains('\n');
+                        let dropped = (leading && (at_start || (after_block && is_indentation)))
+                            || (trailing && (at_end || (before_block && is_indentation)));
+                        if !dropped {
+                            out.push(' ');
+                        }
+                    }
+                    let word = text.find(is_space).unwrap_or(text.len());
+                    out.push_str(&text[..word]);
+                    text = &text[word..];
+                    leading = false;
+                }
+            }
+
+            let mut out = ::std::string::String::with_capacity(html.len());
+            let mut rest = html.as_str();
+            let mut after_block = false;
+            loop {
+                let (text, tail) = rest.split_at(rest.find('<').unwrap_or(rest.len()));
+                let before_block = !tail.starts_with("<!--") && is_block(tail);
+                push_text(&mut out, text, after_block, before_block, tail.is_empty());
+                if tail.is_empty() {
+                    break;
+                }
+                if tail.starts_with("<!--") {
+                    rest = match tail.find("-->") {
+                        ::std::option::Option::Some(end) => &tail[end + 3..],
+                        ::std::option::Option::None => "",
+                    };
+                    continue;
+                }
+
+                let mut quote = ::std::option::Option::None;
+                let mut end = tail.len();
+                for (index, c) in tail.bytes().enumerate().skip(1) {
+                    match (quote, c) {
+                        (::std::option::Option::Some(q), c) if q == c => {
+                            quote = ::std::option::Option::None;
+                        }
+                        (::std::option::Option::Some(_), _) => {}
+                        (::std::option::Option::None, b'"') | (::std::option::Option::None, b'\'') => {
+                            quote = ::std::option::Option::Some(c);
+                        }
+                        (::std::option::Option::None, b'>') => {
+                            end = index + 1;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                let (tag, tail) = tail.split_at(end);
+                out.push_str(tag);
+                rest = tail;
+                after_block = is_block(tag);
+
+                let name = tag_name(tag);
+                let raw = RAW.iter().find(|raw| name.eq_ignore_ascii_case(raw));
+                if let (false, ::std::option::Option::Some(raw)) = (tag.starts_with("</"), raw) {
+                    let close = rest
+                        .match_indices("</")
+                        .map(|(index, _)| index)
+                        .find(|&index| {
+                            rest[index + 2..]
+                                .get(..raw.len())
+                                .map_or(false, |name| name.eq_ignore_ascii_case(raw))
+                        })
+                        .unwrap_or(rest.len());
+                    out.push_str(&rest[..close]);
+                    rest = &rest[close..];
+                }
+            }
+            out
+        }
+    }
+}
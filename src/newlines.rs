@@ -0,0 +1,198 @@
+//! `#[enum_template(newlines = "…")]`: normalize the line endings of the rendered output
+
+use quote::quote;
+
+use crate::{make_static_ty_generics, match_variants, VariantDefinition};
+
+/// `#[enum_template(newlines(style = "…", extensions("…", …)))]`
+pub(crate) struct Newlines {
+    pub(crate) crlf: bool,
+    /// The extensions of the templates to normalize
+    pub(crate) extensions: Vec<syn::LitStr>,
+}
+
+impl Newlines {
+    pub(crate) fn from_style(style: &syn::LitStr) -> syn::Result<Self> {
+        let crlf = match style.value().as_str() {
+            "crlf" => true,
+            "lf" => false,
+            _ => return Err(syn::Error::new(style.span(), "expected \"crlf\" or \"lf\"")),
+        };
+        let extensions = ["txt", "eml"]
+            .iter()
+            .map(|ext| syn::LitStr::new(ext, style.span()))
+            .collect();
+        Ok(Self { crlf, extensions })
+    }
+
+    pub(crate) fn from_list(list: &syn::MetaList) -> syn::Result<Self> {
+        let mut result = None;
+        let mut extensions = None;
+        for nested in &list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(style),
+                    ..
+                })) if path.is_ident("style") => result = Some(Self::from_style(style)?),
+                syn::NestedMeta::Meta(syn::Meta::List(list))
+                    if list.path.is_ident("extensions") =>
+                {
+                    let lits = list
+                        .nested
+                        .iter()
+                        .map(|nested| match nested {
+                            syn::NestedMeta::Lit(syn::Lit::Str(ext)) => Ok(ext.clone()),
+                            nested => Err(syn::Error::new_spanned(nested, "expected a string")),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    extensions = Some(lits);
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected `style = \"…\"` or `extensions(\"…\", …)`",
+                    ));
+                }
+            }
+        }
+        let mut result = result.ok_or_else(|| {
+            syn::Error::new_spanned(list, "expected `style = \"crlf\"` or \"lf\"")
+        })?;
+        if let Some(extensions) = extensions {
+            result.extensions = extensions;
+        }
+        Ok(result)
+    }
+}
+
+/// The writer that converts the line endings, needs to be put into the `const _` block once
+pub(crate) fn make_writer() -> proc_macro2::TokenStream {
+    quote! {
+        struct NewlineWriter<'a, W: ?::std::marker::Sized> {
+            inner: &'a mut W,
+            crlf: ::std::primitive::bool,
+            /// A `'\r'` was read, but it is not known yet if it is followed by a `'\n'`
+            pending_cr: ::std::primitive::bool,
+        }
+
+        impl<'a, W: ::std::fmt::Write + ?::std::marker::Sized> NewlineWriter<'a, W> {
+            fn new(inner: &'a mut W, crlf: ::std::primitive::bool) -> Self {
+                Self {
+                    inner,
+                    crlf,
+                    pending_cr: false,
+                }
+            }
+
+            fn finish(self) -> ::std::fmt::Result {
+                match self.pending_cr {
+                    true => self.inner.write_str("\r"),
+                    false => ::std::result::Result::Ok(()),
+                }
+            }
+        }
+
+        impl<W: ::std::fmt::Write + ?::std::marker::Sized> ::std::fmt::Write for NewlineWriter<'_, W> {
+            fn write_str(&mut self, s: &::std::primitive::str) -> ::std::fmt::Result {
+                let mut rest = s;
+                loop {
+                    let (text, tail) = match rest.find(&['\r', '\n'][..]) {
+                        ::std::option::Option::Some(index) => rest.split_at(index),
+                        ::std::option::Option::None => (rest, ""),
+                    };
+                    if !text.is_empty() {
+                        if self.pending_cr {
+                            self.inner.write_str("\r")?;
+                            self.pending_cr = false;
+                        }
+                        self.inner.write_str(text)?;
+                    }
+                    match tail.as_bytes().first() {
+                        ::std::option::Option::Some(b'\r') => {
+                            if self.pending_cr {
+                                self.inner.write_str("\r")?;
+                            }
+                            self.pending_cr = true;
+                        }
+                        ::std::option::Option::Some(_) => {
+                            self.inner.write_str(match self.crlf {
+                                true => "\r\n",
+                                false => "\n",
+                            })?;
+                            self.pending_cr = false;
+                        }
+                        ::std::option::Option::None => return ::std::result::Result::Ok(()),
+                    }
+                    rest = &tail[1..];
+                }
+            }
+        }
+    }
+}
+
+/// An expression of type `bool`, whether the output of the current variant is normalized
+fn make_is_normalized(
+    newlines: &Newlines,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> proc_macro2::TokenStream {
+    let static_ty_generics = make_static_ty_generics(ast);
+    let extensions = &newlines.extensions;
+    match_variants(data, variant_definitions, |definition| {
+        let ty = definition.template_type(&static_ty_generics);
+        quote!(::std::matches!(
+            <#ty as askama::Template>::EXTENSION,
+            ::std::option::Option::Some(#(#extensions)|*),
+        ))
+    })
+}
+
+/// Wrap the body of `render()`
+pub(crate) fn make_render(
+    newlines: &Newlines,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    render_impl: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let is_normalized = make_is_normalized(newlines, ast, data, variant_definitions);
+    let crlf = newlines.crlf;
+    quote! {
+        let rendered = { #render_impl }?;
+        let is_normalized: ::std::primitive::bool = #is_normalized;
+        if !is_normalized {
+            return ::std::result::Result::Ok(rendered);
+        }
+        let mut normalized = ::std::string::String::with_capacity(rendered.len());
+        let mut writer = NewlineWriter::new(&mut normalized, #crlf);
+        ::std::fmt::Write::write_str(&mut writer, &rendered)?;
+        writer.finish()?;
+        ::std::result::Result::Ok(normalized)
+    }
+}
+
+/// Wrap the body of `render_into()`, which writes into `writer`
+pub(crate) fn make_render_into(
+    newlines: &Newlines,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    render_into_impl: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let is_normalized = make_is_normalized(newlines, ast, data, variant_definitions);
+    let crlf = newlines.crlf;
+    quote! {
+        let is_normalized: ::std::primitive::bool = #is_normalized;
+        if !is_normalized {
+            return { #render_into_impl };
+        }
+        let mut normalizer = NewlineWriter::new(writer, #crlf);
+        {
+            let writer = &mut normalizer;
+            #render_into_impl
+        }?;
+        ::std::result::Result::Ok(normalizer.finish()?)
+    }
+}
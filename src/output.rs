@@ -0,0 +1,107 @@
+//! `#[template(output = "blog/{slug}/index.html")]`: where a variant is written by `write_to()`
+
+use quote::quote;
+use syn::parse_quote;
+
+use crate::args::TemplateArgs;
+use crate::fmt::Fmt;
+use crate::VariantDefinition;
+
+/// Take `output = "…"` out of the `#[template]` of a variant
+pub(crate) fn take(
+    meta: &mut TemplateArgs,
+    is_local: bool,
+    variant: &syn::Variant,
+) -> syn::Result<Option<Fmt>> {
+    match crate::take_variant_key(meta, "output", is_local, variant)? {
+        Some(lit) => parse(variant, &lit).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// The path of `output = "…"`, whose placeholders are fields of `variant`
+fn parse(variant: &syn::Variant, lit: &syn::LitStr) -> syn::Result<Fmt> {
+    if !cfg!(feature = "std") {
+        return Err(syn::Error::new(
+            lit.span(),
+            "`output` needs the feature `std` of askama-enum",
+        ));
+    }
+    if lit.value().is_empty() {
+        return Err(syn::Error::new(lit.span(), "the path in `output` is empty"));
+    }
+    Fmt::parse(variant, lit, "output")
+}
+
+/// `fn output_path(&self)` and `fn write_to(&self, root)`, if a variant has an `output`
+///
+/// Every variant needs an `output` then, because `output_path()` cannot fail.
+pub(crate) fn make_methods(
+    vis: &syn::Visibility,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+) -> syn::Result<Vec<syn::ImplItem>> {
+    if variant_definitions.iter().all(|d| d.output.is_none()) {
+        return Ok(Vec::new());
+    }
+    let mut arms = Vec::new();
+    for (variant, definition) in data.variants.iter().zip(variant_definitions) {
+        let output = match &definition.output {
+            Some(output) => output,
+            None => {
+                let msg = format!(
+                    "other variants have an `output`, so `{}` needs one, too",
+                    variant.ident,
+                );
+                return Err(syn::Error::new(variant.ident.span(), msg));
+            }
+        };
+        let self_variant_name = &variant.ident;
+        let format = &output.format;
+        let (members, names): (Vec<_>, Vec<_>) = output.fields.iter().cloned().unzip();
+        arms.push(quote! {
+            Self::#self_variant_name { #(#members: #names,)* .. } => {
+                ::std::format!(#format, #(#names = #names,)*)
+            }
+        });
+    }
+    let output_path = parse_quote! {
+        /// The path of the variant's `#[template(output = "…")]`, with the fields filled in
+        #vis fn output_path(&self) -> ::std::path::PathBuf {
+            ::std::path::PathBuf::from(match self {
+                #(#arms)*
+            })
+        }
+    };
+    let write_to = parse_quote! {
+        /// Render the variant into `root.join(self.output_path())`
+        ///
+        /// Missing parent directories are created. If rendering fails, the error is returned as an
+        /// `io::Error` of the kind `Other`, and no file is written. If `output_path()` is absolute,
+        /// or contains `..`, e.g. because of a field's value, the error has the kind `InvalidInput`,
+        /// so the file cannot be written outside of `root`.
+        #vis fn write_to(&self, root: &::std::path::Path) -> ::std::io::Result<()> {
+            let output_path = self.output_path();
+            let is_nested = output_path.components().all(|component| {
+                ::std::matches!(
+                    component,
+                    ::std::path::Component::Normal(_) | ::std::path::Component::CurDir,
+                )
+            });
+            if !is_nested {
+                return ::std::result::Result::Err(::std::io::Error::new(
+                    ::std::io::ErrorKind::InvalidInput,
+                    ::std::format!("the output path {:?} is not inside the root", output_path),
+                ));
+            }
+            let rendered = askama::Template::render(self)
+                .map_err(|err| ::std::io::Error::new(::std::io::ErrorKind::Other, err))?;
+            let path = root.join(output_path);
+            if let ::std::option::Option::Some(parent) = path.parent() {
+                ::std::fs::create_dir_all(parent)?;
+            }
+            ::std::fs::write(path, rendered)
+        }
+    };
+    Ok(vec![output_path, write_to])
+}
@@ -0,0 +1,107 @@
+//! `{variant}` placeholders in inline `source` strings, and in `path_pattern = "…"`
+
+use syn::parse_quote;
+
+use crate::args::{self, TemplateArgs};
+use crate::case::RenameRule;
+use crate::dir;
+
+/// Take `path_pattern = "…"` and `case = "…"` out of `meta`, and use the file of the variant as
+/// its `path`
+///
+/// `case` is inherited from the enum's `#[template]`, but in the `#[template]` of the variant it
+/// needs a `path_pattern` of the variant. The file must exist below `dir`, if any.
+pub(crate) fn take_path_pattern(
+    meta: &mut TemplateArgs,
+    local_meta: Option<&TemplateArgs>,
+    dir: Option<&str>,
+    variant: &syn::Variant,
+    owner: &str,
+) -> syn::Result<()> {
+    let case = match meta.take_key("case") {
+        Some(value) => {
+            let lit = args::lit_str(&value)?;
+            let rule = RenameRule::from_case_lit(&lit)?;
+            Some((lit, rule))
+        }
+        None => None,
+    };
+    if let Some((lit, _)) = &case {
+        let is_local = matches!(local_meta, Some(meta) if meta.has_key("case"));
+        if is_local && !meta.has_key("path_pattern") {
+            return Err(syn::Error::new(
+                lit.span(),
+                "`case` can only be used with `path_pattern`",
+            ));
+        }
+    }
+    let pattern = match meta.take_key("path_pattern") {
+        Some(pattern) => args::lit_str(&pattern)?,
+        None => return Ok(()),
+    };
+    if meta.has_key("source") || meta.has_key("path") || meta.has_key("doc") {
+        return Err(syn::Error::new(
+            pattern.span(),
+            "cannot combine `path_pattern` with `source`, `path` or `doc`",
+        ));
+    }
+    let name = match &case {
+        Some((_, rule)) => rule.apply_with_acronyms(&crate::unraw(&variant.ident)),
+        None => crate::unraw(&variant.ident),
+    };
+    let path = substitute(&pattern, &name)?;
+    let path = match dir {
+        Some(dir) => syn::LitStr::new(&dir::join(dir, &path.value()), path.span()),
+        None => path,
+    };
+    let root = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_default()
+        .join("templates");
+    if !root.join(path.value()).is_file() {
+        let msg = format!("{} -> {} not found", owner, path.value());
+        return Err(syn::Error::new(pattern.span(), msg));
+    }
+    meta.push_value(syn::Ident::new("path", pattern.span()), parse_quote!(#path));
+    Ok(())
+}
+
+/// Replace `{variant}` and `{variant:rule}` with the (renamed) name of the variant
+///
+/// A placeholder directly after another `{` is left alone, so `{{variant}}` stays an askama
+/// expression.
+pub(crate) fn substitute(source: &syn::LitStr, variant: &str) -> syn::Result<syn::LitStr> {
+    const PREFIX: &str = "{variant";
+
+    let value = source.value();
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value.as_str();
+    while let Some(start) = rest.find(PREFIX) {
+        let (text, placeholder) = rest.split_at(start);
+        result.push_str(text);
+        let tail = &placeholder[PREFIX.len()..];
+        let replacement = match (text.ends_with('{'), tail.chars().next()) {
+            (false, Some('}')) => Some((variant.to_owned(), 1)),
+            (false, Some(':')) => match tail.find('}') {
+                Some(end) => {
+                    let rule = syn::LitStr::new(&tail[1..end], source.span());
+                    Some((RenameRule::from_lit(&rule)?.apply(variant), end + 1))
+                }
+                None => None,
+            },
+            _ => None,
+        };
+        match replacement {
+            Some((name, len)) => {
+                result.push_str(&name);
+                rest = &tail[len..];
+            }
+            None => {
+                result.push_str(PREFIX);
+                rest = tail;
+            }
+        }
+    }
+    result.push_str(rest);
+    Ok(syn::LitStr::new(&result, source.span()))
+}
@@ -0,0 +1,92 @@
+//! `#[template(trim)]`, `#[template(minify)]` and `#[template(postprocess = "path::to::fn")]`: change
+//! the rendered output, and `#[template(memoize = "…")]`: cache it with the helpers of `memoize`
+//!
+//! All of them need the complete output of the variant, so `render_into()` renders into a buffer first.
+
+use quote::quote_spanned;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+use crate::args::{self, TemplateArgs};
+use crate::{memoize, VariantDefinition};
+
+/// The name of the buffer that `render_into()` renders into
+const BUFFER: &str = "post_buf";
+
+/// Take the function of `postprocess = "…"` out of `meta`, or `None` for `postprocess = ""`
+pub(crate) fn take(meta: &mut TemplateArgs) -> syn::Result<Option<syn::Path>> {
+    let lit = match meta.take_key("postprocess") {
+        Some(value) => args::lit_str(&value)?,
+        None => return Ok(None),
+    };
+    if lit.value().is_empty() {
+        return Ok(None);
+    }
+    match lit.parse() {
+        Ok(path) => Ok(Some(path)),
+        Err(_) => Err(syn::Error::new(
+            lit.span(),
+            "expected the path of a `fn(String) -> askama::Result<String>`",
+        )),
+    }
+}
+
+/// The arguments of `render_into()` if the output is buffered, i.e. the buffer instead of the writer
+pub(crate) fn buffer_args() -> Punctuated<syn::Expr, syn::token::Comma> {
+    let buffer = syn::Ident::new(BUFFER, proc_macro2::Span::call_site());
+    let mut args = Punctuated::new();
+    args.push(parse_quote!(&mut #buffer));
+    args
+}
+
+/// Trim, minify, post-process and cache the output of `call`, then write it into `target` for
+/// `render_into()`
+///
+/// `variant` is only given for the struct of the variant's `#[template]`, because the cache is
+/// shared by all calls of the variant.
+pub(crate) fn wrap(
+    call: syn::Expr,
+    meth_name: &str,
+    definition: &VariantDefinition,
+    variant: Option<&syn::Variant>,
+    target: &Punctuated<syn::Expr, syn::token::Comma>,
+) -> syn::Expr {
+    let buffer = syn::Ident::new(BUFFER, proc_macro2::Span::call_site());
+    let into = meth_name != "render";
+    let mut call = match into {
+        true => parse_quote!(#call.map(|()| #buffer)),
+        false => call,
+    };
+    if definition.trim {
+        call = parse_quote!(#call.map(|rendered| {
+            ::std::borrow::ToOwned::to_owned(
+                rendered.trim_matches(|c: ::std::primitive::char| c.is_ascii_whitespace()),
+            )
+        }));
+    }
+    if definition.minify {
+        call = parse_quote!(#call.map(minify_html));
+    }
+    if let Some(path) = &definition.postprocess {
+        let hook = quote_spanned!(path.span()=> {
+            let hook: fn(
+                ::std::string::String,
+            ) -> askama::Result<::std::string::String> = #path;
+            hook
+        });
+        call = parse_quote!(#call.and_then(#hook));
+    }
+    if let (Some(variant), Some(key)) = (variant, &definition.memoize) {
+        call = memoize::wrap(call, definition, variant, key);
+    }
+    match into {
+        true => parse_quote!({
+            let mut #buffer = ::std::string::String::new();
+            #call.and_then(|rendered| {
+                ::std::fmt::Write::write_str(#target, &rendered).map_err(askama::Error::from)
+            })
+        }),
+        false => call,
+    }
+}
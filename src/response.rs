@@ -0,0 +1,211 @@
+//! The HTTP responses of the web integrations: `router`, `conditional`, and `rouille`
+
+use quote::{quote, ToTokens};
+
+use crate::{match_variants, VariantDefinition};
+
+/// The parts of a variant's HTTP response, as expressions
+pub(crate) struct Parts {
+    /// `Option<&'static str>`, the target of `#[template(redirect = "…")]`
+    redirect: proc_macro2::TokenStream,
+    /// `u16`, the `#[template(status = …)]`, or 200
+    status: proc_macro2::TokenStream,
+    /// `&'static str`, the MIME type of the variant's template
+    mime_type: proc_macro2::TokenStream,
+    /// `&'static [(&'static str, &'static str)]`, the `#[template(header(…))]`s
+    headers: proc_macro2::TokenStream,
+    /// The same as `headers`, but with lowercase names for `HeaderName::from_static()`
+    lowercase_headers: proc_macro2::TokenStream,
+}
+
+impl Parts {
+    /// The parts of the variant of `self`
+    pub(crate) fn of_self(
+        data: &syn::DataEnum,
+        variant_definitions: &[VariantDefinition],
+        static_ty_generics: &proc_macro2::TokenStream,
+    ) -> Self {
+        let by_variant = |f: fn(&Self) -> &proc_macro2::TokenStream| {
+            match_variants(data, variant_definitions, |definition| {
+                f(&Self::of_variant(definition, static_ty_generics)).clone()
+            })
+        };
+        Self {
+            redirect: by_variant(|parts| &parts.redirect),
+            status: by_variant(|parts| &parts.status),
+            mime_type: by_variant(|parts| &parts.mime_type),
+            headers: by_variant(|parts| &parts.headers),
+            lowercase_headers: by_variant(|parts| &parts.lowercase_headers),
+        }
+    }
+
+    /// The parts of a single variant
+    pub(crate) fn of_variant(
+        definition: &VariantDefinition,
+        static_ty_generics: &proc_macro2::TokenStream,
+    ) -> Self {
+        let redirect = match &definition.redirect {
+            Some(target) => quote!(::std::option::Option::Some(#target)),
+            None => quote!(::std::option::Option::None),
+        };
+        let status = match &definition.status {
+            Some(status) => status.to_token_stream(),
+            None => quote!(200),
+        };
+        let mime_type = match &definition.mime {
+            Some(mime) => mime.to_token_stream(),
+            None => {
+                let ty = definition.template_type(static_ty_generics);
+                quote!(<#ty as askama::Template>::MIME_TYPE)
+            }
+        };
+        let headers = definition
+            .headers
+            .iter()
+            .map(|(name, value)| quote!((#name, #value)));
+        // The names were validated when the attribute was parsed.
+        let lowercase_headers = definition.headers.iter().map(|(name, value)| {
+            let name = name.value().to_ascii_lowercase();
+            quote!((#name, #value))
+        });
+        Self {
+            redirect,
+            status,
+            mime_type,
+            headers: quote!(&[#(#headers),*]),
+            lowercase_headers: quote!(&[#(#lowercase_headers),*]),
+        }
+    }
+}
+
+/// Render `subject` into an axum response
+///
+/// With `if_none_match`, the response gets an `ETag` header, and can be `304 Not Modified`.
+/// The helpers of `conditional::make_helpers()` need to be in scope then.
+pub(crate) fn axum(
+    parts: &Parts,
+    subject: proc_macro2::TokenStream,
+    if_none_match: Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let Parts {
+        redirect,
+        status,
+        mime_type,
+        lowercase_headers,
+        ..
+    } = parts;
+    let response = match if_none_match {
+        Some(if_none_match) => quote! {
+            let etag = etag_of(&body);
+            match is_not_modified(status, #if_none_match, &etag) {
+                true => axum::response::IntoResponse::into_response((
+                    axum::http::StatusCode::NOT_MODIFIED,
+                    [(axum::http::header::ETAG, etag)],
+                )),
+                false => axum::response::IntoResponse::into_response((
+                    axum::http::StatusCode::from_u16(status).unwrap_or(axum::http::StatusCode::OK),
+                    [
+                        (
+                            axum::http::header::CONTENT_TYPE,
+                            ::std::string::ToString::to_string(#mime_type),
+                        ),
+                        (axum::http::header::ETAG, etag),
+                    ],
+                    body,
+                )),
+            }
+        },
+        None => quote! {
+            axum::response::IntoResponse::into_response((
+                axum::http::StatusCode::from_u16(status).unwrap_or(axum::http::StatusCode::OK),
+                [(axum::http::header::CONTENT_TYPE, #mime_type)],
+                body,
+            ))
+        },
+    };
+    quote! {{
+        let redirect: ::std::option::Option<&'static ::std::primitive::str> = #redirect;
+        if let ::std::option::Option::Some(target) = redirect {
+            return axum::response::IntoResponse::into_response(
+                axum::response::Redirect::to(target),
+            );
+        }
+        let body = match askama::Template::render(#subject) {
+            ::std::result::Result::Ok(body) => body,
+            ::std::result::Result::Err(err) => {
+                return axum::response::IntoResponse::into_response((
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    ::std::string::ToString::to_string(&err),
+                ));
+            }
+        };
+        let status: ::std::primitive::u16 = #status;
+        let mut response: axum::response::Response = { #response };
+        let headers: &[(&'static ::std::primitive::str, &'static ::std::primitive::str)] =
+            #lowercase_headers;
+        for &(name, value) in headers {
+            response.headers_mut().append(
+                axum::http::HeaderName::from_static(name),
+                axum::http::HeaderValue::from_static(value),
+            );
+        }
+        response
+    }}
+}
+
+/// Render `subject` into a rouille response
+///
+/// With `if_none_match`, the response gets an `ETag` header, and can be `304 Not Modified`.
+/// The helpers of `conditional::make_helpers()` need to be in scope then.
+pub(crate) fn rouille(
+    parts: &Parts,
+    subject: proc_macro2::TokenStream,
+    if_none_match: Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let Parts {
+        redirect,
+        status,
+        mime_type,
+        headers,
+        ..
+    } = parts;
+    let response = match if_none_match {
+        Some(if_none_match) => quote! {
+            let etag = etag_of(&body);
+            let response = match is_not_modified(status, #if_none_match, &etag) {
+                true => rouille::Response {
+                    status_code: 304,
+                    headers: ::std::vec::Vec::new(),
+                    data: rouille::ResponseBody::empty(),
+                    upgrade: ::std::option::Option::None,
+                },
+                false => rouille::Response::from_data(#mime_type, body).with_status_code(status),
+            };
+            response.with_unique_header("ETag", etag)
+        },
+        None => quote! {
+            rouille::Response::from_data(#mime_type, body).with_status_code(status)
+        },
+    };
+    quote! {{
+        let redirect: ::std::option::Option<&'static ::std::primitive::str> = #redirect;
+        if let ::std::option::Option::Some(target) = redirect {
+            return rouille::Response::redirect_303(target);
+        }
+        let body = match askama::Template::render(#subject) {
+            ::std::result::Result::Ok(body) => body,
+            ::std::result::Result::Err(err) => {
+                return rouille::Response::text(::std::string::ToString::to_string(&err))
+                    .with_status_code(500);
+            }
+        };
+        let status: ::std::primitive::u16 = #status;
+        let mut response: rouille::Response = { #response };
+        let headers: &[(&'static ::std::primitive::str, &'static ::std::primitive::str)] =
+            #headers;
+        for &(name, value) in headers {
+            response = response.with_additional_header(name, value);
+        }
+        response
+    }}
+}
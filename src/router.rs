@@ -0,0 +1,115 @@
+//! `#[enum_template(router)]`: an axum `Router` that serves the unit variants
+
+use std::collections::BTreeMap;
+
+use quote::quote;
+use syn::parse_quote;
+
+use crate::args::TemplateArgs;
+use crate::case::RenameRule;
+use crate::{fail_at, response, unraw, EnumOptions, VariantDefinition};
+
+/// Take `route = "…"` out of the `#[template]` of a variant, which needs to be a unit variant
+pub(crate) fn take_route(
+    meta: &mut TemplateArgs,
+    is_local: bool,
+    variant: &syn::Variant,
+) -> syn::Result<Option<syn::LitStr>> {
+    let route = crate::take_variant_key(meta, "route", is_local, variant)?;
+    match &route {
+        Some(route) if !matches!(variant.fields, syn::Fields::Unit) => Err(syn::Error::new(
+            route.span(),
+            "`route` can only be used for unit variants",
+        )),
+        _ => Ok(route),
+    }
+}
+
+/// `fn router<S>()` with `#[enum_template(router)]`, which the variants' `route` needs
+pub(crate) fn make(
+    options: &EnumOptions,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    default_variant_name: Option<&syn::Ident>,
+    static_ty_generics: &proc_macro2::TokenStream,
+) -> Result<Option<syn::ImplItem>, proc_macro::TokenStream> {
+    match &options.router {
+        Some(path) => make_router(
+            path,
+            ast,
+            data,
+            variant_definitions,
+            default_variant_name,
+            static_ty_generics,
+        )
+        .map(Some),
+        None => match variant_definitions.iter().find_map(|d| d.route.as_ref()) {
+            Some(route) => Err(fail_at(route, "`route` needs #[enum_template(router)]")),
+            None => Ok(None),
+        },
+    }
+}
+
+/// `fn router<S>() -> axum::Router<S>`
+fn make_router(
+    path: &syn::Path,
+    ast: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    variant_definitions: &[VariantDefinition],
+    default_variant_name: Option<&syn::Ident>,
+    static_ty_generics: &proc_macro2::TokenStream,
+) -> Result<syn::ImplItem, proc_macro::TokenStream> {
+    let has_type_generics = ast
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, syn::GenericParam::Lifetime(_)));
+    if has_type_generics {
+        return Err(fail_at(
+            path,
+            "`router` cannot be used for enums with type or const generics",
+        ));
+    }
+
+    let mut routes = BTreeMap::new();
+    for (variant, definition) in data.variants.iter().zip(variant_definitions) {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            continue;
+        }
+        let route = match &definition.route {
+            Some(route) => route.value(),
+            None if Some(&definition.helper) == default_variant_name => "/".to_owned(),
+            None => format!("/{}", RenameRule::Kebab.apply(&unraw(&variant.ident))),
+        };
+        if let Some((other, _)) = routes.insert(route.clone(), (&variant.ident, definition)) {
+            let msg = format!("the route {:?} is used by `{}`, too", route, other);
+            return Err(fail_at(&variant.ident, &msg));
+        }
+    }
+
+    let enum_name = &ast.ident;
+    let vis = &ast.vis;
+    let routes = routes.into_iter().map(|(route, (ident, definition))| {
+        let parts = response::Parts::of_variant(definition, static_ty_generics);
+        let response = response::axum(&parts, quote!(&page), None);
+        quote! {
+            .route(#route, axum::routing::get(|| async {
+                let page = #enum_name::#ident;
+                #response
+            }))
+        }
+    });
+    Ok(parse_quote! {
+        /// An axum router with a `GET` route for every unit variant
+        ///
+        /// Variants with a `redirect` are answered with a 303 redirect.
+        /// If rendering fails, the response has the status code 500, and contains the error message.
+        #vis fn router<S>() -> axum::Router<S>
+        where
+            S: ::std::clone::Clone + ::std::marker::Send + ::std::marker::Sync + 'static,
+        {
+            axum::Router::new() #(#routes)*
+        }
+    })
+}
@@ -0,0 +1,116 @@
+//! A JSON description of the variables that each variant's template can use
+//!
+//! The JSON is built by hand to keep the dependencies of the proc-macro small.
+
+use std::fmt::Write;
+
+use quote::ToTokens;
+
+use crate::fields::FieldTransform;
+use crate::{is_untagged_variant, unraw, EnumOptions};
+
+/// `{"enum": "…", "variants": [{"name": "…", "fields": [{"name": "…", "type": "…", "origin": "…"}]}]}`
+pub(crate) fn context_schema(
+    ast: &syn::DeriveInput,
+    options: &EnumOptions,
+    data: &syn::DataEnum,
+) -> String {
+    let mut json = String::new();
+    json.push_str("{\"enum\":");
+    write_str(&mut json, &unraw(&ast.ident));
+    json.push_str(",\"variants\":[");
+    for (index, variant) in data.variants.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str("{\"name\":");
+        write_str(&mut json, &unraw(&variant.ident));
+        json.push_str(",\"fields\":[");
+        match &options.untagged {
+            Some(untagged) if is_untagged_variant(variant) => {
+                let ty = variant.fields.iter().next().unwrap().ty.to_token_stream();
+                write_field(&mut json, &unraw(&untagged.value), &ty.to_string(), "value");
+                json.push(',');
+                write_field(&mut json, "__variant", "&'static str", "variant");
+            }
+            _ => {
+                for (index, field) in variant.fields.iter().enumerate() {
+                    if index > 0 {
+                        json.push(',');
+                    }
+                    let transform = FieldTransform::from_field(field).ok().flatten();
+                    let name = match transform.as_ref().and_then(FieldTransform::rename) {
+                        Some(ident) => unraw(ident),
+                        None => match &field.ident {
+                            Some(ident) => unraw(ident),
+                            None => index.to_string(),
+                        },
+                    };
+                    let ty = match &transform {
+                        Some(transform) => transform.ty().to_token_stream(),
+                        None => field.ty.to_token_stream(),
+                    };
+                    let ty = ty.to_string();
+                    write_field(&mut json, &name, &ty, "field");
+                }
+            }
+        }
+        for (index, computed) in options.compute.iter().enumerate() {
+            if index > 0 || !variant.fields.is_empty() {
+                json.push(',');
+            }
+            let ty = match &computed.ty {
+                Some(_) => "IcuFormatter",
+                None => "String",
+            };
+            write_field(&mut json, &unraw(&computed.name), ty, "computed");
+        }
+        json.push_str("]}");
+    }
+    json.push_str("]}");
+    json
+}
+
+fn write_field(json: &mut String, name: &str, ty: &str, origin: &str) {
+    json.push_str("{\"name\":");
+    write_str(json, name);
+    json.push_str(",\"type\":");
+    write_str(json, &tidy_type(ty));
+    json.push_str(",\"origin\":");
+    write_str(json, origin);
+    json.push('}');
+}
+
+fn write_str(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// Turn `Vec < & 'a str >` into `Vec<&'a str>`
+fn tidy_type(ty: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(ty.len());
+    for token in ty.split(' ').filter(|token| !token.is_empty()) {
+        let separated = match (result.chars().last(), token.chars().next()) {
+            (Some(prev), Some(next)) => {
+                (is_word(prev) && is_word(next)) || matches!(prev, ',' | ';')
+            }
+            _ => false,
+        };
+        if separated {
+            result.push(' ');
+        }
+        result.push_str(token);
+    }
+    result
+}
@@ -0,0 +1,128 @@
+//! `#[template_default(unit(…), fields(…))]`: default templates per variant shape
+//!
+//! Like the groups of `#[template_for]`, the defaults are expanded before anything else looks at
+//! the enum: every variant of the shape without a `#[template]` of its own gets the attribute
+//! `#[template(key = value, …)]` of the shape's list.
+
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::args::{self, TemplateArgs};
+use crate::{is_template_attr, unraw};
+
+/// Add the `#[template]` of its shape's default to every variant without a `#[template]`
+pub(crate) fn apply(ast: &mut syn::DeriveInput) -> syn::Result<()> {
+    let data = match &mut ast.data {
+        syn::Data::Enum(data) => data,
+        _ => return Ok(()),
+    };
+    let mut unit = None;
+    let mut fields = None;
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("template_default"))
+    {
+        if unit.is_some() || fields.is_some() {
+            return Err(syn::Error::new(
+                attr.path.span(),
+                "there can only be one #[template_default], e.g. \
+                `#[template_default(unit(…), fields(…))]`",
+            ));
+        }
+        let mut meta = TemplateArgs::from_attr(attr)?;
+        for (shape, default) in [("unit", &mut unit), ("fields", &mut fields)] {
+            let mut lists = meta.take_lists(shape).into_iter();
+            if let Some(list) = lists.next() {
+                if let Some(other) = lists.next() {
+                    return Err(syn::Error::new(other.key.span(), "duplicated key"));
+                }
+                let args = TemplateArgs {
+                    path: syn::Ident::new("template", list.key.span()).into(),
+                    args: list.parse_args()?,
+                };
+                *default = Some((list.key, args));
+            }
+        }
+        if let Some(arg) = meta.args.first() {
+            return Err(syn::Error::new(
+                arg.key().span(),
+                "#[template_default] expects the lists `unit(…)` and `fields(…)`",
+            ));
+        }
+        if unit.is_none() && fields.is_none() {
+            return Err(syn::Error::new(
+                attr.path.span(),
+                "#[template_default] needs `unit(…)` or `fields(…)`",
+            ));
+        }
+    }
+    for variant in &mut data.variants {
+        if variant.attrs.iter().any(is_template_attr) {
+            continue;
+        }
+        let default = match &variant.fields {
+            syn::Fields::Unit => &unit,
+            _ => &fields,
+        };
+        if let Some((shape, meta)) = default {
+            check_fields(&ast.ident, variant, shape, meta)?;
+            variant.attrs.push(parse_quote!(#[#meta]));
+        }
+    }
+    Ok(())
+}
+
+/// Every `self.field` in an inline `source` of the default needs to be a field of the variant
+fn check_fields(
+    enum_name: &syn::Ident,
+    variant: &syn::Variant,
+    shape: &syn::Ident,
+    meta: &TemplateArgs,
+) -> syn::Result<()> {
+    let source = match meta.value("source") {
+        Some(source) => match args::lit_str(source) {
+            Ok(source) => source,
+            Err(_) => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+    let members = variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => unraw(ident),
+            None => index.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let text = source.value();
+    for (start, _) in text.match_indices("self.") {
+        let before = text[..start].chars().next_back();
+        if matches!(before, Some(c) if c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let member = text[start + 5..]
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        if member.is_empty() || members.iter().any(|other| other == member) {
+            continue;
+        }
+        let msg = format!(
+            "`{}::{}` has no field `{}`, but #[template_default({}(…))] uses `self.{}`",
+            enum_name,
+            unraw(&variant.ident),
+            member,
+            shape,
+            member,
+        );
+        let mut err = syn::Error::new(source.span(), msg);
+        err.combine(syn::Error::new(
+            variant.ident.span(),
+            "give the variant a #[template] of its own",
+        ));
+        return Err(err);
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! `#[template(skip)]` and `#[template(redirect = "/path")]`: variants that render nothing
+
+use syn::parse_quote;
+
+use crate::args::{self, TemplateArgs};
+
+/// Take `skip` and `redirect = "…"` out of the `#[template]` of a variant
+///
+/// A redirect has no body, so the variant is rendered like a `skip` variant, and `skip` is set for
+/// it, too. `own_source` is the key that gives the variant a template of its own, if any.
+pub(crate) fn take(
+    meta: &mut TemplateArgs,
+    own_source: Option<&str>,
+    delegated: bool,
+) -> syn::Result<(Option<syn::Ident>, Option<syn::LitStr>)> {
+    let redirect = match meta.take_key("redirect") {
+        Some(value) => Some(parse_redirect(&value)?),
+        None => None,
+    };
+    let mut skip = meta.take_path("skip");
+    if let Some(redirect) = &redirect {
+        if let Some(skip) = &skip {
+            return Err(syn::Error::new(
+                skip.span(),
+                "cannot combine `skip` with `redirect`",
+            ));
+        }
+        if let Some(key) = own_source {
+            let msg = format!(
+                "`redirect` variants render nothing, so they cannot be combined with `{}`",
+                key,
+            );
+            return Err(syn::Error::new(redirect.span(), msg));
+        }
+        skip = Some(syn::Ident::new("skip", redirect.span()));
+    }
+    if let Some(skip) = &skip {
+        if delegated || meta.has_key("delegate_with") {
+            return Err(syn::Error::new(
+                skip.span(),
+                "cannot combine `skip` with `delegate`",
+            ));
+        }
+        if let Some(key) = own_source {
+            let msg = format!(
+                "`skip` variants render nothing, so they cannot be combined with `{}`",
+                key,
+            );
+            return Err(syn::Error::new(skip.span(), msg));
+        }
+        meta.clear_source(skip.span());
+        if !meta.has_key("ext") {
+            meta.push_value(syn::Ident::new("ext", skip.span()), parse_quote!("txt"));
+        }
+    }
+    Ok((skip, redirect))
+}
+
+/// The target of `redirect = "/path"`, a non-empty URL without whitespace
+fn parse_redirect(value: &syn::Expr) -> syn::Result<syn::LitStr> {
+    let target = args::lit_str(value)?;
+    let value = target.value();
+    if value.is_empty() || value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(syn::Error::new(
+            target.span(),
+            "expected a URL without whitespace, e.g. `redirect = \"/auth/login\"`",
+        ));
+    }
+    Ok(target)
+}
@@ -0,0 +1,128 @@
+//! `#[template(prepend_source = "…", append_source = "…")]`: a shared header and footer
+//!
+//! askama cannot add text to a template file, so a `path` is read and turned into an inline
+//! `source` if there is something to add.
+
+use std::path::PathBuf;
+
+use crate::args::{self, TemplateArgs};
+use crate::expand::Include;
+
+/// The keys that give a variant a template of its own
+const SOURCE_KEYS: &[&str] = &[
+    "source",
+    "source_file",
+    "source_env",
+    "path",
+    "path_pattern",
+    "doc",
+];
+
+/// Turn `prepend = "…"` and `append = "…"` in the `#[template]` of a variant into
+/// `prepend_source` and `append_source`, which surround the source of the enum's `#[template]`
+///
+/// The variant must not have a template of its own, the enum's `#[template]` must have one.
+pub(crate) fn compose(local: &mut TemplateArgs, global: Option<&TemplateArgs>) -> syn::Result<()> {
+    for (key, full_key) in [("prepend", "prepend_source"), ("append", "append_source")]
+        .iter()
+        .copied()
+    {
+        let value = match local.take_key(key) {
+            Some(value) => args::lit_str(&value)?,
+            None => continue,
+        };
+        if let Some(source_key) = SOURCE_KEYS.iter().find(|name| local.has_key(name)) {
+            let msg = format!(
+                "`{}` adds to the template of the enum's #[template], so it cannot be combined \
+                with `{}`; use `{}` to add to the variant's own template",
+                key, source_key, full_key,
+            );
+            return Err(syn::Error::new(value.span(), msg));
+        }
+        if local.has_key(full_key) {
+            let msg = format!("cannot combine `{}` with `{}`", key, full_key);
+            return Err(syn::Error::new(value.span(), msg));
+        }
+        if !local.has_key("like")
+            && !matches!(global, Some(global) if SOURCE_KEYS.iter().any(|name| global.has_key(name)))
+        {
+            let msg = format!(
+                "`{}` needs a `source` or `path` in the enum's #[template] to add to",
+                key,
+            );
+            return Err(syn::Error::new(value.span(), msg));
+        }
+        local.push_value(
+            syn::Ident::new(full_key, value.span()),
+            syn::parse_quote!(#value),
+        );
+    }
+    Ok(())
+}
+
+/// Take `prepend_source = "…"` and `append_source = "…"` out of `meta`
+pub(crate) fn take(
+    meta: &mut TemplateArgs,
+) -> syn::Result<(Option<syn::LitStr>, Option<syn::LitStr>)> {
+    let prepend = match meta.take_key("prepend_source") {
+        Some(value) => Some(args::lit_str(&value)?),
+        None => None,
+    };
+    let append = match meta.take_key("append_source") {
+        Some(value) => Some(args::lit_str(&value)?),
+        None => None,
+    };
+    Ok((prepend, append))
+}
+
+/// Surround the `source` of `meta` with `prepend` and `append`
+///
+/// The absolute paths of the files that were read are returned, so the crate can be rebuilt if
+/// they change.
+pub(crate) fn apply(
+    meta: &mut TemplateArgs,
+    prepend: Option<&syn::LitStr>,
+    append: Option<&syn::LitStr>,
+    owner: &str,
+) -> syn::Result<Vec<Include>> {
+    let prepend = prepend.map(syn::LitStr::value).unwrap_or_default();
+    let append = append.map(syn::LitStr::value).unwrap_or_default();
+    if prepend.is_empty() && append.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut includes = Vec::new();
+    if let Some(path) = meta.take_key("path") {
+        let path = args::lit_str(&path)?;
+        let full_path = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join("templates")
+            .join(path.value());
+        let source = std::fs::read_to_string(&full_path).map_err(|err| {
+            let msg = format!("{}: could not read {:?}: {}", owner, path.value(), err);
+            syn::Error::new(path.span(), msg)
+        })?;
+        includes.push(Include::File(full_path.display().to_string()));
+        let source = syn::LitStr::new(&source, path.span());
+        meta.push_value(
+            syn::Ident::new("source", path.span()),
+            syn::parse_quote!(#source),
+        );
+        let ext = std::path::Path::new(&path.value())
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned());
+        if let (false, Some(ext)) = (meta.has_key("ext"), ext) {
+            let ext = syn::LitStr::new(&ext, path.span());
+            meta.push_value(syn::Ident::new("ext", path.span()), syn::parse_quote!(#ext));
+        }
+    }
+    if let Some(syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(source),
+        ..
+    })) = meta.value_mut("source")
+    {
+        let value = format!("{}{}{}", prepend, source.value(), append);
+        *source = syn::LitStr::new(&value, source.span());
+    }
+    Ok(includes)
+}
@@ -0,0 +1,244 @@
+//! The keys of a `#[template(…)]` that describe the rendered response or choose between builds,
+//! e.g. `mime`, `header(…)`, `cfg(…)` or `alternate(…)`
+
+use syn::parse::{ParseStream, Parser};
+use syn::parse_quote;
+use syn::spanned::Spanned;
+use syn::Token;
+
+use crate::args::{self, TemplateArgs};
+
+/// A `#[template(…)]` attribute with the keys interpreted by askama-enum removed
+pub(crate) struct TemplateMeta {
+    /// The attributes to copy onto the generated struct
+    pub(crate) attrs: Vec<syn::Attribute>,
+    /// `#[template(deprecated = "…")]`
+    pub(crate) deprecated: Option<syn::LitStr>,
+    /// `#[template(mime = "…")]`
+    pub(crate) mime: Option<syn::LitStr>,
+    /// `#[template(status = …)]`
+    pub(crate) status: Option<syn::LitInt>,
+    /// The names and values of `#[template(header("Name" = "value"), …)]`
+    pub(crate) headers: Vec<(syn::LitStr, syn::LitStr)>,
+    /// `#[template(size_hint = …)]`
+    pub(crate) size_hint: Option<syn::LitInt>,
+    /// The predicates of `#[template(cfg(predicate, …), …)]`
+    pub(crate) cfg_alternatives: Vec<syn::Meta>,
+    /// The attribute to copy onto the struct of `#[template(alternate(…))]`
+    pub(crate) alternate: Option<syn::Attribute>,
+    /// The pairs of `#[template(meta(key = "value", …))]`
+    pub(crate) metadata: Vec<(syn::Ident, syn::LitStr)>,
+}
+
+/// Take the keys of [`TemplateMeta`] out of `meta`, and turn the rest into `#[template]` attributes
+pub(crate) fn process(mut meta: TemplateArgs) -> syn::Result<TemplateMeta> {
+    let deprecated = match meta.take_key("deprecated") {
+        Some(value) => Some(args::lit_str(&value)?),
+        None => None,
+    };
+    let mime = match (meta.take_key("mime"), meta.take_key("content_type")) {
+        (Some(_), Some(value)) => {
+            return Err(syn::Error::new(
+                value.span(),
+                "`content_type` is another name for `mime`, so only use one of them",
+            ));
+        }
+        (Some(value), None) | (None, Some(value)) => Some(parse_mime(args::lit_str(&value)?)?),
+        (None, None) => None,
+    };
+    let status = match meta.take_key("status") {
+        Some(value) => Some(parse_status(&value)?),
+        None => None,
+    };
+    let headers = meta
+        .take_lists("header")
+        .iter()
+        .map(parse_header)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let size_hint = match meta.take_key("size_hint") {
+        Some(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        })) => {
+            lit.base10_parse::<usize>()?;
+            Some(lit)
+        }
+        Some(value) => {
+            return Err(syn::Error::new(value.span(), "expected an integer literal"));
+        }
+        None => None,
+    };
+    let metadata_lists = meta.take_lists("meta");
+    if let Some(list) = metadata_lists.get(1) {
+        return Err(syn::Error::new(list.key.span(), "duplicated key"));
+    }
+    let mut metadata = Vec::<(syn::Ident, syn::LitStr)>::new();
+    if let Some(list) = metadata_lists.first() {
+        for arg in list.parse_args()? {
+            let (key, value) = match arg {
+                args::TemplateArg::NameValue { key, value, .. } => (key, args::lit_str(&value)?),
+                arg => {
+                    return Err(syn::Error::new(
+                        arg.key().span(),
+                        "expected `key = \"value\"`",
+                    ));
+                }
+            };
+            if metadata.iter().any(|(other, _)| *other == key) {
+                return Err(syn::Error::new(key.span(), "duplicated key"));
+            }
+            metadata.push((key, value));
+        }
+    }
+    let mut alternatives = Vec::new();
+    for alternative in meta.take_lists("cfg") {
+        let (predicate, keys) = alternative.parse_predicate_and_args().map_err(|err| {
+            let msg = "expected a configuration predicate, e.g. \
+                `cfg(feature = \"name\", path = \"…\")`";
+            let mut result = syn::Error::new(alternative.key.span(), msg);
+            result.combine(err);
+            result
+        })?;
+        alternatives.push((predicate, args::merge(&meta.args, keys)));
+    }
+    let alternate = meta.take_lists("alternate");
+    if let Some(list) = alternate.get(1) {
+        return Err(syn::Error::new(list.key.span(), "duplicated key"));
+    }
+    let alternate = match alternate.first() {
+        Some(list) if !alternatives.is_empty() => {
+            return Err(syn::Error::new(
+                list.key.span(),
+                "cannot combine `alternate(…)` with `cfg(…)`",
+            ));
+        }
+        Some(list) => {
+            let keys = args::merge(&meta.args, list.parse_args()?);
+            Some(parse_quote!(#[template(#keys)]))
+        }
+        None => None,
+    };
+    let debug = meta.take_lists("debug");
+    let release = meta.take_lists("release");
+    if let Some(list) = debug.get(1).or_else(|| release.get(1)) {
+        return Err(syn::Error::new(list.key.span(), "duplicated key"));
+    }
+    if let Some(list) = debug.first().or_else(|| release.first()) {
+        if alternate.is_some() {
+            return Err(syn::Error::new(
+                list.key.span(),
+                "cannot combine `debug(…)`/`release(…)` with `alternate(…)`",
+            ));
+        }
+        if !alternatives.is_empty() {
+            return Err(syn::Error::new(
+                list.key.span(),
+                "cannot combine `debug(…)`/`release(…)` with `cfg(…)`",
+            ));
+        }
+        let has_source = meta.has_key("source") || meta.has_key("path");
+        if !has_source && (debug.is_empty() || release.is_empty()) {
+            return Err(syn::Error::new(
+                list.key.span(),
+                "need both `debug(…)` and `release(…)`, \
+                or a shared `source` or `path` to fall back to",
+            ));
+        }
+        let builds = [
+            (debug.first(), parse_quote!(debug_assertions)),
+            (release.first(), parse_quote!(not(debug_assertions))),
+        ];
+        for (list, predicate) in builds {
+            let keys = match list {
+                Some(list) => args::merge(&meta.args, list.parse_args()?),
+                None => meta.args.clone(),
+            };
+            alternatives.push((predicate, keys));
+        }
+    }
+
+    let mut cfg_alternatives = Vec::new();
+    let mut template_attrs = Vec::new();
+    for (predicate, keys) in alternatives {
+        template_attrs.push(parse_quote!(#[cfg_attr(#predicate, template(#keys))]));
+        cfg_alternatives.push(predicate);
+    }
+    if template_attrs.is_empty() {
+        template_attrs.push(parse_quote!(#[#meta]));
+    }
+    Ok(TemplateMeta {
+        attrs: template_attrs,
+        deprecated,
+        mime,
+        status,
+        headers,
+        size_hint,
+        cfg_alternatives,
+        alternate,
+        metadata,
+    })
+}
+
+/// `header("Name" = "value")`, with a valid HTTP header name and value
+fn parse_header(list: &args::TemplateList) -> syn::Result<(syn::LitStr, syn::LitStr)> {
+    let parser = |input: ParseStream<'_>| {
+        let name = input.parse::<syn::LitStr>()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<syn::LitStr>()?;
+        Ok((name, value))
+    };
+    let (name, value) = parser.parse2(list.tokens.clone()).map_err(|err| {
+        let mut result =
+            syn::Error::new(list.key.span(), "expected `header(\"Name\" = \"value\")`");
+        result.combine(err);
+        result
+    })?;
+    let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+    if name.value().is_empty() || !name.value().chars().all(is_token) {
+        return Err(syn::Error::new(
+            name.span(),
+            "expected an HTTP header name, i.e. letters, digits and any of !#$%&'*+-.^_`|~",
+        ));
+    }
+    let is_visible = |c: char| c == ' ' || c == '\t' || c.is_ascii_graphic();
+    if !value.value().chars().all(is_visible) {
+        return Err(syn::Error::new(
+            value.span(),
+            "an HTTP header value can only contain visible ASCII characters, spaces and tabs",
+        ));
+    }
+    Ok((name, value))
+}
+
+/// An HTTP status code in `100..=599`
+fn parse_status(value: &syn::Expr) -> syn::Result<syn::LitInt> {
+    let msg = "expected an HTTP status code in 100..=599";
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => match lit.base10_parse::<u16>() {
+            Ok(100..=599) => Ok(syn::LitInt::new(lit.base10_digits(), lit.span())),
+            _ => Err(syn::Error::new(lit.span(), msg)),
+        },
+        value => Err(syn::Error::new(value.span(), msg)),
+    }
+}
+
+/// A MIME type `type/subtype`, optionally followed by parameters `; key=value`
+pub(crate) fn parse_mime(lit: syn::LitStr) -> syn::Result<syn::LitStr> {
+    let value = lit.value();
+    let essence = value.split(';').next().unwrap_or_default().trim();
+    let is_token = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+    };
+    match essence.split_once('/') {
+        Some((ty, subtype)) if is_token(ty) && is_token(subtype) => Ok(lit),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            "expected a MIME type like `type/subtype`",
+        )),
+    }
+}
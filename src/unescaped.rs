@@ -0,0 +1,60 @@
+//! `#[enum_template(unescaped)]`: `render_unescaped()`, the same templates without HTML escaping
+
+use quote::format_ident;
+use syn::parse::ParseStream;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::Token;
+
+use crate::args::{TemplateArg, TemplateArgs};
+
+/// A copy of the helper struct `item` that renders the same template with `escape = "none"`
+///
+/// Templates that set `escape` themselves keep it.
+pub(crate) fn make_item(item: &syn::DeriveInput) -> syn::Result<syn::DeriveInput> {
+    let mut unescaped = item.clone();
+    unescaped.ident = format_ident!("{}_unescaped", item.ident);
+    for attr in &mut unescaped.attrs {
+        if attr.path.is_ident("template") {
+            let mut meta = TemplateArgs::from_attr(attr)?;
+            set_escape(&mut meta.args, attr.span());
+            *attr = parse_quote!(#[#meta]);
+        } else if attr.path.is_ident("cfg_attr") {
+            let (predicate, mut args) = attr.parse_args_with(|input: ParseStream<'_>| {
+                let predicate = input.parse::<syn::Meta>()?;
+                input.parse::<Token![,]>()?;
+                let content;
+                input.parse::<syn::Ident>()?;
+                syn::parenthesized!(content in input);
+                let args = Punctuated::<TemplateArg, Token![,]>::parse_terminated(&content)?;
+                Ok((predicate, args))
+            })?;
+            set_escape(&mut args, attr.span());
+            *attr = parse_quote!(#[cfg_attr(#predicate, template(#args))]);
+        }
+    }
+    Ok(unescaped)
+}
+
+fn set_escape(args: &mut Punctuated<TemplateArg, Token![,]>, span: proc_macro2::Span) {
+    if !args.iter().any(|arg| arg.key() == "escape") {
+        args.push(TemplateArg::NameValue {
+            key: syn::Ident::new("escape", span),
+            eq_token: Default::default(),
+            value: Box::new(parse_quote!("none")),
+        });
+    }
+}
+
+/// `fn render_unescaped(&self) -> askama::Result<String>`
+pub(crate) fn make_method(vis: &syn::Visibility, render_impl: syn::ExprMatch) -> syn::ImplItem {
+    parse_quote! {
+        /// Render the template without HTML escaping, e.g. for trusted previews
+        ///
+        /// Variants that set `escape` in their `#[template]` keep their escaping.
+        #vis fn render_unescaped(&self) -> askama::Result<::std::string::String> {
+            #render_impl
+        }
+    }
+}
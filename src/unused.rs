@@ -0,0 +1,141 @@
+//! `#[enum_template(check_unused_templates = "…")]`: find template files no variant uses
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+
+/// `#[enum_template(check_unused_templates(dir = "…", ignore("…", …)))]`
+pub(crate) struct UnusedTemplates {
+    /// The directory to check, relative to the `templates` directory
+    pub(crate) dir: syn::LitStr,
+    /// Files in `dir` that are not used by a variant on purpose, e.g. base templates
+    pub(crate) ignore: Vec<syn::LitStr>,
+}
+
+impl UnusedTemplates {
+    pub(crate) fn from_list(list: &syn::MetaList) -> syn::Result<Self> {
+        let mut dir = None;
+        let mut ignore = Vec::new();
+        for nested in &list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if path.is_ident("dir") => dir = Some(lit.clone()),
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("ignore") => {
+                    for nested in &list.nested {
+                        match nested {
+                            syn::NestedMeta::Lit(syn::Lit::Str(lit)) => ignore.push(lit.clone()),
+                            nested => {
+                                return Err(syn::Error::new_spanned(nested, "expected a string"));
+                            }
+                        }
+                    }
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected `dir = \"…\"` or `ignore(\"…\", …)`",
+                    ));
+                }
+            }
+        }
+        let dir = dir.ok_or_else(|| {
+            syn::Error::new_spanned(
+                list,
+                "expected `check_unused_templates(dir = \"…\", ignore(\"…\", …))`",
+            )
+        })?;
+        Ok(Self { dir, ignore })
+    }
+
+    /// Fail if a file in `dir` is neither used in `attrs` nor ignored
+    pub(crate) fn check<'a>(
+        &self,
+        attrs: impl IntoIterator<Item = &'a syn::Attribute>,
+    ) -> syn::Result<()> {
+        let root = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join("templates");
+        let dir = self.dir.value();
+        let dir = dir.trim_end_matches('/');
+        if !root.join(dir).is_dir() {
+            let msg = format!(
+                "the directory {:?} does not exist in the `templates` directory",
+                dir,
+            );
+            return Err(syn::Error::new(self.dir.span(), msg));
+        }
+
+        let mut files = BTreeSet::new();
+        list_files(&root, &root.join(dir), &mut files)
+            .map_err(|err| syn::Error::new(self.dir.span(), err))?;
+        for ignored in &self.ignore {
+            files.remove(&format!("{}/{}", dir, ignored.value()));
+        }
+        let mut used = BTreeSet::new();
+        for attr in attrs {
+            collect_paths(attr.tokens.clone(), &mut used);
+        }
+        let unused = files
+            .difference(&used)
+            .map(|path| format!("{:?}", path))
+            .collect::<Vec<_>>();
+        match unused.is_empty() {
+            true => Ok(()),
+            false => {
+                let msg = format!(
+                    "templates in {:?} that no variant uses: {}",
+                    dir,
+                    unused.join(", "),
+                );
+                Err(syn::Error::new(self.dir.span(), msg))
+            }
+        }
+    }
+}
+
+/// Add all files in `dir` and its subdirectories to `files`, relative to `root`
+fn list_files(root: &Path, dir: &Path, files: &mut BTreeSet<String>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let components = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>();
+            files.insert(components.join("/"));
+        }
+    }
+    Ok(())
+}
+
+/// Find all `path = "…"` in the arguments of a `#[template(…)]` or `#[cfg_attr(…)]` attribute
+fn collect_paths(tokens: TokenStream, used: &mut BTreeSet<String>) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Group(group) => collect_paths(group.stream(), used),
+            TokenTree::Ident(ident) if ident == "path" => {
+                let value = match tokens.get(index + 1..index + 3) {
+                    Some([TokenTree::Punct(eq), TokenTree::Literal(lit)])
+                        if eq.as_char() == '=' =>
+                    {
+                        lit
+                    }
+                    _ => continue,
+                };
+                if let Ok(lit) = syn::parse2::<syn::LitStr>(value.to_token_stream()) {
+                    used.insert(lit.value());
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! `#[enum_template(variant_name)]`, `#[enum_template(expose_index)]`: facts about the variant as variables of every template
+//!
+//! They are stored in the generated structs like computed values, so they share their checks.
+
+use syn::parse_quote;
+
+use crate::{unraw, Computed};
+
+/// `name: &'static str`, the name of the variant without `r#`
+pub(crate) fn variant_name(name: syn::Ident, data: &syn::DataEnum) -> Computed {
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+    let names = data.variants.iter().map(|variant| unraw(&variant.ident));
+    Computed {
+        name,
+        expr: parse_quote!(match self {
+            #(Self::#variants { .. } => #names,)*
+        }),
+        ty: Some(parse_quote!(&'static ::std::primitive::str)),
+    }
+}
+
+/// `variant_index: usize` and `variant_count: usize`, counting all variants that the derive sees
+pub(crate) fn index(path: &syn::Path, data: &syn::DataEnum) -> Vec<Computed> {
+    let span = syn::spanned::Spanned::span(path);
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+    let indices = (0..data.variants.len()).map(syn::Index::from);
+    let count = syn::Index::from(data.variants.len());
+    vec![
+        Computed {
+            name: syn::Ident::new("variant_index", span),
+            expr: parse_quote!(match self {
+                #(Self::#variants { .. } => #indices,)*
+            }),
+            ty: Some(parse_quote!(::std::primitive::usize)),
+        },
+        Computed {
+            name: syn::Ident::new("variant_count", span),
+            expr: parse_quote!(#count),
+            ty: Some(parse_quote!(::std::primitive::usize)),
+        },
+    ]
+}
+
+/// Reject a second value with the same name, e.g. `variant_name = "x"` and `compute(x = "…")`
+pub(crate) fn push(
+    computed: &mut Vec<Computed>,
+    value: Computed,
+    key: &str,
+) -> Result<(), proc_macro::TokenStream> {
+    if let Some(other) = computed.iter().find(|other| other.name == value.name) {
+        let msg = format!(
+            "`{}` needs the name `{}`, but it is used twice",
+            key, other.name
+        );
+        return Err(crate::fail_at(&value.name, &msg));
+    }
+    computed.push(value);
+    Ok(())
+}
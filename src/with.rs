@@ -0,0 +1,91 @@
+//! `#[template(with = "Type")]`: render an existing template struct, built from the variant's fields
+
+use syn::spanned::Spanned;
+
+use crate::args::{self, TemplateArgs};
+
+/// A variant that constructs and renders a template struct that is defined by the user
+pub(crate) struct With {
+    /// The template struct, e.g. `InvoiceMail` or `mail::InvoiceMail<T>`
+    pub(crate) ty: syn::Path,
+    /// `ty` with turbofishes, so it can be used in a struct expression
+    pub(crate) expr: syn::Path,
+    /// The span of the attribute's value, used for errors about the struct
+    pub(crate) span: proc_macro2::Span,
+}
+
+impl With {
+    /// Take `with = "Type"` out of the `#[template]` of a variant
+    ///
+    /// The struct has a template of its own, so the keys inherited from the enum are removed.
+    /// `own_source` is the key that gives the variant a template of its own, if any.
+    pub(crate) fn take(
+        meta: &mut TemplateArgs,
+        own_source: Option<&str>,
+        delegated: bool,
+        adapter: bool,
+        skip: bool,
+    ) -> syn::Result<Option<Self>> {
+        let lit = match meta.take_key("with") {
+            Some(value) => args::lit_str(&value)?,
+            None => return Ok(None),
+        };
+        let other = match () {
+            _ if delegated => Some("delegate"),
+            _ if adapter => Some("delegate_with"),
+            _ if skip => Some("skip"),
+            _ => None,
+        };
+        if let Some(other) = other {
+            let msg = format!("cannot combine `with` with `{}`", other);
+            return Err(syn::Error::new(lit.span(), msg));
+        }
+        if let Some(key) = own_source {
+            let msg = format!(
+                "`with` renders the template of an existing struct, so it cannot be combined \
+                with `{}`",
+                key,
+            );
+            return Err(syn::Error::new(lit.span(), msg));
+        }
+        meta.remove(crate::INHERITED_KEYS);
+        Self::parse(&lit).map(Some)
+    }
+
+    /// The variant has no other templates, because it renders the struct's template
+    pub(crate) fn check(
+        variant: &syn::Variant,
+        cfg_alternatives: &[syn::Meta],
+        alternate: bool,
+    ) -> syn::Result<()> {
+        crate::fields::reject_field_attrs(variant, "`with` variants")?;
+        if let Some(predicate) = cfg_alternatives.first() {
+            return Err(syn::Error::new(
+                predicate.span(),
+                "cannot combine `with` with `cfg(…)`, `debug(…)` or `release(…)`",
+            ));
+        }
+        match alternate {
+            true => Err(syn::Error::new(
+                variant.ident.span(),
+                "cannot combine `with` with `alternate(…)`",
+            )),
+            false => Ok(()),
+        }
+    }
+
+    fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        let ty = lit.parse::<syn::Path>()?;
+        let mut expr = ty.clone();
+        for segment in &mut expr.segments {
+            if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                args.colon2_token = Some(Default::default());
+            }
+        }
+        Ok(Self {
+            ty,
+            expr,
+            span: lit.span(),
+        })
+    }
+}
@@ -0,0 +1,31 @@
+//! Without the feature `templates`, this crate does not depend on askama,
+//! so it only compiles if no generated code is emitted.
+
+use askama_enum::EnumTemplate;
+
+#[derive(Debug, EnumTemplate)]
+#[enum_template(feature_gate = "templates", kind_enum, error_context)]
+pub enum Greeting<'a> {
+    #[template(ext = "txt", source = "Hello, {{name}}!")]
+    Hello { name: &'a str },
+    #[template(ext = "txt", source = "Bye!")]
+    Bye,
+}
+
+#[cfg(feature = "templates")]
+#[test]
+fn test() {
+    use askama::Template;
+
+    let hello = Greeting::Hello { name: "world" };
+    assert_eq!(hello.render().unwrap(), "Hello, world!");
+    assert_eq!(Greeting::Bye.to_string(), "Bye!");
+    assert_eq!(hello.kind(), GreetingKind::Hello);
+}
+
+#[cfg(not(feature = "templates"))]
+#[test]
+fn test() {
+    let _ = Greeting::Hello { name: "world" };
+    let _ = format!("{:?}", Greeting::Bye);
+}
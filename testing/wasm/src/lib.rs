@@ -0,0 +1,22 @@
+//! Build with `cargo build --target wasm32-unknown-unknown` to test the `wasm` feature
+
+use askama_enum::EnumTemplate;
+use js_sys::JsString;
+use wasm_bindgen::prelude::*;
+
+#[derive(EnumTemplate)]
+#[enum_template(wasm)]
+enum Greeting<'a> {
+    #[template(ext = "html", source = "<p>Hello, {{self.0}}!</p>")]
+    Hello(&'a str),
+    #[template(ext = "txt", source = "Goodbye!")]
+    Goodbye,
+}
+
+#[wasm_bindgen]
+pub fn greet(name: Option<String>) -> Result<JsString, JsValue> {
+    match &name {
+        Some(name) => Greeting::Hello(name).render_js(),
+        None => Greeting::Goodbye.render_js(),
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Notification<'a> {
+    #[template(
+        ext = "html",
+        source = "<p>Hello, <b>{{name}}</b>!</p>",
+        alternate(ext = "txt", source = "Hello, {{name}}!")
+    )]
+    Greeting { name: &'a str },
+    #[template(ext = "html", source = "<p>Bye</p>")]
+    Farewell,
+}
+
+#[test]
+fn test() {
+    let greeting = Notification::Greeting { name: "<World>" };
+    assert_eq!(greeting.to_string(), "<p>Hello, <b>&lt;World&gt;</b>!</p>");
+    assert_eq!(format!("{}", greeting), greeting.render().unwrap());
+    assert_eq!(format!("{:#}", greeting), "Hello, <World>!");
+
+    assert_eq!(format!("{}", Notification::Farewell), "<p>Bye</p>");
+    assert_eq!(format!("{:#}", Notification::Farewell), "<p>Bye</p>");
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "html",
+    prepend_source = "<main>",
+    append_source = "</main>{% include \"snippets/shared_footer.html\" %}"
+)]
+enum Page<'a> {
+    #[template(path = "pages/Home.html")]
+    Home { user: &'a str },
+    #[template(source = "<p>{{ text }}</p>")]
+    Text { text: &'a str },
+    #[template(source = "{{ text }}", append_source = "")]
+    Bare { text: &'a str },
+    #[template(source = "{{ text }}", ext = "txt")]
+    Plain { text: &'a str },
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Page::Home { user: "<ada>" }.render().unwrap(),
+        "<main>Home of &lt;ada&gt;</main><footer>shared</footer>",
+    );
+    assert_eq!(
+        Page::Text { text: "a & b" }.render().unwrap(),
+        "<main><p>a &amp; b</p></main><footer>shared</footer>",
+    );
+    assert_eq!(Page::Bare { text: "x" }.render().unwrap(), "<main>x");
+    assert_eq!(
+        Page::Plain { text: "a & b" }.render().unwrap(),
+        "<main>a & b</main><footer>shared</footer>",
+    );
+    assert_eq!(Page::EXTENSION, Some("html"));
+}
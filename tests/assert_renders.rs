@@ -0,0 +1,58 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::{assert_renders_eq, assert_renders_ok, EnumTemplate};
+
+#[derive(EnumTemplate)]
+enum Letter<'a> {
+    #[template(ext = "txt", source = "Dear {{ self.0 }},\nthank you.\nBye")]
+    ThankYou(&'a str),
+    #[template(ext = "txt", source = "{{ self.0.fail()? }}")]
+    Broken(Failing),
+}
+
+struct Failing;
+
+impl Failing {
+    fn fail(&self) -> askama::Result<&'static str> {
+        Err(askama::Error::Fmt(std::fmt::Error))
+    }
+}
+
+#[test]
+fn test_ok() {
+    assert_renders_eq!(Letter::ThankYou("Ann"), "Dear Ann,\nthank you.\nBye");
+    assert_renders_eq!(
+        Letter::ThankYou("Ann"),
+        String::from("Dear Ann,\nthank you.\nBye")
+    );
+    assert_eq!(
+        assert_renders_ok!(Letter::ThankYou("Bob")),
+        "Dear Bob,\nthank you.\nBye",
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "rendered output of `assert_renders::Letter<'_>` does not match \
+                           (- expected, + rendered):\n\
+                           - \"Dear Ann,\"\n\
+                           + \"Dear Bob,\"\n  \
+                           \"thank you.\"\n\
+                           - \"Bye!\"\n\
+                           + \"Bye\"\n"
+)]
+fn test_mismatch() {
+    assert_renders_eq!(Letter::ThankYou("Bob"), "Dear Ann,\nthank you.\nBye!");
+}
+
+#[test]
+#[should_panic(expected = "+ \"Bye\"\n")]
+fn test_missing_line() {
+    assert_renders_eq!(Letter::ThankYou("Ann"), "Dear Ann,\nthank you.");
+}
+
+#[test]
+#[should_panic(expected = "could not render `assert_renders::Letter<'_>`: ")]
+fn test_error() {
+    assert_renders_ok!(Letter::Broken(Failing));
+}
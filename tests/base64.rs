@@ -0,0 +1,39 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+use base64::Engine;
+
+#[derive(EnumTemplate)]
+enum Image<'a> {
+    #[template(ext = "html", source = "<img src='data:image/png;base64,{{png}}'>")]
+    Png {
+        #[template_field(base64)]
+        png: Vec<u8>,
+    },
+    #[template(ext = "txt", source = "{{self.0}} {{self.1}}")]
+    Raw(
+        #[template_field(base64(url_safe))] &'a [u8],
+        #[template_field(base64)] [u8; 3],
+    ),
+}
+
+#[test]
+fn test() {
+    let bytes = vec![0xfb, 0xff, 0x00, 0x42];
+    let html = Image::Png { png: bytes.clone() }.render().unwrap();
+    let encoded = html
+        .strip_prefix("<img src='data:image/png;base64,")
+        .and_then(|s| s.strip_suffix("'>"))
+        .unwrap();
+    assert_eq!(encoded, "+/8AQg==");
+    assert_eq!(
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap(),
+        bytes,
+    );
+
+    let text = Image::Raw(&[0xfb, 0xff], *b"abc").render().unwrap();
+    assert_eq!(text, "-_8= YWJj");
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+#[template(path = "blocks/page.html")]
+enum Page<'a> {
+    #[template(block = "settings")]
+    Settings { user: &'a str },
+    #[template(block = "profile")]
+    Profile { name: &'a str, bio: &'a str },
+    #[template(block = "bio", ext = "txt")]
+    Bio { bio: &'a str },
+    #[template(source = "{{ self.0 }}", ext = "txt")]
+    Text(&'a str),
+}
+
+#[test]
+fn test() {
+    let settings = Page::Settings { user: "<ann>" };
+    assert_eq!(
+        settings.render().unwrap(),
+        "<h1>Settings of &lt;ann&gt;</h1>",
+    );
+    assert_eq!(settings.mime_type(), "text/html; charset=utf-8");
+    let profile = Page::Profile {
+        name: "Ann",
+        bio: "hi",
+    };
+    assert_eq!(profile.render().unwrap(), "<h1>Ann</h1><p>hi</p>");
+    let bio = Page::Bio { bio: "<b>" };
+    assert_eq!(bio.render().unwrap(), "<p><b></p>");
+    assert_eq!(bio.mime_type(), "text/plain; charset=utf-8");
+    assert_eq!(Page::Text("x").render().unwrap(), "x");
+    assert_eq!(Page::EXTENSION, Some("html"));
+}
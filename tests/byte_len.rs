@@ -0,0 +1,25 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(byte_len))]
+enum MyEnum<'a> {
+    #[template(ext = "txt", source = "")]
+    Empty,
+    #[template(
+        ext = "html",
+        source = "{% for s in self.0 %}<li>{{s}}</li>{% endfor %}"
+    )]
+    List(&'a [&'a str]),
+}
+
+#[test]
+fn test() {
+    let values = [MyEnum::Empty, MyEnum::List(&["äöü", "<€>", "✓", "🦀"])];
+    for value in &values {
+        assert_eq!(value.byte_len().unwrap(), value.render().unwrap().len());
+    }
+    assert_eq!(values[0].byte_len().unwrap(), 0);
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "txt",
+    cfg(feature = "testing", source = "default with testing"),
+    cfg(not(feature = "testing"), source = "default without testing")
+)]
+enum MyEnum<'a> {
+    A,
+    #[template(
+        ext = "html",
+        cfg(not(feature = "testing"), source = "B without testing: {{self.0}}"),
+        cfg(feature = "testing", source = "B with testing: {{self.0}}")
+    )]
+    B(&'a str),
+    #[template(ext = "txt", source = "C")]
+    C,
+}
+
+#[test]
+fn test() {
+    assert_eq!(MyEnum::A.to_string(), "default with testing");
+    assert_eq!(MyEnum::B("<>").to_string(), "B with testing: &lt;&gt;");
+    assert_eq!(MyEnum::C.to_string(), "C");
+}
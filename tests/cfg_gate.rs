@@ -0,0 +1,29 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", cfg = "test")]
+enum Enabled {
+    #[template(source = "enabled")]
+    A,
+}
+
+// the enum stays usable, but it has no `Template` or `Display` impl
+#[derive(EnumTemplate, Debug, PartialEq)]
+#[template(
+    ext = "txt",
+    source = "{{ self.0 }}",
+    cfg = r#"all(feature = "testing", not(test))"#
+)]
+enum Disabled {
+    A(u32),
+}
+
+#[test]
+fn test() {
+    assert_eq!(Enabled::A.render().unwrap(), "enabled");
+    assert_eq!(Enabled::A.to_string(), "enabled");
+    assert_eq!(Disabled::A(1), Disabled::A(1));
+}
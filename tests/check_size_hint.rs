@@ -0,0 +1,27 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(check_size_hint(factor = 2))]
+enum Page<'a> {
+    #[template(ext = "txt", source = "Hello, world!")]
+    Static,
+    #[template(ext = "txt", source = "{{text}}")]
+    Dynamic { text: &'a str },
+}
+
+#[test]
+fn test() {
+    assert_eq!(Page::Static.render().unwrap(), "Hello, world!");
+    assert_eq!(Page::Dynamic { text: "short" }.render().unwrap(), "short");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "rendering Page::Dynamic produced 4096 bytes")]
+fn test_too_long() {
+    let text = "x".repeat(4096);
+    let _ = Page::Dynamic { text: &text }.render();
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+
+use std::cell::Cell;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+thread_local! {
+    static COUNTER: Cell<u32> = const { Cell::new(0) };
+}
+
+fn next_request_id() -> u32 {
+    COUNTER.with(|counter| {
+        counter.set(counter.get() + 1);
+        counter.get()
+    })
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{inner}} {{request_id}}")]
+#[enum_template(
+    compute(request_id = "next_request_id()", greeting = "\"hello\""),
+    untagged(value = "inner")
+)]
+enum Page<'a> {
+    #[template(ext = "txt", source = "{{greeting}} {{request_id}}")]
+    Unit,
+    #[template(ext = "txt", source = "{{greeting}} {{name}} {{request_id}}")]
+    Named {
+        name: &'a str,
+    },
+    Untagged(&'a str),
+}
+
+#[test]
+fn test() {
+    assert_eq!(Page::Unit.render().unwrap(), "hello 1");
+    assert_eq!(
+        Page::Named { name: "world" }.render().unwrap(),
+        "hello world 2"
+    );
+    assert_eq!(Page::Untagged("untagged").render().unwrap(), "untagged 3");
+    assert_eq!(Page::Unit.to_string(), "hello 4");
+    assert_eq!(COUNTER.with(Cell::get), 4);
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "testing")]
+
+use std::io::Read;
+
+use askama_enum::EnumTemplate;
+use axum::http::{header, StatusCode};
+
+#[derive(EnumTemplate)]
+#[enum_template(conditional)]
+enum Page<'a> {
+    #[template(ext = "html", source = "<p>{{ self.0 }}</p>")]
+    Html(&'a str),
+    #[template(ext = "html", source = "<h1>Not found</h1>", status = 404)]
+    NotFound,
+    #[template(
+        ext = "html",
+        source = "<p>cached</p>",
+        header("Cache-Control" = "max-age=60")
+    )]
+    Cached,
+}
+
+#[tokio::test]
+async fn test_axum() {
+    let page = Page::Html("Hello");
+    let etag = page.etag().unwrap();
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+    assert_ne!(etag, Page::Html("Bye").etag().unwrap());
+
+    let response = page.to_axum_conditional_response(None);
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8",
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body, "<p>Hello</p>");
+
+    let response = page.to_axum_conditional_response(Some("\"outdated\""));
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let if_none_match = format!("\"outdated\", W/{}", etag);
+    let response = page.to_axum_conditional_response(Some(&if_none_match));
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty());
+
+    // only successful responses can be "not modified"
+    let etag = Page::NotFound.etag().unwrap();
+    let response = Page::NotFound.to_axum_conditional_response(Some(&etag));
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body, "<h1>Not found</h1>");
+
+    let etag = Page::Cached.etag().unwrap();
+    let response = Page::Cached.to_axum_conditional_response(Some(&etag));
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::CACHE_CONTROL], "max-age=60");
+}
+
+#[test]
+fn test_rouille() {
+    let body = |response: rouille::Response| {
+        let (mut reader, _) = response.data.into_reader_and_size();
+        let mut body = String::new();
+        reader.read_to_string(&mut body).unwrap();
+        body
+    };
+    let etag_header = |response: &rouille::Response| {
+        response
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("ETag"))
+            .map(|(_, value)| value.to_string())
+    };
+
+    let page = Page::Html("Hello");
+    let etag = page.etag().unwrap();
+
+    let response = page.to_rouille_conditional_response(None);
+    assert_eq!(response.status_code, 200);
+    assert_eq!(etag_header(&response), Some(etag.clone()));
+    assert_eq!(body(response), "<p>Hello</p>");
+
+    let response = page.to_rouille_conditional_response(Some(&etag));
+    assert_eq!(response.status_code, 304);
+    assert_eq!(etag_header(&response), Some(etag));
+    assert_eq!(body(response), "");
+
+    let etag = Page::NotFound.etag().unwrap();
+    let response = Page::NotFound.to_rouille_conditional_response(Some(&etag));
+    assert_eq!(response.status_code, 404);
+    assert_eq!(body(response), "<h1>Not found</h1>");
+
+    let etag = Page::Cached.etag().unwrap();
+    let response = Page::Cached.to_rouille_conditional_response(Some(&etag));
+    assert_eq!(response.status_code, 304);
+    assert!(response
+        .headers
+        .iter()
+        .any(|(name, value)| name == "Cache-Control" && value == "max-age=60"));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Asset<'a> {
+    #[template(ext = "html", source = "<p>{{ text }}</p>")]
+    Page { text: &'a str },
+    #[template(
+        ext = "txt",
+        source = r#"{"text": "{{ text }}"}"#,
+        content_type = "application/json"
+    )]
+    Fragment { text: &'a str },
+    #[template(
+        ext = "html",
+        source = r#"<svg><text>{{ text }}</text></svg>"#,
+        content_type = "image/svg+xml"
+    )]
+    Badge { text: &'a str },
+}
+
+#[test]
+fn test() {
+    let page = Asset::Page { text: "hi" };
+    assert_eq!(page.content_type(), "text/html; charset=utf-8");
+    assert_eq!(page.render().unwrap(), "<p>hi</p>");
+
+    let fragment = Asset::Fragment { text: "hi" };
+    assert_eq!(fragment.content_type(), "application/json");
+    assert_eq!(fragment.mime_type(), "application/json");
+    assert_eq!(fragment.render().unwrap(), r#"{"text": "hi"}"#);
+
+    let badge = Asset::Badge { text: "ok" };
+    assert_eq!(badge.content_type(), "image/svg+xml");
+    assert_eq!(badge.render().unwrap(), "<svg><text>ok</text></svg>");
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(coverage)]
+enum Page<'a> {
+    #[template(ext = "html", source = "<h1>{{ title }}</h1>")]
+    Index { title: &'a str },
+    #[template(ext = "html", source = "<p>About</p>")]
+    About,
+    #[template(ext = "html", source = "<p>{{ self.0 }}</p>")]
+    NotFound(u16),
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Page::render_coverage(),
+        [("Index", false), ("About", false), ("NotFound", false)],
+    );
+
+    Page::Index { title: "Home" }.render().unwrap();
+    assert_eq!(
+        Page::render_coverage(),
+        [("Index", true), ("About", false), ("NotFound", false)],
+    );
+
+    let _ = Page::NotFound(404).to_string();
+    let missing = Page::render_coverage()
+        .into_iter()
+        .filter(|&(_, rendered)| !rendered)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(missing, ["About"]);
+
+    Page::About.render().unwrap();
+    assert!(Page::render_coverage()
+        .iter()
+        .all(|&(_, rendered)| rendered));
+}
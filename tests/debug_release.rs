@@ -0,0 +1,34 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "html",
+    debug(source = "<!-- default -->{{self.0}}"),
+    release(source = "{{self.0}}")
+)]
+enum MyEnum<'a> {
+    A(&'a str),
+    #[template(
+        ext = "html",
+        source = "<b>{{self.0}}</b>",
+        debug(source = "<!-- B --><b>{{self.0}}</b>")
+    )]
+    B(&'a str),
+    #[template(ext = "txt", source = "C", release(source = "c"))]
+    C,
+}
+
+#[test]
+fn test() {
+    if cfg!(debug_assertions) {
+        assert_eq!(MyEnum::A("<a>").to_string(), "<!-- default -->&lt;a&gt;");
+        assert_eq!(MyEnum::B("<b>").to_string(), "<!-- B --><b>&lt;b&gt;</b>");
+        assert_eq!(MyEnum::C.to_string(), "C");
+    } else {
+        assert_eq!(MyEnum::A("<a>").to_string(), "&lt;a&gt;");
+        assert_eq!(MyEnum::B("<b>").to_string(), "<b>&lt;b&gt;</b>");
+        assert_eq!(MyEnum::C.to_string(), "c");
+    }
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(default_variant = "Fallback", escape = "none")]
+enum Page {
+    #[template(ext = "html", source = "<p>{{ page }}: {variant}</p>", meta_default)]
+    Fallback {
+        page: &'static str,
+    },
+    About {
+        page: &'static str,
+    },
+    Contact {
+        page: &'static str,
+    },
+    #[template(ext = "txt", source = "<home>")]
+    Home,
+}
+
+#[derive(EnumTemplate)]
+#[template(default_variant = "Empty")]
+enum Status {
+    #[template(ext = "txt", source = "nothing to see")]
+    Empty,
+    Loading,
+    Failed,
+}
+
+#[test]
+fn test() {
+    let page = Page::Fallback { page: "<x>" };
+    assert_eq!(page.render().unwrap(), "<p><x>: Fallback</p>");
+    let page = Page::About { page: "about" };
+    assert_eq!(page.render().unwrap(), "<p>about: About</p>");
+    let page = Page::Contact { page: "contact" };
+    assert_eq!(page.render().unwrap(), "<p>contact: Contact</p>");
+    assert_eq!(Page::Home.render().unwrap(), "<home>");
+
+    assert_eq!(Status::Empty.render().unwrap(), "nothing to see");
+    assert_eq!(Status::Loading.render().unwrap(), "nothing to see");
+    assert_eq!(Status::Failed.render().unwrap(), "nothing to see");
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(Template)]
+#[template(ext = "html", source = "<b>{{ title }}</b>")]
+struct Card<'a> {
+    title: &'a str,
+}
+
+#[derive(EnumTemplate)]
+enum Entry<'a> {
+    #[template(delegate, ext = "html", source = "<li class=\"item\">{{ inner }}</li>")]
+    Item(Card<'a>),
+    #[template(ext = "html", source = "<li>{{ self.0 }}</li>")]
+    Text(&'a str),
+}
+
+#[test]
+fn test() {
+    let item = Entry::Item(Card {
+        title: "<Tom & Jerry>",
+    });
+    assert_eq!(
+        item.render().unwrap(),
+        "<li class=\"item\"><b>&lt;Tom &amp; Jerry&gt;</b></li>",
+    );
+    assert_eq!(Entry::Text("<b>").to_string(), "<li>&lt;b&gt;</li>",);
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(Template)]
+#[template(ext = "html", source = "<h1>{{ title }}</h1>")]
+struct HomeTemplate {
+    title: &'static str,
+}
+
+#[derive(Template)]
+#[template(ext = "txt", source = "{{ posts }} posts")]
+struct BlogTemplate {
+    posts: u32,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+#[template(delegate)]
+enum Page<'a> {
+    Home(HomeTemplate),
+    Blog(&'a BlogTemplate),
+    #[template(delegate = "body")]
+    Article {
+        id: u32,
+        body: HomeTemplate,
+    },
+    #[template(delegate = "1")]
+    Pair(u32, &'a BlogTemplate),
+    #[template(ext = "txt", source = "plain {{ self.0 }}")]
+    Plain(u32),
+}
+
+#[test]
+fn test() {
+    let blog = BlogTemplate { posts: 3 };
+    assert_eq!(
+        Page::Home(HomeTemplate { title: "<Hi>" }).render().unwrap(),
+        "<h1>&lt;Hi&gt;</h1>",
+    );
+    assert_eq!(Page::Blog(&blog).render().unwrap(), "3 posts");
+    assert_eq!(Page::Blog(&blog).to_string(), "3 posts");
+    let article = Page::Article {
+        id: 1,
+        body: HomeTemplate { title: "News" },
+    };
+    assert_eq!(article.render().unwrap(), "<h1>News</h1>");
+    let pair = Page::Pair(2, &blog);
+    assert_eq!(pair.render().unwrap(), "3 posts");
+    if let Page::Pair(n, _) = pair {
+        assert_eq!(n, 2);
+    }
+    assert_eq!(Page::Plain(4).render().unwrap(), "plain 4");
+
+    assert_eq!(Page::EXTENSION, HomeTemplate::EXTENSION);
+    assert_eq!(
+        Page::SIZE_HINT,
+        HomeTemplate::SIZE_HINT.max(BlogTemplate::SIZE_HINT)
+    );
+    assert_eq!(Page::MIME_TYPE, HomeTemplate::MIME_TYPE);
+    assert_eq!(Page::Blog(&blog).mime_type(), BlogTemplate::MIME_TYPE);
+    if let Page::Article { id, .. } = article {
+        assert_eq!(id, 1);
+    }
+}
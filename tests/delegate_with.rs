@@ -0,0 +1,66 @@
+#![cfg(feature = "testing")]
+
+use std::io;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(Template)]
+#[template(
+    ext = "html",
+    source = "<p class=\"error\">{{ kind }}: {{ message }}</p>"
+)]
+struct ErrorPage<'a> {
+    kind: String,
+    message: &'a str,
+}
+
+mod adapters {
+    use super::*;
+
+    pub(crate) fn error_page(err: &io::Error) -> ErrorPage<'static> {
+        ErrorPage {
+            kind: format!("{:?}", err.kind()),
+            message: "please try again",
+        }
+    }
+
+    pub(crate) fn custom_page<'a>(code: &u16, message: &&'a str) -> ErrorPage<'a> {
+        ErrorPage {
+            kind: code.to_string(),
+            message,
+        }
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+#[template(ext = "html")]
+enum Response<'a> {
+    #[template(delegate_with = "adapters::error_page")]
+    Error(io::Error),
+    #[template(delegate_with = "adapters::custom_page")]
+    Custom { code: u16, message: &'a str },
+    #[template(source = "<p>{{ self.0 }}</p>")]
+    Text(&'a str),
+}
+
+#[test]
+fn test() {
+    let error = Response::Error(io::Error::new(io::ErrorKind::NotFound, "gone"));
+    assert_eq!(
+        error.render().unwrap(),
+        "<p class=\"error\">NotFound: please try again</p>",
+    );
+    assert_eq!(error.to_string(), error.render().unwrap());
+    let custom = Response::Custom {
+        code: 418,
+        message: "<teapot>",
+    };
+    assert_eq!(
+        custom.render().unwrap(),
+        "<p class=\"error\">418: &lt;teapot&gt;</p>",
+    );
+    assert_eq!(custom.mime_type(), "text/html; charset=utf-8");
+    assert_eq!(Response::Text("hi").render().unwrap(), "<p>hi</p>");
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", deny_default)]
+enum Mail {
+    #[template(source = "welcome")]
+    Welcome,
+    #[template(source = "bye")]
+    Bye,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(untagged(value = "inner"))]
+#[template(ext = "txt", source = "{{ inner }}", strict)]
+enum Shown {
+    #[template(source = "nothing")]
+    Nothing,
+    Number(u32),
+}
+
+#[test]
+fn test() {
+    assert_eq!(Mail::Welcome.render().unwrap(), "welcome");
+    assert_eq!(Mail::Bye.render().unwrap(), "bye");
+    assert_eq!(Shown::Nothing.render().unwrap(), "nothing");
+    assert_eq!(Shown::Number(5).render().unwrap(), "5");
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "testing")]
+#![allow(deprecated)]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "txt",
+    source = "default",
+    deprecated = "all of it is deprecated"
+)]
+enum AllDeprecated {
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum Notification<'a> {
+    #[template(ext = "txt", source = "new {{self.0}}")]
+    New(&'a str),
+    #[template(ext = "txt", source = "old {{self.0}}", deprecated = "use New instead")]
+    Old(&'a str),
+}
+
+#[test]
+fn test() {
+    assert_eq!(AllDeprecated::A.to_string(), "default");
+    assert_eq!(Notification::New("a").to_string(), "new a");
+    assert_eq!(Notification::Old("b").to_string(), "old b");
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(dir = "mails/", path = "welcome.txt")]
+enum Email<'a> {
+    Welcome {
+        name: &'a str,
+    },
+    #[template(path = "goodbye.txt")]
+    Goodbye {
+        name: &'a str,
+    },
+    #[template(dir = "", path = "kind.txt")]
+    Root {
+        name: &'a str,
+    },
+    #[template(path = "/kind.txt")]
+    Absolute {
+        name: &'a str,
+    },
+    #[template(ext = "txt", source = "inline")]
+    Inline,
+}
+
+#[derive(EnumTemplate)]
+#[template(
+    dir = "/pages//",
+    path_pattern = "snake//{variant}.html",
+    case = "snake"
+)]
+enum Page {
+    HttpError { code: u16 },
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Email::Welcome { name: "ada" }.render().unwrap(),
+        "Welcome, ada!"
+    );
+    assert_eq!(
+        Email::Goodbye { name: "ada" }.render().unwrap(),
+        "Goodbye, ada!"
+    );
+    assert_eq!(Email::Root { name: "ada" }.render().unwrap(), "Hello, ada!");
+    assert_eq!(
+        Email::Absolute { name: "ada" }.render().unwrap(),
+        "Hello, ada!"
+    );
+    assert_eq!(Email::Inline.render().unwrap(), "inline");
+    assert_eq!(Page::HttpError { code: 404 }.render().unwrap(), "error 404");
+}
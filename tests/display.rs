@@ -0,0 +1,58 @@
+#![cfg(feature = "testing")]
+
+use std::fmt;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+struct Id(u32);
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+enum Log<'a> {
+    #[template(display = "0")]
+    Raw(&'a str),
+    #[template(display = "id")]
+    Item { id: Id, name: &'a str },
+    #[template(ext = "html", source = "<p>{{ self.0 }}</p>")]
+    Html(&'a str),
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+enum Only {
+    #[template(display = "0", ext = "json")]
+    Value(u8),
+}
+
+#[test]
+fn test() {
+    let raw = Log::Raw("<not escaped>");
+    assert_eq!(raw.render().unwrap(), "<not escaped>");
+    assert_eq!(raw.to_string(), "<not escaped>");
+
+    let item = Log::Item {
+        id: Id(7),
+        name: "unused",
+    };
+    assert_eq!(item.render().unwrap(), "#7");
+    let mut buf = String::from("> ");
+    item.render_into(&mut buf).unwrap();
+    assert_eq!(buf, "> #7");
+    if let Log::Item { name, .. } = item {
+        assert_eq!(name, "unused");
+    }
+
+    assert_eq!(Log::Html("<b>").render().unwrap(), "<p>&lt;b&gt;</p>");
+    assert_eq!(Log::EXTENSION, Some("html"));
+    assert_eq!(Log::Raw("").mime_type(), "text/plain; charset=utf-8");
+
+    assert_eq!(Only::Value(3).render().unwrap(), "3");
+    assert_eq!(Only::Value(3).mime_type(), "application/json");
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Status {
+    #[template(ext = "txt", source = "ok")]
+    Ok,
+    #[template(ext = "txt", source = "failed: {{ reason }}")]
+    Failed { reason: &'static str },
+    #[template(
+        ext = "txt",
+        source = "warning",
+        alternate(ext = "txt", source = "WARNING")
+    )]
+    Warning,
+}
+
+#[test]
+fn test_flags() {
+    assert_eq!(format!("{:>6}", Status::Ok), "    ok");
+    assert_eq!(format!("{:-<6}", Status::Ok), "ok----");
+    assert_eq!(format!("{:^8}", Status::Warning), "warning ");
+    assert_eq!(format!("{:^#9}", Status::Warning), " WARNING ");
+    assert_eq!(
+        format!(
+            "{:.10}",
+            Status::Failed {
+                reason: "disk full"
+            }
+        ),
+        "failed: di",
+    );
+    assert_eq!(
+        format!(
+            "[{:>12.6}]",
+            Status::Failed {
+                reason: "disk full"
+            }
+        ),
+        "[      failed]",
+    );
+}
+
+#[test]
+fn test_unflagged() {
+    for status in [
+        Status::Ok,
+        Status::Failed {
+            reason: "disk full",
+        },
+        Status::Warning,
+    ] {
+        assert_eq!(format!("{}", status), status.render().unwrap());
+    }
+    assert_eq!(format!("{:#}", Status::Warning), "WARNING");
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", doc)]
+enum ApiError {
+    /// E{{ code }}: the resource was not found.
+    /// Please check the URL.
+    NotFound { code: u16 },
+    /// Too many requests, retry in {{ seconds }}s.
+    #[template(doc)]
+    RateLimited { seconds: u32 },
+    ///   {{ text }}
+    #[template(doc, ext = "html")]
+    Escaped { text: &'static str },
+    #[template(ext = "txt", source = "no doc comment needed")]
+    Internal,
+    /// Slow down, {{ name }}!
+    #[template(source_from_doc, ext = "txt")]
+    Throttled { name: &'static str },
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        ApiError::NotFound { code: 404 }.render().unwrap(),
+        "E404: the resource was not found.\nPlease check the URL.",
+    );
+    assert_eq!(
+        ApiError::RateLimited { seconds: 30 }.render().unwrap(),
+        "Too many requests, retry in 30s.",
+    );
+    assert_eq!(
+        ApiError::Escaped { text: "<b>" }.render().unwrap(),
+        "  &lt;b&gt;",
+    );
+    assert_eq!(
+        ApiError::Internal.render().unwrap(),
+        "no doc comment needed"
+    );
+    assert_eq!(
+        ApiError::Throttled { name: "Ann" }.render().unwrap(),
+        "Slow down, Ann!",
+    );
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", escape = "html", source = "", enforce_escape = "html")]
+enum Page<'a> {
+    #[template(source = "<p>{{ text }}</p>")]
+    Text {
+        text: &'a str,
+    },
+    #[template(source = "{{ html }}", escape = "none", allow_unescaped)]
+    Trusted {
+        html: &'a str,
+    },
+    #[template(
+        source = "<p>{{ self.0 }}</p>",
+        alternate(ext = "txt", escape = "html")
+    )]
+    Both(&'a str),
+    Empty,
+}
+
+#[test]
+fn test() {
+    let text = Page::Text { text: "<b>" };
+    assert_eq!(text.render().unwrap(), "<p>&lt;b&gt;</p>");
+    let trusted = Page::Trusted { html: "<b>" };
+    assert_eq!(trusted.render().unwrap(), "<b>");
+    assert_eq!(Page::Both("<b>").render().unwrap(), "<p>&lt;b&gt;</p>");
+    assert_eq!(format!("{:#}", Page::Both("<b>")), "<p>&lt;b&gt;</p>");
+    assert_eq!(Page::Empty.render().unwrap(), "");
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(extension = "json", mime = "application/json", size_hint = 4096)]
+enum Pinned {
+    #[template(ext = "txt", source = "{}")]
+    Empty,
+    #[template(ext = "html", source = "[]")]
+    List,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(size_hint = 4096)]
+enum Partial {
+    #[template(ext = "html", source = "A")]
+    A,
+    #[template(ext = "txt", source = "B")]
+    B,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Pinned::EXTENSION, Some("json"));
+    assert_eq!(Pinned::MIME_TYPE, "application/json");
+    assert_eq!(Pinned::SIZE_HINT, 4096);
+    assert_eq!(Pinned::Empty.render().unwrap(), "{}");
+    assert_eq!(Pinned::List.render().unwrap(), "[]");
+
+    assert_eq!(Partial::EXTENSION, Some("html"));
+    assert_eq!(Partial::MIME_TYPE, "text/html; charset=utf-8");
+    assert_eq!(Partial::SIZE_HINT, 4096);
+    assert_eq!(Partial::A.render().unwrap(), "A");
+    assert_eq!(Partial::B.render().unwrap(), "B");
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "testing")]
+
+use std::error::Error;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+mod filters {
+    pub fn fail<T>(_: T) -> askama::Result<String> {
+        Err(askama::Error::Fmt(std::fmt::Error))
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(error_context)]
+enum Page {
+    #[template(ext = "txt", source = "ok")]
+    Ok,
+    #[template(ext = "txt", source = "{{ \"x\"|fail }}")]
+    Broken,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Page::Ok.render().unwrap(), "ok");
+
+    let err = Page::Broken.render().unwrap_err();
+    assert!(err.to_string().contains("Page::Broken"), "{}", err);
+    let inner = match &err {
+        askama::Error::Custom(inner) => inner.downcast_ref::<PageRenderError>().unwrap(),
+        err => panic!("unexpected error: {:?}", err),
+    };
+    assert_eq!(inner.variant, "Page::Broken");
+    assert!(matches!(inner.error, askama::Error::Fmt(_)));
+    assert!(inner.source().is_some());
+    assert_eq!(
+        inner.to_string(),
+        format!("could not render Page::Broken: {}", inner.error),
+    );
+
+    let mut buf = String::new();
+    let err = Page::Broken.render_into(&mut buf).unwrap_err();
+    assert!(err.to_string().contains("Page::Broken"), "{}", err);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(examples)]
+enum Page<'a> {
+    #[template(ext = "html", source = "<h1>Welcome</h1>")]
+    Index,
+    #[template(
+        ext = "html",
+        source = "<p>{{ some }} {{ more }} {{ fields }}</p>",
+        example = "Self::Article { some: \"hi\", more: 1, fields: \"x\" }"
+    )]
+    Article {
+        some: &'a str,
+        more: u32,
+        fields: &'a str,
+    },
+    #[template(ext = "html", source = "<p>{{ self.0 }}: {{ self.1 }}</p>")]
+    Error(
+        #[template_field(example = "404")] u16,
+        #[template_field(example = "\"Not Found\"")] &'a str,
+    ),
+    #[template(ext = "html", source = "<p>{{ self.0 }}</p>")]
+    Untested(&'a str),
+}
+
+#[test]
+fn test() {
+    let rendered = Page::examples()
+        .iter()
+        .map(|page| page.render().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rendered,
+        ["<h1>Welcome</h1>", "<p>hi 1 x</p>", "<p>404: Not Found</p>",],
+    );
+    assert_eq!(Page::Untested("x").to_string(), "<p>x</p>");
+}
@@ -0,0 +1,6 @@
+#![cfg(feature = "expansion-tests")]
+
+#[test]
+fn expand() {
+    macrotest::expand("tests/expand/*.rs");
+}
@@ -0,0 +1,167 @@
+use askama_enum::EnumTemplate;
+enum Fields<'a> {
+    #[template(ext = "txt", source = "{{self.0}} {{self.1}}")]
+    Tuple(u32, &'a str),
+    #[template(ext = "txt", source = "{{some}} {{fields}}")]
+    Named { some: u32, fields: &'a str },
+    #[template(ext = "txt", source = "unit")]
+    Unit,
+}
+#[allow(non_camel_case_types, non_snake_case, unused_qualifications)]
+const _: () = {
+    impl<'a> askama::Template for Fields<'a> {
+        fn render(&self) -> askama::Result<::std::string::String> {
+            match self {
+                Self::Tuple(_0, _1) => {
+                    _Fields_0_Tuple::<'a, '_>(_0, _1, ::std::marker::PhantomData)
+                        .render()
+                }
+                Self::Named { some: _0, fields: _1 } => {
+                    _Fields_1_Named::<'a, '_> {
+                        some: _0,
+                        fields: _1,
+                        _Fields_1_Named: ::std::marker::PhantomData,
+                    }
+                        .render()
+                }
+                Self::Unit => {
+                    _Fields_2_Unit::<'a, '_>(::std::marker::PhantomData).render()
+                }
+            }
+        }
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?::std::marker::Sized),
+        ) -> askama::Result<()> {
+            match self {
+                Self::Tuple(_0, _1) => {
+                    _Fields_0_Tuple::<'a, '_>(_0, _1, ::std::marker::PhantomData)
+                        .render_into(writer)
+                }
+                Self::Named { some: _0, fields: _1 } => {
+                    _Fields_1_Named::<'a, '_> {
+                        some: _0,
+                        fields: _1,
+                        _Fields_1_Named: ::std::marker::PhantomData,
+                    }
+                        .render_into(writer)
+                }
+                Self::Unit => {
+                    _Fields_2_Unit::<'a, '_>(::std::marker::PhantomData)
+                        .render_into(writer)
+                }
+            }
+        }
+        const EXTENSION: ::std::option::Option<&'static str> = <_Fields_0_Tuple as askama::Template>::EXTENSION;
+        const SIZE_HINT: ::std::primitive::usize = <_Fields_0_Tuple as askama::Template>::SIZE_HINT;
+        const MIME_TYPE: &'static ::std::primitive::str = <_Fields_0_Tuple as askama::Template>::MIME_TYPE;
+    }
+    #[template(ext = "txt", source = "{{self.0}} {{self.1}}")]
+    struct _Fields_0_Tuple<'a, '_Fields_0_Tuple>(
+        &'_Fields_0_Tuple u32,
+        &'_Fields_0_Tuple &'a str,
+        ::std::marker::PhantomData<&'_Fields_0_Tuple Fields<'a>>,
+    );
+    impl<'a, '_Fields_0_Tuple> ::askama::Template
+    for _Fields_0_Tuple<'a, '_Fields_0_Tuple> {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer
+                .write_fmt(
+                    format_args!(
+                        "{0} {1}", &::askama::MarkupDisplay::new_unsafe(& (self.0),
+                        ::askama::Text), &::askama::MarkupDisplay::new_unsafe(& (self.1),
+                        ::askama::Text),
+                    ),
+                )?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "txt",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 7;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/plain; charset=utf-8";
+    }
+    impl<'a, '_Fields_0_Tuple> ::std::fmt::Display
+    for _Fields_0_Tuple<'a, '_Fields_0_Tuple> {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    #[template(ext = "txt", source = "{{some}} {{fields}}")]
+    struct _Fields_1_Named<'a, '_Fields_1_Named> {
+        some: &'_Fields_1_Named u32,
+        fields: &'_Fields_1_Named &'a str,
+        _Fields_1_Named: ::std::marker::PhantomData<&'_Fields_1_Named Fields<'a>>,
+    }
+    impl<'a, '_Fields_1_Named> ::askama::Template
+    for _Fields_1_Named<'a, '_Fields_1_Named> {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer
+                .write_fmt(
+                    format_args!(
+                        "{0} {1}", &::askama::MarkupDisplay::new_unsafe(& (self.some),
+                        ::askama::Text,), &::askama::MarkupDisplay::new_unsafe(& (self
+                        .fields), ::askama::Text,),
+                    ),
+                )?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "txt",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 7;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/plain; charset=utf-8";
+    }
+    impl<'a, '_Fields_1_Named> ::std::fmt::Display
+    for _Fields_1_Named<'a, '_Fields_1_Named> {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    #[template(ext = "txt", source = "unit")]
+    struct _Fields_2_Unit<'a, '_Fields_2_Unit>(
+        ::std::marker::PhantomData<&'_Fields_2_Unit Fields<'a>>,
+    );
+    impl<'a, '_Fields_2_Unit> ::askama::Template
+    for _Fields_2_Unit<'a, '_Fields_2_Unit> {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer.write_str("unit")?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "txt",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 4;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/plain; charset=utf-8";
+    }
+    impl<'a, '_Fields_2_Unit> ::std::fmt::Display
+    for _Fields_2_Unit<'a, '_Fields_2_Unit> {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    impl<'a> ::std::fmt::Display for Fields<'a> {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            if f.width().is_some() || f.precision().is_some() {
+                let mut buffer = ::std::string::String::new();
+                askama::Template::render_into(self, &mut buffer)
+                    .map_err(|_| ::std::fmt::Error {})?;
+                return f.pad(&buffer);
+            }
+            askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+};
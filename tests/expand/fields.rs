@@ -0,0 +1,11 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Fields<'a> {
+    #[template(ext = "txt", source = "{{self.0}} {{self.1}}")]
+    Tuple(u32, &'a str),
+    #[template(ext = "txt", source = "{{some}} {{fields}}")]
+    Named { some: u32, fields: &'a str },
+    #[template(ext = "txt", source = "unit")]
+    Unit,
+}
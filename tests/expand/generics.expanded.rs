@@ -0,0 +1,206 @@
+use askama_enum::EnumTemplate;
+#[template(ext = "html", source = "default")]
+enum Generics<'a, T: std::fmt::Display, const N: usize>
+where
+    T: Clone,
+{
+    A,
+    #[template(ext = "html", source = "{{self.0}}")]
+    B(&'a T),
+    #[template(ext = "txt", source = "{{values.len()}}")]
+    C { values: [T; N] },
+}
+#[allow(non_camel_case_types, non_snake_case, unused_qualifications)]
+const _: () = {
+    impl<'a, T: std::fmt::Display, const N: usize> askama::Template
+    for Generics<'a, T, N>
+    where
+        T: Clone,
+    {
+        fn render(&self) -> askama::Result<::std::string::String> {
+            match self {
+                Self::A => {
+                    _Generics_0_A::<'a, '_, T, N>(::std::marker::PhantomData).render()
+                }
+                Self::B(_0) => {
+                    _Generics_1_B::<'a, '_, T, N>(_0, ::std::marker::PhantomData)
+                        .render()
+                }
+                Self::C { values: _0 } => {
+                    _Generics_2_C::<'a, '_, T, N> {
+                        values: _0,
+                        _Generics_2_C: ::std::marker::PhantomData,
+                    }
+                        .render()
+                }
+            }
+        }
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?::std::marker::Sized),
+        ) -> askama::Result<()> {
+            match self {
+                Self::A => {
+                    _Generics_0_A::<'a, '_, T, N>(::std::marker::PhantomData)
+                        .render_into(writer)
+                }
+                Self::B(_0) => {
+                    _Generics_1_B::<'a, '_, T, N>(_0, ::std::marker::PhantomData)
+                        .render_into(writer)
+                }
+                Self::C { values: _0 } => {
+                    _Generics_2_C::<'a, '_, T, N> {
+                        values: _0,
+                        _Generics_2_C: ::std::marker::PhantomData,
+                    }
+                        .render_into(writer)
+                }
+            }
+        }
+        const EXTENSION: ::std::option::Option<&'static str> = <_Generics_0_A<
+            T,
+            N,
+        > as askama::Template>::EXTENSION;
+        const SIZE_HINT: ::std::primitive::usize = <_Generics_0_A<
+            T,
+            N,
+        > as askama::Template>::SIZE_HINT;
+        const MIME_TYPE: &'static ::std::primitive::str = <_Generics_0_A<
+            T,
+            N,
+        > as askama::Template>::MIME_TYPE;
+    }
+    #[template(ext = "html", source = "default")]
+    struct _Generics_0_A<'a, '_Generics_0_A, T: std::fmt::Display, const N: usize>(
+        ::std::marker::PhantomData<&'_Generics_0_A Generics<'a, T, N>>,
+    )
+    where
+        T: Clone;
+    impl<'a, '_Generics_0_A, T: std::fmt::Display, const N: usize> ::askama::Template
+    for _Generics_0_A<'a, '_Generics_0_A, T, N>
+    where
+        T: Clone,
+    {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer.write_str("default")?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "html",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 7;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/html; charset=utf-8";
+    }
+    impl<'a, '_Generics_0_A, T: std::fmt::Display, const N: usize> ::std::fmt::Display
+    for _Generics_0_A<'a, '_Generics_0_A, T, N>
+    where
+        T: Clone,
+    {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    #[template(ext = "html", source = "{{self.0}}")]
+    struct _Generics_1_B<'a, '_Generics_1_B, T: std::fmt::Display, const N: usize>(
+        &'_Generics_1_B &'a T,
+        ::std::marker::PhantomData<&'_Generics_1_B Generics<'a, T, N>>,
+    )
+    where
+        T: Clone;
+    impl<'a, '_Generics_1_B, T: std::fmt::Display, const N: usize> ::askama::Template
+    for _Generics_1_B<'a, '_Generics_1_B, T, N>
+    where
+        T: Clone,
+    {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer
+                .write_fmt(
+                    format_args!(
+                        "{0}", &::askama::MarkupDisplay::new_unsafe(& (self.0),
+                        ::askama::Html),
+                    ),
+                )?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "html",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 3;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/html; charset=utf-8";
+    }
+    impl<'a, '_Generics_1_B, T: std::fmt::Display, const N: usize> ::std::fmt::Display
+    for _Generics_1_B<'a, '_Generics_1_B, T, N>
+    where
+        T: Clone,
+    {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    #[template(ext = "txt", source = "{{values.len()}}")]
+    struct _Generics_2_C<'a, '_Generics_2_C, T: std::fmt::Display, const N: usize>
+    where
+        T: Clone,
+    {
+        values: &'_Generics_2_C [T; N],
+        _Generics_2_C: ::std::marker::PhantomData<&'_Generics_2_C Generics<'a, T, N>>,
+    }
+    impl<'a, '_Generics_2_C, T: std::fmt::Display, const N: usize> ::askama::Template
+    for _Generics_2_C<'a, '_Generics_2_C, T, N>
+    where
+        T: Clone,
+    {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer
+                .write_fmt(
+                    format_args!(
+                        "{0}", &::askama::MarkupDisplay::new_unsafe(& (self.values
+                        .len()), ::askama::Text,),
+                    ),
+                )?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "txt",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 3;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/plain; charset=utf-8";
+    }
+    impl<'a, '_Generics_2_C, T: std::fmt::Display, const N: usize> ::std::fmt::Display
+    for _Generics_2_C<'a, '_Generics_2_C, T, N>
+    where
+        T: Clone,
+    {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    impl<'a, T: std::fmt::Display, const N: usize> ::std::fmt::Display
+    for Generics<'a, T, N>
+    where
+        T: Clone,
+    {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            if f.width().is_some() || f.precision().is_some() {
+                let mut buffer = ::std::string::String::new();
+                askama::Template::render_into(self, &mut buffer)
+                    .map_err(|_| ::std::fmt::Error {})?;
+                return f.pad(&buffer);
+            }
+            askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+};
@@ -0,0 +1,14 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = "default")]
+enum Generics<'a, T: std::fmt::Display, const N: usize>
+where
+    T: Clone,
+{
+    A,
+    #[template(ext = "html", source = "{{self.0}}")]
+    B(&'a T),
+    #[template(ext = "txt", source = "{{values.len()}}")]
+    C { values: [T; N] },
+}
@@ -0,0 +1,90 @@
+use askama_enum::EnumTemplate;
+enum Unit {
+    #[template(ext = "txt", source = "A")]
+    A,
+    #[template(ext = "txt", source = "B")]
+    B,
+}
+#[allow(non_camel_case_types, non_snake_case, unused_qualifications)]
+const _: () = {
+    impl askama::Template for Unit {
+        fn render(&self) -> askama::Result<::std::string::String> {
+            match self {
+                Self::A => _Unit_0_A::<'_>(::std::marker::PhantomData).render(),
+                Self::B => _Unit_1_B::<'_>(::std::marker::PhantomData).render(),
+            }
+        }
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?::std::marker::Sized),
+        ) -> askama::Result<()> {
+            match self {
+                Self::A => {
+                    _Unit_0_A::<'_>(::std::marker::PhantomData).render_into(writer)
+                }
+                Self::B => {
+                    _Unit_1_B::<'_>(::std::marker::PhantomData).render_into(writer)
+                }
+            }
+        }
+        const EXTENSION: ::std::option::Option<&'static str> = <_Unit_0_A as askama::Template>::EXTENSION;
+        const SIZE_HINT: ::std::primitive::usize = <_Unit_0_A as askama::Template>::SIZE_HINT;
+        const MIME_TYPE: &'static ::std::primitive::str = <_Unit_0_A as askama::Template>::MIME_TYPE;
+    }
+    #[template(ext = "txt", source = "A")]
+    struct _Unit_0_A<'_Unit_0_A>(::std::marker::PhantomData<&'_Unit_0_A Unit>);
+    impl<'_Unit_0_A> ::askama::Template for _Unit_0_A<'_Unit_0_A> {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer.write_str("A")?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "txt",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 1;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/plain; charset=utf-8";
+    }
+    impl<'_Unit_0_A> ::std::fmt::Display for _Unit_0_A<'_Unit_0_A> {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    #[template(ext = "txt", source = "B")]
+    struct _Unit_1_B<'_Unit_1_B>(::std::marker::PhantomData<&'_Unit_1_B Unit>);
+    impl<'_Unit_1_B> ::askama::Template for _Unit_1_B<'_Unit_1_B> {
+        fn render_into(
+            &self,
+            writer: &mut (impl ::std::fmt::Write + ?Sized),
+        ) -> ::askama::Result<()> {
+            writer.write_str("B")?;
+            ::askama::Result::Ok(())
+        }
+        const EXTENSION: ::std::option::Option<&'static ::std::primitive::str> = Some(
+            "txt",
+        );
+        const SIZE_HINT: ::std::primitive::usize = 1;
+        const MIME_TYPE: &'static ::std::primitive::str = "text/plain; charset=utf-8";
+    }
+    impl<'_Unit_1_B> ::std::fmt::Display for _Unit_1_B<'_Unit_1_B> {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+    impl ::std::fmt::Display for Unit {
+        #[inline]
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            if f.width().is_some() || f.precision().is_some() {
+                let mut buffer = ::std::string::String::new();
+                askama::Template::render_into(self, &mut buffer)
+                    .map_err(|_| ::std::fmt::Error {})?;
+                return f.pad(&buffer);
+            }
+            askama::Template::render_into(self, f).map_err(|_| ::std::fmt::Error {})
+        }
+    }
+};
@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Unit {
+    #[template(ext = "txt", source = "A")]
+    A,
+    #[template(ext = "txt", source = "B")]
+    B,
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ discriminant }}", expose_discriminant)]
+#[repr(u16)]
+enum Status {
+    Ok = 200,
+    Created,
+    #[template(source = "{{ discriminant }} Not Found")]
+    NotFound = 404,
+    MethodNotAllowed,
+    #[template(source = "teapot", expose_discriminant = false)]
+    Teapot = 418,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ discriminant }}")]
+enum Implicit<'a> {
+    #[template(expose_discriminant)]
+    First,
+    #[template(source = "{{ self.0 }}")]
+    Second(&'a str),
+    #[template(source = "{{ discriminant }} {{ name }}", expose_discriminant)]
+    Third { name: &'a str },
+}
+
+#[test]
+fn test() {
+    assert_eq!(Status::Ok.render().unwrap(), "200");
+    assert_eq!(Status::Created.render().unwrap(), "201");
+    assert_eq!(Status::NotFound.render().unwrap(), "404 Not Found");
+    assert_eq!(Status::MethodNotAllowed.render().unwrap(), "405");
+    assert_eq!(Status::Teapot.render().unwrap(), "teapot");
+
+    assert_eq!(Implicit::First.render().unwrap(), "0");
+    assert_eq!(Implicit::Second("x").render().unwrap(), "x");
+    assert_eq!(Implicit::Third { name: "c" }.render().unwrap(), "2 c");
+}
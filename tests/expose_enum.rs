@@ -0,0 +1,62 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", expose_enum = "outer")]
+enum Badge<'a> {
+    #[template(source = r#"<span class="{{ outer.css_class() }}">{{ name }}</span>"#)]
+    User { name: &'a str },
+    #[template(source = r#"<span class="{{ outer.css_class() }}">admin</span>"#)]
+    Admin,
+    #[template(source = "{{ self.0 }}", expose_enum = "")]
+    Text(&'a str),
+}
+
+impl Badge<'_> {
+    fn css_class(&self) -> &'static str {
+        match self {
+            Badge::User { .. } => "badge",
+            Badge::Admin => "badge badge-admin",
+            Badge::Text(_) => "",
+        }
+    }
+}
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "txt",
+    source = "{{ value }} {{ this.is_b() }}",
+    expose_enum = "this"
+)]
+#[enum_template(untagged(value = "value"))]
+enum Untagged {
+    A(u32),
+    #[template(source = "{{ value }}! {{ this.is_b() }}")]
+    B {
+        value: u32,
+    },
+}
+
+impl Untagged {
+    fn is_b(&self) -> bool {
+        matches!(self, Untagged::B { .. })
+    }
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Badge::User { name: "ada" }.render().unwrap(),
+        r#"<span class="badge">ada</span>"#,
+    );
+    assert_eq!(
+        Badge::Admin.render().unwrap(),
+        r#"<span class="badge badge-admin">admin</span>"#,
+    );
+    assert_eq!(Badge::Text("x").render().unwrap(), "x");
+
+    assert_eq!(Untagged::A(1).render().unwrap(), "1 false");
+    assert_eq!(Untagged::B { value: 2 }.render().unwrap(), "2! true");
+}
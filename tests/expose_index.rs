@@ -0,0 +1,34 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "txt",
+    source = "step {{ variant_index + 1 }} of {{ variant_count }}"
+)]
+#[enum_template(expose_index)]
+enum Step<'a> {
+    Account,
+    #[template(skip)]
+    Internal,
+    #[template(source = "{{ variant_index }}/{{ variant_count }} {{ email }}")]
+    Confirm {
+        email: &'a str,
+    },
+    #[cfg(any())]
+    Removed,
+    Done,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Step::Account.render().unwrap(), "step 1 of 4");
+    assert_eq!(Step::Internal.render().unwrap(), "");
+    assert_eq!(
+        Step::Confirm { email: "a@b.c" }.render().unwrap(),
+        "2/4 a@b.c"
+    );
+    assert_eq!(Step::Done.render().unwrap(), "step 4 of 4");
+}
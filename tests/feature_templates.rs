@@ -0,0 +1,33 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+#[template(ext = "html")]
+enum Button<'a> {
+    #[template(source = "<button>{{ label }}</button>")]
+    #[template(feature = "docsrs", source = "<a class=\"docs\">{{ label }}</a>")]
+    #[template(feature = "testing", source = "<a class=\"wl\">{{ label }}</a>")]
+    #[template(feature = "std", source = "<a class=\"std\">{{ label }}</a>")]
+    Primary { label: &'a str },
+    #[template(source = "<button>{{ self.0 }}</button>")]
+    #[template(feature = "docsrs", ext = "txt", source = "{{ self.0 }}")]
+    Secondary(&'a str),
+    #[template(source = "plain")]
+    Plain,
+}
+
+#[test]
+fn test() {
+    // `testing` comes before `std`, so its template wins
+    let primary = Button::Primary { label: "<ok>" };
+    assert_eq!(primary.render().unwrap(), "<a class=\"wl\">&lt;ok&gt;</a>");
+    assert_eq!(primary.to_string(), "<a class=\"wl\">&lt;ok&gt;</a>");
+
+    let secondary = Button::Secondary("<ok>");
+    assert_eq!(secondary.render().unwrap(), "<button>&lt;ok&gt;</button>");
+    assert_eq!(secondary.mime_type(), "text/html; charset=utf-8");
+    assert_eq!(Button::Plain.render().unwrap(), "plain");
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum CliError<'a> {
+    #[template(fmt = "user {name} not found in {tries} tries")]
+    NotFound { name: &'a str, tries: u32 },
+    #[template(fmt = "{1}:{0:>3} {{{1}}}")]
+    Position(u32, &'a str),
+    #[template(fmt = "interrupted")]
+    Interrupted,
+    #[template(fmt = "{0:.2}%")]
+    Progress(f64, &'a str),
+    #[template(ext = "html", source = "<p>{{ self.0 }}</p>")]
+    Html(&'a str),
+}
+
+#[test]
+fn test() {
+    let not_found = CliError::NotFound {
+        name: "<ada>",
+        tries: 3,
+    };
+    assert_eq!(
+        not_found.render().unwrap(),
+        "user <ada> not found in 3 tries",
+    );
+    assert_eq!(not_found.to_string(), "user <ada> not found in 3 tries");
+
+    let mut buf = String::new();
+    CliError::Position(7, "main.rs")
+        .render_into(&mut buf)
+        .unwrap();
+    assert_eq!(buf, "main.rs:  7 {main.rs}");
+
+    assert_eq!(CliError::Interrupted.render().unwrap(), "interrupted");
+    let progress = CliError::Progress(12.345, "upload");
+    assert_eq!(progress.render().unwrap(), "12.35%");
+    if let CliError::Progress(_, label) = progress {
+        assert_eq!(label, "upload");
+    }
+    assert_eq!(CliError::Html("<b>").render().unwrap(), "<p>&lt;b&gt;</p>");
+    assert_eq!(CliError::EXTENSION, Some("html"));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Page<'a> {
+    #[template(ext = "html", source = "<h1>Hello {{ name }}</h1>")]
+    #[template(format = "txt", ext = "txt", source = "Hello {{ name }}")]
+    #[template(format = "json", ext = "txt", source = r#"{"hello": "{{ name }}"}"#)]
+    Hello { name: &'a str },
+    #[template(format = "html", ext = "html", source = "<p>bye</p>")]
+    #[template(format = "txt", ext = "txt", source = "bye")]
+    Bye,
+    #[template(ext = "txt", source = "plain")]
+    Plain,
+}
+
+#[test]
+fn test() {
+    let hello = Page::Hello { name: "<ada>" };
+    assert_eq!(hello.render().unwrap(), "<h1>Hello &lt;ada&gt;</h1>");
+    assert_eq!(hello.formats(), &["txt", "json"]);
+    assert_eq!(hello.render_as("txt").unwrap().unwrap(), "Hello <ada>");
+    assert_eq!(
+        hello.render_as("json").unwrap().unwrap(),
+        r#"{"hello": "<ada>"}"#,
+    );
+    assert!(hello.render_as("html").is_none());
+
+    assert_eq!(Page::Bye.render().unwrap(), "<p>bye</p>");
+    assert_eq!(Page::Bye.formats(), &["html", "txt"]);
+    assert_eq!(Page::Bye.render_as("html").unwrap().unwrap(), "<p>bye</p>");
+    assert_eq!(Page::Bye.render_as("txt").unwrap().unwrap(), "bye");
+    assert!(Page::Bye.render_as("json").is_none());
+
+    assert_eq!(Page::Plain.render().unwrap(), "plain");
+    assert!(Page::Plain.formats().is_empty());
+    assert!(Page::Plain.render_as("txt").is_none());
+}
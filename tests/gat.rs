@@ -0,0 +1,44 @@
+#![cfg(feature = "testing")]
+
+use std::fmt::Display;
+
+use askama_enum::EnumTemplate;
+
+trait Store {
+    type Record<'a>: Display;
+}
+
+struct Strings;
+
+impl Store for Strings {
+    type Record<'a> = &'a str;
+}
+
+#[derive(EnumTemplate)]
+enum Row<T: Store> {
+    #[template(ext = "txt", source = "hit: {{self.0}}")]
+    Hit(T::Record<'static>),
+    #[template(ext = "txt", source = "miss")]
+    Miss,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "default")]
+enum BorrowedRow<'r, T: Store> {
+    #[template(ext = "txt", source = "hit: {{record}}")]
+    Hit {
+        record: <T as Store>::Record<'r>,
+    },
+    Miss,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Row::<Strings>::Hit("a").to_string(), "hit: a");
+    assert_eq!(Row::<Strings>::Miss.to_string(), "miss");
+
+    let record = String::from("b");
+    let row: BorrowedRow<'_, Strings> = BorrowedRow::Hit { record: &record };
+    assert_eq!(row.to_string(), "hit: b");
+    assert_eq!(BorrowedRow::<Strings>::Miss.to_string(), "default");
+}
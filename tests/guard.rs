@@ -0,0 +1,38 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum Cart<'a> {
+    #[template(if = "items.is_empty()", source = "your cart is empty")]
+    #[template(if = "items.len() == 1", source = "one item: {{ items[0] }}")]
+    #[template(source = "{{ items.len() }} items")]
+    Order { items: Vec<&'a str> },
+    #[template(if = "*_0 > 9", source = "many")]
+    #[template(source = "{{ self.0 }}")]
+    Count(u32),
+    #[template(source = "done")]
+    Done,
+}
+
+#[test]
+fn test() {
+    let empty = Cart::Order { items: vec![] };
+    assert_eq!(empty.render().unwrap(), "your cart is empty");
+    let one = Cart::Order { items: vec!["tea"] };
+    assert_eq!(one.render().unwrap(), "one item: tea");
+    let many = Cart::Order {
+        items: vec!["tea", "milk"],
+    };
+    assert_eq!(many.render().unwrap(), "2 items");
+
+    let mut buf = String::new();
+    empty.render_into(&mut buf).unwrap();
+    assert_eq!(buf, "your cart is empty");
+
+    assert_eq!(Cart::Count(3).render().unwrap(), "3");
+    assert_eq!(Cart::Count(10).render().unwrap(), "many");
+    assert_eq!(Cart::Done.render().unwrap(), "done");
+}
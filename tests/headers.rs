@@ -0,0 +1,35 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(rouille)]
+#[template(ext = "html", source = "<h1>Welcome</h1>")]
+enum Page {
+    Home,
+    #[template(
+        header("Cache-Control" = "no-store"),
+        header("X-Robots-Tag" = "noindex")
+    )]
+    Account,
+}
+
+#[test]
+fn test() {
+    assert!(Page::Home.headers().is_empty());
+    assert_eq!(
+        Page::Account.headers(),
+        &[("Cache-Control", "no-store"), ("X-Robots-Tag", "noindex")],
+    );
+
+    let response = Page::Account.to_rouille_response();
+    let header = |name: &str| {
+        response
+            .headers
+            .iter()
+            .find(|(other, _)| other.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_ref())
+    };
+    assert_eq!(header("Cache-Control"), Some("no-store"));
+    assert_eq!(header("X-Robots-Tag"), Some("noindex"));
+}
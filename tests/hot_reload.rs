@@ -0,0 +1,77 @@
+#![cfg(feature = "testing")]
+
+use std::fs;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Page<'a> {
+    #[template(path = "hot_reload.html")]
+    Greeting { name: &'a str },
+    #[template(ext = "txt", source = "inline")]
+    Inline,
+}
+
+#[derive(EnumTemplate)]
+enum Processed<'a> {
+    #[template(if = "name.is_empty()", ext = "txt", source = "EMPTY")]
+    #[template(path = "hot_reload/guard.txt")]
+    Guarded { name: &'a str },
+    #[template(path = "hot_reload/trim.txt", trim, postprocess = "shout")]
+    Shouted { name: &'a str },
+}
+
+fn shout(text: String) -> askama::Result<String> {
+    Ok(text.to_uppercase())
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(newlines = "crlf")]
+enum Crlf {
+    #[template(path = "hot_reload/crlf.txt")]
+    Lines,
+}
+
+#[test]
+fn test() {
+    let page = Page::Greeting { name: "<World>" };
+    assert_eq!(page.render().unwrap(), "Hello, &lt;World&gt;!");
+    assert_eq!(Page::Inline.render().unwrap(), "inline");
+
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    std::env::set_var("ASKAMA_ENUM_TEMPLATE_DIR", dir.path());
+    let path = dir.path().join("hot_reload.html");
+
+    fs::write(&path, "Goodbye, {{ self.name }}!\n").unwrap();
+    assert_eq!(page.render().unwrap(), "Goodbye, &lt;World&gt;!");
+    assert_eq!(page.to_string(), "Goodbye, &lt;World&gt;!");
+    assert_eq!(Page::Inline.render().unwrap(), "inline");
+
+    // unsupported syntax falls back to the compiled template
+    fs::write(&path, "{% if true %}Goodbye{% endif %}, {{ name }}!").unwrap();
+    assert_eq!(page.render().unwrap(), "Hello, &lt;World&gt;!");
+
+    // unknown variables, too
+    fs::write(&path, "Goodbye, {{ unknown }}!").unwrap();
+    assert_eq!(page.render().unwrap(), "Hello, &lt;World&gt;!");
+
+    // variants whose output is chosen or changed after rendering use the compiled template
+    fs::create_dir(dir.path().join("hot_reload")).unwrap();
+    for name in ["guard.txt", "trim.txt", "crlf.txt"] {
+        fs::write(dir.path().join("hot_reload").join(name), "changed\n").unwrap();
+    }
+    assert_eq!(Processed::Guarded { name: "" }.render().unwrap(), "EMPTY");
+    assert_eq!(
+        Processed::Guarded { name: "x" }.render().unwrap(),
+        "Hello x"
+    );
+    assert_eq!(Processed::Shouted { name: "x" }.render().unwrap(), "HI X");
+    assert_eq!(Processed::Shouted { name: "x" }.to_string(), "HI X");
+    assert_eq!(Crlf::Lines.render().unwrap(), "a\r\nb");
+    assert_eq!(Crlf::Lines.to_string(), "a\r\nb");
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+use icu_calendar::{Date, Iso};
+use icu_locale_core::locale;
+
+#[derive(EnumTemplate)]
+#[enum_template(icu)]
+enum Report {
+    #[template(
+        ext = "txt",
+        source = "{{ fmt.decimal(self.count) }} visits since {{ fmt.date(self.since) }}"
+    )]
+    Visits { count: u32, since: Date<Iso> },
+    #[template(ext = "txt", source = "{{ fmt.decimal(sum) }}")]
+    Total { sum: i64 },
+}
+
+#[test]
+fn test() {
+    let report = Report::Visits {
+        count: 1234567,
+        since: Date::try_new_iso(2024, 3, 5).unwrap(),
+    };
+    assert_eq!(
+        report.render_in(&locale!("en")).unwrap(),
+        "1,234,567 visits since Mar 5, 2024",
+    );
+    assert_eq!(
+        report.render_in(&locale!("de")).unwrap(),
+        "1.234.567 visits since 05.03.2024",
+    );
+    assert_eq!(
+        Report::Total { sum: -4200 }
+            .render_in(&locale!("de"))
+            .unwrap(),
+        "-4.200",
+    );
+
+    // without a locale, the formatter of the unknown locale is used
+    assert_eq!(report.render().unwrap(), report.to_string());
+    assert!(report.render().unwrap().contains("1,234,567"));
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+#[template(ext = "html", escape = "none", source = "A: {{ text }}")]
+enum Unescaped {
+    A {
+        text: &'static str,
+    },
+    #[template(source = "B: {{ text }}")]
+    B {
+        text: &'static str,
+    },
+    #[template(escape = "html", source = "C: {{ text }}")]
+    C {
+        text: &'static str,
+    },
+    #[template(path = "hot_reload.html")]
+    D {
+        name: &'static str,
+    },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{variant}", mime = "text/calendar")]
+enum Local {
+    A,
+    #[template(source = "B")]
+    B,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Unescaped::A { text: "<a>" }.render().unwrap(), "A: <a>");
+    assert_eq!(Unescaped::B { text: "<b>" }.render().unwrap(), "B: <b>");
+    assert_eq!(
+        Unescaped::C { text: "<c>" }.render().unwrap(),
+        "C: &lt;c&gt;"
+    );
+    assert_eq!(
+        Unescaped::D { name: "Dee" }.render().unwrap(),
+        "Hello, Dee!",
+    );
+    assert_eq!(
+        Unescaped::D { name: "Dee" }.mime_type(),
+        "text/html; charset=utf-8"
+    );
+
+    assert_eq!(Local::A.render().unwrap(), "A");
+    assert_eq!(Local::B.render().unwrap(), "B");
+    assert_eq!(Local::A.mime_type(), "text/calendar");
+    assert_eq!(Local::B.mime_type(), "text/plain; charset=utf-8");
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(Clone, Copy)]
+enum Theme {
+    Light,
+    Dark,
+    HighContrast,
+}
+
+mod colors {
+    pub enum Accent {
+        Red,
+        Blue,
+    }
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html")]
+enum Page<'a> {
+    #[template(
+        key = "theme",
+        source = "<body>{{ name }}</body>",
+        arms(
+            Light(source = "<body class=\"light\">{{ name }}</body>"),
+            Dark(source = "<body class=\"dark\">{{ name }}</body>"),
+            _(),
+        )
+    )]
+    Home { theme: Theme, name: &'a str },
+    #[template(
+        key = "0",
+        arms(Red(source = "red"), _(source = "other: {{ self.1 }}"))
+    )]
+    Badge(&'a colors::Accent, u32),
+    #[template(source = "plain")]
+    Plain,
+}
+
+#[test]
+fn test() {
+    let home = |theme| Page::Home { theme, name: "ada" };
+    assert_eq!(
+        home(Theme::Light).render().unwrap(),
+        "<body class=\"light\">ada</body>",
+    );
+    assert_eq!(
+        home(Theme::Dark).render().unwrap(),
+        "<body class=\"dark\">ada</body>",
+    );
+    assert_eq!(
+        home(Theme::HighContrast).render().unwrap(),
+        "<body>ada</body>",
+    );
+    let mut buf = String::new();
+    home(Theme::Dark).render_into(&mut buf).unwrap();
+    assert_eq!(buf, "<body class=\"dark\">ada</body>");
+
+    assert_eq!(
+        Page::Badge(&colors::Accent::Red, 1).render().unwrap(),
+        "red"
+    );
+    assert_eq!(
+        Page::Badge(&colors::Accent::Blue, 2).render().unwrap(),
+        "other: 2",
+    );
+    assert_eq!(Page::Plain.render().unwrap(), "plain");
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "testing")]
+
+use std::collections::HashSet;
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(kind_enum(rename_all = "kebab-case"))]
+enum Page<'a> {
+    #[template(path = "kind.txt")]
+    FromFile { name: &'a str },
+    #[template(ext = "html", source = "<p>{{self.0}}</p>")]
+    InlineHtml(&'a str),
+    #[template(ext = "txt", source = "{}", mime = "application/json")]
+    EmptyJson,
+}
+
+#[test]
+fn test() {
+    let page = Page::FromFile { name: "world" };
+    assert_eq!(page.to_string(), "Hello, world!");
+    assert_eq!(page.kind(), PageKind::FromFile);
+    assert_eq!(Page::InlineHtml("x").kind(), PageKind::InlineHtml);
+    assert_eq!(Page::EmptyJson.kind(), PageKind::EmptyJson);
+
+    assert_eq!(PageKind::FromFile.name(), "from-file");
+    assert_eq!(PageKind::InlineHtml.to_string(), "inline-html");
+    assert_eq!(PageKind::FromFile.extension(), Some("txt"));
+    assert_eq!(PageKind::InlineHtml.extension(), Some("html"));
+    assert_eq!(PageKind::FromFile.mime_type(), "text/plain; charset=utf-8");
+    assert_eq!(PageKind::EmptyJson.mime_type(), "application/json");
+    assert_eq!(PageKind::FromFile.template_path(), Some("kind.txt"));
+    assert_eq!(PageKind::InlineHtml.template_path(), None);
+
+    let kinds = [PageKind::FromFile, PageKind::FromFile, PageKind::EmptyJson];
+    assert_eq!(kinds.iter().copied().collect::<HashSet<_>>().len(), 2);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", layout = "layouts/base.html", slot = "content")]
+enum Page<'a> {
+    #[template(path = "pages/Home.html")]
+    Home { user: &'a str },
+    #[template(source = "<p>{{ text }}</p>")]
+    Text { text: &'a str },
+    #[template(source = "{{ text }}", slot = "title")]
+    Title { text: &'a str },
+    #[template(source = "{{ text }}", layout = false)]
+    Fragment { text: &'a str },
+}
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "html",
+    source = "{{ self.0 }}",
+    layout = "layouts/base.html",
+    prepend_source = "<b>",
+    append_source = "</b>"
+)]
+enum Default {
+    Bold(&'static str),
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Page::Home { user: "<ada>" }.render().unwrap(),
+        "<html><title>Site</title><body>Home of &lt;ada&gt;</body></html>",
+    );
+    assert_eq!(
+        Page::Text { text: "hi" }.render().unwrap(),
+        "<html><title>Site</title><body><p>hi</p></body></html>",
+    );
+    assert_eq!(
+        Page::Title { text: "About" }.render().unwrap(),
+        "<html><title>About</title><body></body></html>",
+    );
+    assert_eq!(Page::Fragment { text: "x" }.render().unwrap(), "x");
+    assert_eq!(
+        Default::Bold("b").render().unwrap(),
+        "<html><title>Site</title><body><b>b</b></body></html>",
+    );
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "testing")]
+#![allow(non_snake_case)]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{self.0}}")]
+enum MyEnum<'_MyEnum_0_A, '_MyEnum_0_A_1, '_MyEnum_1_B> {
+    #[template(ext = "txt", source = "{{self.0}}{{self.1}}")]
+    A(&'_MyEnum_0_A str, &'_MyEnum_0_A_1 str),
+    B(&'_MyEnum_1_B str),
+    #[template(ext = "txt", source = "{{some}}")]
+    C {
+        some: &'_MyEnum_0_A str,
+    },
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(untagged(value = "inner"))]
+#[template(ext = "txt", source = "{{inner}}")]
+enum Untagged<'_Untagged_untagged> {
+    A(&'_Untagged_untagged str),
+    B(&'_Untagged_untagged str),
+}
+
+#[test]
+fn test() {
+    assert_eq!(MyEnum::A("a", "b").to_string(), "ab");
+    assert_eq!(MyEnum::B("b").to_string(), "b");
+    assert_eq!(MyEnum::C { some: "c" }.to_string(), "c");
+    assert_eq!(Untagged::A("a").to_string(), "a");
+    assert_eq!(Untagged::B("b").to_string(), "b");
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+enum Error {
+    #[template(
+        ext = "html",
+        source = "<h1>Sorry, {{ path }} is gone ({variant})</h1>",
+        meta(team = "web")
+    )]
+    NotFound { path: &'static str },
+    #[template(like = "NotFound")]
+    Gone { path: &'static str },
+    #[template(like = "NotFound", ext = "txt")]
+    Moved { path: &'static str },
+    /// {{ self.0 }} is broken
+    #[template(ext = "txt", doc)]
+    Broken(u32),
+    /// unused
+    #[template(like = "Broken")]
+    AlsoBroken(u32),
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Error::NotFound { path: "/a" }.render().unwrap(),
+        "<h1>Sorry, /a is gone (NotFound)</h1>",
+    );
+    assert_eq!(
+        Error::Gone { path: "<b>" }.render().unwrap(),
+        "<h1>Sorry, &lt;b&gt; is gone (Gone)</h1>",
+    );
+    assert_eq!(
+        Error::Moved { path: "<c>" }.render().unwrap(),
+        "<h1>Sorry, <c> is gone (Moved)</h1>",
+    );
+    assert_eq!(
+        Error::Moved { path: "" }.mime_type(),
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(Error::NotFound { path: "" }.metadata(), [("team", "web")]);
+    assert_eq!(Error::Gone { path: "" }.metadata(), []);
+    assert_eq!(Error::Broken(1).render().unwrap(), "1 is broken");
+    assert_eq!(Error::AlsoBroken(2).render().unwrap(), "2 is broken");
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ self.0 }}")]
+#[enum_template(default_locale = "en")]
+enum Greeting<'a> {
+    #[template(locale = "en", path = "greetings/hello.en.txt")]
+    #[template(locale = "de", path = "greetings/hello.de.txt")]
+    Hello {
+        name: &'a str,
+    },
+    #[template(locale = "en", source = "Goodbye")]
+    #[template(locale = "de", source = "Tschüss")]
+    #[template(locale = "pt-BR", source = "Tchau")]
+    Goodbye,
+    Plain(&'a str),
+}
+
+#[test]
+fn test() {
+    let hello = Greeting::Hello { name: "Ada" };
+    assert_eq!(hello.render().unwrap(), "Hello, Ada!");
+    assert_eq!(hello.render_locale("en").unwrap(), "Hello, Ada!");
+    assert_eq!(hello.render_locale("de").unwrap(), "Hallo, Ada!");
+    assert_eq!(hello.render_locale("pt-BR").unwrap(), "Hello, Ada!");
+
+    let goodbye = Greeting::Goodbye;
+    assert_eq!(goodbye.render().unwrap(), "Goodbye");
+    assert_eq!(goodbye.render_locale("de").unwrap(), "Tschüss");
+    assert_eq!(goodbye.render_locale("pt-BR").unwrap(), "Tchau");
+    assert_eq!(goodbye.render_locale("fr").unwrap(), "Goodbye");
+
+    let plain = Greeting::Plain("no translation");
+    assert_eq!(plain.render_locale("de").unwrap(), "no translation");
+}
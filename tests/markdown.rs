@@ -0,0 +1,33 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Post<'a> {
+    #[template(ext = "html", source = "<article>{{ body|safe }}</article>")]
+    Article {
+        #[template_field(markdown(rename = "body"))]
+        body_md: String,
+    },
+    #[template(ext = "html", source = "{{ self.0|safe }}")]
+    Table(#[template_field(markdown(tables, footnotes))] &'a str),
+}
+
+#[test]
+fn test() {
+    let html = Post::Article {
+        body_md: "Some *emphasis* and a [link](https://example.com).".to_owned(),
+    }
+    .render()
+    .unwrap();
+    assert_eq!(
+        html,
+        "<article><p>Some <em>emphasis</em> and a \
+        <a href=\"https://example.com\">link</a>.</p>\n</article>",
+    );
+
+    let html = Post::Table("| a |\n|---|\n| 1 |\n").render().unwrap();
+    assert!(html.starts_with("<table>"), "{}", html);
+    assert!(html.contains("<td>1</td>"), "{}", html);
+}
@@ -0,0 +1,77 @@
+// The generated cache needs Rust 1.63, above the MSRV of the crate.
+#![allow(clippy::incompatible_msrv)]
+#![cfg(feature = "testing")]
+
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+thread_local! {
+    static RENDERS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn count_render() -> u32 {
+    RENDERS.with(|renders| {
+        renders.set(renders.get() + 1);
+        renders.get()
+    })
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+// Equal versions have equal hashes, but not the other way around.
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.major.hash(state);
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(memoize(capacity = 2), compute(count = "count_render()"))]
+enum Badge<'a> {
+    #[template(ext = "txt", source = "{{label}} #{{count}}")]
+    Label { label: &'a str },
+    #[template(ext = "txt", source = "empty #{{count}}")]
+    Empty {},
+    #[template(ext = "txt", source = "{{first}}|{{second}}")]
+    Pair { first: &'a str, second: &'a str },
+    #[template(ext = "txt", source = "{{version.major}}.{{version.minor}}")]
+    Release { version: Version },
+}
+
+#[test]
+fn test() {
+    assert_eq!(Badge::Label { label: "a" }.render().unwrap(), "a #1");
+    assert_eq!(Badge::Label { label: "a" }.render().unwrap(), "a #1");
+    assert_eq!(Badge::Label { label: "b" }.render().unwrap(), "b #2");
+    assert_eq!(Badge::Empty {}.render().unwrap(), "empty #3");
+    assert_eq!(Badge::Empty {}.render().unwrap(), "empty #3");
+
+    // "a" was evicted, "b" is still cached
+    assert_eq!(Badge::Label { label: "b" }.render().unwrap(), "b #2");
+    assert_eq!(Badge::Label { label: "a" }.render().unwrap(), "a #4");
+
+    // render_into() bypasses the cache
+    let mut buf = String::new();
+    Badge::Empty {}.render_into(&mut buf).unwrap();
+    assert_eq!(buf, "empty #5");
+    assert_eq!(RENDERS.with(Cell::get), 5);
+
+    // the fields are compared, not just concatenated
+    let pair = |first, second| Badge::Pair { first, second }.render().unwrap();
+    assert_eq!(pair("ab", "c"), "ab|c");
+    assert_eq!(pair("a", "bc"), "a|bc");
+
+    // the fields are compared with `Eq`, not by their hash
+    let release = |major, minor| Badge::Release {
+        version: Version { major, minor },
+    };
+    assert_eq!(release(1, 0).render().unwrap(), "1.0");
+    assert_eq!(release(1, 1).render().unwrap(), "1.1");
+}
@@ -0,0 +1,145 @@
+// The generated cache needs Rust 1.63, above the MSRV of the crate.
+#![allow(clippy::incompatible_msrv)]
+#![cfg(feature = "testing")]
+
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+thread_local! {
+    static RENDERS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn renders() -> u32 {
+    RENDERS.with(Cell::get)
+}
+
+struct Chart {
+    id: u32,
+    points: Vec<u32>,
+}
+
+impl Chart {
+    fn plot(&self) -> String {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        let points = self.points.iter().map(u32::to_string).collect::<Vec<_>>();
+        format!("chart {}: {}", self.id, points.join(","))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+// Equal versions have equal hashes, but not the other way around.
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.major.hash(state);
+    }
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum Widget<'a> {
+    #[template(source = "{{ chart.plot() }}", memoize = "chart.id")]
+    Chart { chart: &'a Chart },
+    #[template(
+        source = "{{ self.1 }}: {{ self.0.plot() }}",
+        memoize = "(_1.to_string(), _0.id)"
+    )]
+    Labeled(&'a Chart, &'a str),
+    #[template(source = "{{ self.0.plot() }}")]
+    Uncached(&'a Chart),
+    #[template(
+        source = "{{ version.major }}.{{ version.minor }}",
+        memoize = "version"
+    )]
+    Release { version: Version },
+}
+
+#[test]
+fn test() {
+    let first = Chart {
+        id: 1,
+        points: vec![1, 2],
+    };
+    let second = Chart {
+        id: 2,
+        points: vec![3],
+    };
+
+    assert_eq!(
+        Widget::Chart { chart: &first }.render().unwrap(),
+        "chart 1: 1,2",
+    );
+    assert_eq!(renders(), 1);
+    assert_eq!(
+        Widget::Chart { chart: &first }.render().unwrap(),
+        "chart 1: 1,2",
+    );
+    assert_eq!(Widget::Chart { chart: &first }.to_string(), "chart 1: 1,2");
+    let mut buf = String::new();
+    Widget::Chart { chart: &first }
+        .render_into(&mut buf)
+        .unwrap();
+    assert_eq!(buf, "chart 1: 1,2");
+    assert_eq!(renders(), 1);
+    assert_eq!(
+        Widget::Chart { chart: &second }.render().unwrap(),
+        "chart 2: 3",
+    );
+    assert_eq!(renders(), 2);
+
+    // the key is all that counts, so a changed chart with the same id is not rendered again
+    let changed = Chart {
+        id: 1,
+        points: vec![9],
+    };
+    assert_eq!(
+        Widget::Chart { chart: &changed }.render().unwrap(),
+        "chart 1: 1,2",
+    );
+    assert_eq!(renders(), 2);
+
+    assert_eq!(
+        Widget::Labeled(&first, "a").render().unwrap(),
+        "a: chart 1: 1,2",
+    );
+    assert_eq!(
+        Widget::Labeled(&first, "a").render().unwrap(),
+        "a: chart 1: 1,2",
+    );
+    assert_eq!(renders(), 3);
+    assert_eq!(
+        Widget::Labeled(&first, "b").render().unwrap(),
+        "b: chart 1: 1,2",
+    );
+    assert_eq!(renders(), 4);
+
+    Widget::Uncached(&first).render().unwrap();
+    Widget::Uncached(&first).render().unwrap();
+    assert_eq!(renders(), 6);
+
+    // the key's value is compared, not its hash
+    Widget::Labeled(&first, "xy").render().unwrap();
+    Widget::Labeled(&first, "x").render().unwrap();
+    assert_eq!(renders(), 8);
+
+    // the keys are compared with `Eq`, not by their hash
+    let release = |major, minor| Widget::Release {
+        version: Version { major, minor },
+    };
+    assert_eq!(release(1, 0).render().unwrap(), "1.0");
+    assert_eq!(release(1, 1).render().unwrap(), "1.1");
+
+    Widget::clear_memoized();
+    assert_eq!(
+        Widget::Chart { chart: &changed }.render().unwrap(),
+        "chart 1: 9",
+    );
+    assert_eq!(renders(), 9);
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "testing")]
+
+use std::fmt::Display;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+enum Fragment<T: Display> {
+    #[template(
+        ext = "json",
+        escape = "none",
+        source = "{\"value\": \"{{ self.0 }}\"}"
+    )]
+    Json(T),
+    #[template(ext = "html", source = "<p>{{ self.0 }}</p>", meta_default)]
+    Html(T),
+    #[template(ext = "txt", source = "{{ self.0 }}")]
+    Text(T),
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = "<p>default</p>")]
+enum WithDefault {
+    #[template(ext = "txt", source = "text", meta_default, mime = "text/x-custom")]
+    Text,
+    Default,
+}
+
+#[test]
+fn test() {
+    assert_eq!(<Fragment<u8> as Template>::EXTENSION, Some("html"));
+    assert_eq!(
+        <Fragment<u8> as Template>::MIME_TYPE,
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(Fragment::Json(1).render().unwrap(), "{\"value\": \"1\"}");
+    assert_eq!(Fragment::Html(2).mime_type(), "text/html; charset=utf-8");
+    assert_eq!(Fragment::Text(3).render().unwrap(), "3");
+
+    assert_eq!(WithDefault::EXTENSION, Some("txt"));
+    assert_eq!(WithDefault::MIME_TYPE, "text/x-custom");
+    assert_eq!(WithDefault::Default.render().unwrap(), "<p>default</p>");
+    assert_eq!(WithDefault::Text.render().unwrap(), "text");
+}
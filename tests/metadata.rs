@@ -0,0 +1,38 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{variant}", meta(team = "platform"))]
+enum Mail {
+    #[template(
+        ext = "txt",
+        source = "Reset your password",
+        meta(title = "Password reset", team = "auth", r#type = "security")
+    )]
+    PasswordReset,
+    Welcome,
+    #[template(ext = "txt", source = "Hi {{ name }}")]
+    Greeting {
+        name: &'static str,
+    },
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Mail::PasswordReset.metadata(),
+        [
+            ("title", "Password reset"),
+            ("team", "auth"),
+            ("type", "security"),
+        ],
+    );
+    assert_eq!(Mail::Welcome.metadata(), [("team", "platform")]);
+    assert_eq!(Mail::Greeting { name: "Ann" }.metadata(), []);
+
+    assert_eq!(Mail::PasswordReset.render().unwrap(), "Reset your password");
+    assert_eq!(Mail::Welcome.render().unwrap(), "Welcome");
+    assert_eq!(Mail::Greeting { name: "Ann" }.render().unwrap(), "Hi Ann");
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::MetricKind;
+
+mod filters {
+    pub fn fail<T>(_: T) -> askama::Result<String> {
+        Err(askama::Error::Fmt(std::fmt::Error))
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(metrics(prefix = "tpl"))]
+enum Page<'a> {
+    #[template(ext = "txt", source = "Hello, {{self.0}}!")]
+    Hello(&'a str),
+    #[template(ext = "txt", source = "{{self.0|fail}}")]
+    Broken(&'a str),
+}
+
+#[test]
+fn test() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        assert_eq!(Page::Hello("world").render().unwrap(), "Hello, world!");
+        assert_eq!(Page::Hello("world").to_string(), "Hello, world!");
+        assert!(Page::Broken("world").render().is_err());
+    });
+
+    let mut counters = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| {
+            assert_eq!(key.kind(), MetricKind::Counter);
+            let (key, labels) = key.key().clone().into_parts();
+            let labels = labels
+                .into_iter()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect::<Vec<_>>();
+            let value = match value {
+                DebugValue::Counter(value) => value,
+                value => panic!("unexpected value {:?}", value),
+            };
+            (key.as_str().to_owned(), labels, value)
+        })
+        .collect::<Vec<_>>();
+    counters.sort();
+    assert_eq!(
+        counters,
+        [
+            (
+                "tpl_render_errors_total".to_owned(),
+                vec!["enum=Page".to_owned(), "variant=Broken".to_owned()],
+                1,
+            ),
+            (
+                "tpl_render_total".to_owned(),
+                vec!["enum=Page".to_owned(), "variant=Broken".to_owned()],
+                1,
+            ),
+            (
+                "tpl_render_total".to_owned(),
+                vec!["enum=Page".to_owned(), "variant=Hello".to_owned()],
+                2,
+            ),
+        ],
+    );
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(rouille)]
+#[template(ext = "txt", source = "{{self.0}}", mime = "text/calendar")]
+enum Response<'a> {
+    Calendar(&'a str),
+    #[template(
+        ext = "txt",
+        source = r#"{"title": "{{self.0}}"}"#,
+        mime = "application/problem+json"
+    )]
+    Problem(&'a str),
+    #[template(ext = "html", source = "<p>{{self.0}}</p>")]
+    Html(&'a str),
+}
+
+#[test]
+fn test() {
+    assert_eq!(Response::Calendar("x").mime_type(), "text/calendar");
+    assert_eq!(
+        Response::Problem("x").mime_type(),
+        "application/problem+json"
+    );
+    assert_eq!(Response::Html("x").mime_type(), "text/html; charset=utf-8");
+    assert_eq!(Response::MIME_TYPE, "text/calendar");
+    assert_eq!(
+        Response::Problem("x").render().unwrap(),
+        r#"{"title": "x"}"#
+    );
+}
+
+#[test]
+fn test_rouille() {
+    let response = Response::Problem("x").to_rouille_response();
+    let content_type = response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Content-Type"))
+        .map(|(_, value)| value.as_ref());
+    assert_eq!(content_type, Some("application/problem+json"));
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", minify, source = "<p>  default  </p>")]
+enum Page<'a> {
+    #[template(source = "
+        <!DOCTYPE html>
+        <!-- the greeting -->
+        <main class=\"a  b\">
+            <h1>Hello,   <b>{{ name }}</b> !</h1>
+            <pre>
+  keep
+    me</pre>
+            <script>if (a  <  b) {}</script>
+        </main>
+    ")]
+    Greeting {
+        name: &'a str,
+    },
+    #[template(source = "<p>  raw  </p>", minify = false)]
+    Raw,
+    Default,
+    #[template(ext = "txt", source = "plain  text", minify = false)]
+    Plain,
+    #[template(source = "<p>Hello\n<b>World</b>\n  <i>!</i></p>\n<ul>\n  <li>a</li>\n</ul>")]
+    Inline,
+}
+
+#[test]
+fn test() {
+    let expected = "<!DOCTYPE html><main class=\"a  b\"><h1>Hello, <b>ada</b> !</h1>\
+        <pre>\n  keep\n    me</pre><script>if (a  <  b) {}</script></main>";
+    let greeting = Page::Greeting { name: "ada" };
+    assert_eq!(greeting.render().unwrap(), expected);
+    assert_eq!(greeting.to_string(), expected);
+    let mut buf = String::new();
+    greeting.render_into(&mut buf).unwrap();
+    assert_eq!(buf, expected);
+
+    assert_eq!(Page::Raw.render().unwrap(), "<p>  raw  </p>");
+    assert_eq!(Page::Default.render().unwrap(), "<p> default </p>");
+    assert_eq!(Page::Plain.render().unwrap(), "plain  text");
+
+    // line breaks next to inline elements are rendered as spaces
+    assert_eq!(
+        Page::Inline.render().unwrap(),
+        "<p>Hello <b>World</b> <i>!</i></p><ul><li>a</li></ul>",
+    );
+}
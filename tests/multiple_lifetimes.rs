@@ -0,0 +1,77 @@
+#![cfg(feature = "testing")]
+
+use std::fmt::Display;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "default")]
+enum View<'a, 'b: 'a, T: Display + 'b> {
+    Unit,
+    #[template(ext = "txt", source = "{{self.0}} {{self.1}} {{self.2}}")]
+    Pair(&'a str, &'b str, &'a T),
+    #[template(ext = "txt", source = "{{outer}} {{inner}}")]
+    Named {
+        outer: &'a &'b str,
+        inner: &'b T,
+    },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{__variant}}: {{value}}")]
+#[enum_template(untagged(value = "value"), check_size_hint, methods(mime_type))]
+enum Untagged<'a, 'b: 'a> {
+    First(&'a &'b str),
+    Second(&'a &'b str),
+    #[template(ext = "txt", source = "{{self.0}}")]
+    Other(&'b str),
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(byte_len))]
+enum Nested<'a, 'b, 'c>
+where
+    'c: 'b,
+    'b: 'a,
+{
+    #[template(ext = "txt", source = "{{self.0}} {{self.1}} {{self.2}}")]
+    Three(&'a str, &'b str, &'c str),
+}
+
+#[test]
+fn test() {
+    let long = String::from("long");
+    let value = 42;
+    {
+        let short = String::from("short");
+        let view = View::Pair(&short, &long, &value);
+        assert_eq!(view.render().unwrap(), "short long 42");
+        let outer = &long.as_str();
+        let view = View::Named {
+            outer,
+            inner: &value,
+        };
+        assert_eq!(view.render().unwrap(), "long 42");
+        assert_eq!(View::<'_, '_, i32>::Unit.render().unwrap(), "default");
+        assert_eq!(View::<'_, '_, i32>::EXTENSION, Some("txt"));
+        assert_eq!(View::<'_, '_, i32>::SIZE_HINT, 7);
+    }
+
+    let text = "text";
+    let text_ref = &text;
+    assert_eq!(Untagged::First(text_ref).render().unwrap(), "First: text");
+    assert_eq!(Untagged::Second(text_ref).render().unwrap(), "Second: text");
+    assert_eq!(Untagged::Other(text).render().unwrap(), "text");
+    assert_eq!(
+        Untagged::Other(text).mime_type(),
+        "text/plain; charset=utf-8"
+    );
+
+    let c = String::from("c");
+    let b = String::from("b");
+    let a = String::from("a");
+    let nested = Nested::Three(&a, &b, &c);
+    assert_eq!(nested.render().unwrap(), "a b c");
+    assert_eq!(nested.byte_len().unwrap(), 5);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(newlines = "crlf")]
+enum Mail<'a> {
+    #[template(ext = "txt", source = "Hello {{name}},\nline\r\nlast\rline\n\nBye")]
+    Text { name: &'a str },
+    #[template(ext = "html", source = "<p>\n{{name}}\r\n</p>")]
+    Html { name: &'a str },
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(newlines(style = "lf", extensions("txt", "csv")))]
+enum Export<'a> {
+    #[template(ext = "csv", escape = "none", source = "a,b\r\n{{a}},{{b}}\r\nend")]
+    Csv { a: &'a str, b: &'a str },
+    #[template(ext = "txt", source = "{{text}}")]
+    Text { text: &'a str },
+}
+
+#[test]
+fn test() {
+    let mail = Mail::Text { name: "x\ny" };
+    let expected = "Hello x\r\ny,\r\nline\r\nlast\rline\r\n\r\nBye";
+    assert_eq!(mail.render().unwrap(), expected);
+    assert_eq!(mail.to_string(), expected);
+
+    let mail = Mail::Html { name: "x" };
+    assert_eq!(mail.render().unwrap(), "<p>\nx\r\n</p>");
+
+    let export = Export::Csv { a: "1", b: "2" };
+    assert_eq!(export.render().unwrap(), "a,b\n1,2\nend");
+    assert_eq!(export.to_string(), "a,b\n1,2\nend");
+
+    let export = Export::Text { text: "\r\r\n\r" };
+    assert_eq!(export.render().unwrap(), "\r\n\r");
+    assert_eq!(export.to_string(), "\r\n\r");
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "testing")]
+
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use askama_enum::EnumTemplate;
+
+mod filters {
+    pub fn fail<T>(_: T) -> askama::Result<String> {
+        Err(askama::Error::Fmt(std::fmt::Error))
+    }
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html")]
+enum Site<'a> {
+    #[template(source = "<h1>Home</h1>", output = "index.html")]
+    Home,
+    #[template(
+        source = "<h1 id=\"{{ slug }}\">{{ title }}</h1>",
+        output = "blog/{slug}/index.html"
+    )]
+    Post { slug: &'a str, title: &'a str },
+    #[template(source = "page {{ self.0 }}", output = "blog/page/{0}.html")]
+    Archive(u32),
+    #[template(source = "{{ self.0|fail }}", output = "broken.html")]
+    Broken(&'a str),
+    #[template(source = "{{ self.0 }}", output = "{0}")]
+    Upload(&'a str),
+}
+
+#[test]
+fn test_output_path() {
+    assert_eq!(Site::Home.output_path(), PathBuf::from("index.html"));
+    let post = Site::Post {
+        slug: "hello",
+        title: "Hello",
+    };
+    assert_eq!(post.output_path(), PathBuf::from("blog/hello/index.html"));
+    assert_eq!(
+        Site::Archive(2).output_path(),
+        PathBuf::from("blog/page/2.html"),
+    );
+}
+
+#[test]
+fn test_write_to() {
+    let dir = tempfile::tempdir().unwrap();
+    Site::Home.write_to(dir.path()).unwrap();
+    let post = Site::Post {
+        slug: "hello",
+        title: "Hello",
+    };
+    post.write_to(dir.path()).unwrap();
+    assert_eq!(
+        read_to_string(dir.path().join("index.html")).unwrap(),
+        "<h1>Home</h1>",
+    );
+    assert_eq!(
+        read_to_string(dir.path().join("blog/hello/index.html")).unwrap(),
+        "<h1 id=\"hello\">Hello</h1>",
+    );
+
+    let err = Site::Broken("x").write_to(dir.path()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Other);
+    assert!(!dir.path().join("broken.html").exists());
+}
+
+#[test]
+fn test_write_to_outside() {
+    let root = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    let absolute = outside.path().join("evil.html");
+    let absolute = absolute.to_str().unwrap();
+    for slug in ["..", "../.."] {
+        let post = Site::Post { slug, title: "x" };
+        let err = post.write_to(root.path()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+    for name in ["../evil.html", absolute] {
+        let err = Site::Upload(name).write_to(root.path()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+    assert!(!outside.path().join("evil.html").exists());
+    assert_eq!(root.path().read_dir().unwrap().count(), 0);
+
+    Site::Upload("./upload.html").write_to(root.path()).unwrap();
+    assert_eq!(
+        read_to_string(root.path().join("upload.html")).unwrap(),
+        "./upload.html",
+    );
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(kind_enum)]
+enum Page<'a> {
+    #[template(path = ["themes/custom/greeting.html", "themes/default/greeting.html"])]
+    Greeting { name: &'a str },
+    #[template(path = ["themes/custom/farewell.html", "themes/default/farewell.html"])]
+    Farewell { name: &'a str },
+}
+
+#[test]
+fn test() {
+    let greeting = Page::Greeting { name: "x" };
+    assert_eq!(greeting.render().unwrap(), "custom x");
+    assert_eq!(
+        greeting.kind().template_path(),
+        Some("themes/custom/greeting.html"),
+    );
+
+    let farewell = Page::Farewell { name: "x" };
+    assert_eq!(farewell.render().unwrap(), "default x");
+    assert_eq!(
+        farewell.kind().template_path(),
+        Some("themes/default/farewell.html"),
+    );
+}
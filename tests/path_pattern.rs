@@ -0,0 +1,64 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+#[template(path_pattern = "pages/{variant}.html", escape = "none")]
+enum Page {
+    Home {
+        user: &'static str,
+    },
+    Settings,
+    Profile {
+        user: &'static str,
+    },
+    #[template(ext = "html", source = "About {{ version }}")]
+    About {
+        version: u32,
+    },
+}
+
+#[test]
+fn test() {
+    assert_eq!(Page::Home { user: "ann" }.render().unwrap(), "Home of ann");
+    assert_eq!(Page::Settings.render().unwrap(), "Settings");
+    assert_eq!(
+        Page::Profile { user: "bob" }.render().unwrap(),
+        "Profile of bob",
+    );
+    assert_eq!(Page::About { version: 2 }.render().unwrap(), "About 2");
+    assert_eq!(Page::Settings.mime_type(), "text/html; charset=utf-8");
+}
+
+#[derive(EnumTemplate)]
+#[template(path_pattern = "pages/snake/{variant}.html", case = "snake")]
+enum Snake {
+    UserProfile,
+    HTTPError { code: u16 },
+}
+
+#[derive(EnumTemplate)]
+#[template(path_pattern = "pages/kebab/{variant}.html", case = "kebab")]
+enum Kebab {
+    UserProfile,
+    #[template(path_pattern = "pages/snake/{variant}.html", case = "snake")]
+    HTTPError {
+        code: u16,
+    },
+}
+
+#[test]
+fn test_case() {
+    assert_eq!(Snake::UserProfile.render().unwrap(), "profile");
+    assert_eq!(
+        Snake::HTTPError { code: 404 }.render().unwrap(),
+        "error 404"
+    );
+    assert_eq!(Kebab::UserProfile.render().unwrap(), "profile");
+    assert_eq!(
+        Kebab::HTTPError { code: 500 }.render().unwrap(),
+        "error 500"
+    );
+}
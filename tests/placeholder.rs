@@ -0,0 +1,42 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "html",
+    source = "<span class=\"badge badge-{variant:kebab-case}\">{variant}</span>"
+)]
+enum Badge {
+    New,
+    InProgress,
+    Done,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{variant}: {{variant}} {{\"{\"}}variant}")]
+enum Escaped {
+    Named { variant: u32 },
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Badge::New.render().unwrap(),
+        "<span class=\"badge badge-new\">New</span>",
+    );
+    assert_eq!(
+        Badge::InProgress.render().unwrap(),
+        "<span class=\"badge badge-in-progress\">InProgress</span>",
+    );
+    assert_eq!(
+        Badge::Done.render().unwrap(),
+        "<span class=\"badge badge-done\">Done</span>",
+    );
+
+    assert_eq!(
+        Escaped::Named { variant: 42 }.render().unwrap(),
+        "Named: 42 {variant}",
+    );
+}
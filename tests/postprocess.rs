@@ -0,0 +1,54 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+mod typography {
+    pub fn quotes(rendered: String) -> askama::Result<String> {
+        Ok(rendered.replace("&quot;", "“"))
+    }
+}
+
+fn bust_cache(rendered: String) -> askama::Result<String> {
+    Ok(rendered.replace(".css\"", ".css?v=42\""))
+}
+
+fn fail(_: String) -> askama::Result<String> {
+    Err(askama::Error::Fmt(std::fmt::Error))
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", postprocess = "typography::quotes")]
+enum Page<'a> {
+    #[template(source = "{{ quote }}")]
+    Quote { quote: &'a str },
+    #[template(source = r#"<link href="style.css">"#, postprocess = "bust_cache")]
+    Head,
+    #[template(source = "{{ quote }}", postprocess = "")]
+    Plain { quote: &'a str },
+    #[template(source = "  {{ quote }}\n", trim)]
+    Trimmed { quote: &'a str },
+    #[template(source = "-", postprocess = "fail")]
+    Broken,
+}
+
+#[test]
+fn test() {
+    let quote = Page::Quote { quote: "\"hi\"" };
+    assert_eq!(quote.render().unwrap(), "“hi“");
+    assert_eq!(quote.to_string(), "“hi“");
+    assert_eq!(
+        Page::Head.render().unwrap(),
+        r#"<link href="style.css?v=42">"#
+    );
+    let mut buf = String::new();
+    Page::Head.render_into(&mut buf).unwrap();
+    assert_eq!(buf, r#"<link href="style.css?v=42">"#);
+    assert_eq!(
+        Page::Plain { quote: "\"hi\"" }.render().unwrap(),
+        "&quot;hi&quot;"
+    );
+    assert_eq!(Page::Trimmed { quote: "\"hi\"" }.render().unwrap(), "“hi“");
+    assert!(Page::Broken.render().is_err());
+    assert!(Page::Broken.render_into(&mut String::new()).is_err());
+}
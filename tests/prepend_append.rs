@@ -0,0 +1,54 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "Hello {{ user }}")]
+enum Greeting<'a> {
+    Plain {
+        user: &'a str,
+    },
+    #[template(prepend = "Welcome back! ")]
+    Returning {
+        user: &'a str,
+    },
+    #[template(append = ", happy birthday!")]
+    Birthday {
+        user: &'a str,
+    },
+    #[template(prepend = "[", append = "]")]
+    Both {
+        user: &'a str,
+    },
+}
+
+#[derive(EnumTemplate)]
+#[template(path = "pages/Home.html")]
+enum Page<'a> {
+    Home {
+        user: &'a str,
+    },
+    #[template(append = " (beta)")]
+    Beta {
+        user: &'a str,
+    },
+}
+
+#[test]
+fn test() {
+    let user = "ada";
+    assert_eq!(Greeting::Plain { user }.render().unwrap(), "Hello ada");
+    assert_eq!(
+        Greeting::Returning { user }.render().unwrap(),
+        "Welcome back! Hello ada",
+    );
+    assert_eq!(
+        Greeting::Birthday { user }.render().unwrap(),
+        "Hello ada, happy birthday!",
+    );
+    assert_eq!(Greeting::Both { user }.render().unwrap(), "[Hello ada]");
+
+    assert_eq!(Page::Home { user }.render().unwrap(), "Home of ada");
+    assert_eq!(Page::Beta { user }.render().unwrap(), "Home of ada (beta)");
+}
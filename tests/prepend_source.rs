@@ -0,0 +1,51 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(
+    ext = "html",
+    source = "{% call tag(\"default\") %}",
+    prepend_source = "{% macro tag(name) %}<{{ name }}>{% endmacro %}"
+)]
+enum Page<'a> {
+    Default,
+    #[template(source = "{% call tag(\"own\") %}")]
+    Own,
+    #[template(source = "plain", prepend_source = "")]
+    OptOut,
+    #[template(
+        path = "pages/Home.html",
+        prepend_source = "{% macro tag(name) %}<{{ name }}>{% endmacro %}[{% call tag(\"file\") %}] "
+    )]
+    FromFile {
+        user: &'a str,
+    },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", prepend_source = "[{variant}] ")]
+enum Log {
+    #[template(source = "started")]
+    Started,
+    #[template(source = "stopped")]
+    Stopped,
+    #[template(skip)]
+    Hidden,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Page::Default.render().unwrap(), "<default>");
+    assert_eq!(Page::Own.render().unwrap(), "<own>");
+    assert_eq!(Page::OptOut.render().unwrap(), "plain");
+    assert_eq!(
+        Page::FromFile { user: "ada" }.render().unwrap(),
+        "[<file>] Home of ada",
+    );
+
+    assert_eq!(Log::Started.render().unwrap(), "[Started] started");
+    assert_eq!(Log::Stopped.render().unwrap(), "[Stopped] stopped");
+    assert_eq!(Log::Hidden.render().unwrap(), "");
+}
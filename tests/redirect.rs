@@ -0,0 +1,36 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(rouille)]
+#[template(ext = "html", source = "<h1>Welcome</h1>")]
+enum Page {
+    Home,
+    #[template(redirect = "/auth/login")]
+    Login,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Page::Home.redirect_target(), None);
+    assert_eq!(Page::Home.render().unwrap(), "<h1>Welcome</h1>");
+
+    assert_eq!(Page::Login.redirect_target(), Some("/auth/login"));
+    let err = Page::Login.render().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Page::Login` redirects to \"/auth/login\", so it has no body",
+    );
+    assert!(Page::Login.render_into(&mut String::new()).is_err());
+
+    let response = Page::Login.to_rouille_response();
+    assert_eq!(response.status_code, 303);
+    let location = response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Location"))
+        .map(|(_, value)| value.as_ref());
+    assert_eq!(location, Some("/auth/login"));
+}
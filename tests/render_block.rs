@@ -0,0 +1,58 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Page<'a> {
+    #[template(path = "blocks/inbox.html", blocks("header", "body"))]
+    Inbox {
+        count: usize,
+        subjects: &'a [&'a str],
+    },
+    #[template(path = "blocks/inbox.html", blocks("header"))]
+    Outbox {
+        count: usize,
+        subjects: &'a [&'a str],
+    },
+    #[template(ext = "txt", source = "{{ self.0 }}")]
+    Text(&'a str),
+}
+
+#[test]
+fn test() {
+    let inbox = Page::Inbox {
+        count: 2,
+        subjects: &["hi", "<re>"],
+    };
+    assert_eq!(inbox.blocks(), &["header", "body"]);
+    assert_eq!(
+        inbox.render_block("header").unwrap(),
+        "<h1>2 new messages</h1>",
+    );
+    assert_eq!(
+        inbox.render_block("body").unwrap(),
+        "<ul><li>hi</li><li>&lt;re&gt;</li></ul>",
+    );
+    assert!(inbox
+        .render()
+        .unwrap()
+        .starts_with("<html>\n<header><h1>2 new"));
+    assert!(inbox.render_block("footer").is_err());
+
+    let outbox = Page::Outbox {
+        count: 0,
+        subjects: &[],
+    };
+    assert_eq!(outbox.blocks(), &["header"]);
+    assert_eq!(
+        outbox.render_block("header").unwrap(),
+        "<h1>0 new messages</h1>",
+    );
+    assert!(outbox.render_block("body").is_err());
+
+    let text = Page::Text("x");
+    assert!(text.blocks().is_empty());
+    let err = text.render_block("header").unwrap_err();
+    assert_eq!(err.to_string(), "the variant has no block \"header\"");
+}
@@ -0,0 +1,75 @@
+// The generated `OnceLock`s need Rust 1.70, above the MSRV of the crate.
+#![allow(clippy::incompatible_msrv)]
+#![cfg(feature = "testing")]
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(render_cow))]
+enum Page<'a> {
+    #[template(ext = "html", source = "<h1>Welcome</h1>")]
+    Index,
+    #[template(ext = "html", source = "<p>Hello, {{ name }}!</p>")]
+    Greeting { name: &'a str },
+}
+
+static MAINTENANCE: AtomicBool = AtomicBool::new(false);
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(render_cow))]
+#[template(ext = "txt")]
+enum Status {
+    #[template(if = "MAINTENANCE.load(Ordering::Relaxed)", source = "down")]
+    #[template(source = "up")]
+    Current,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(render_cow))]
+#[template(ext = "txt")]
+enum Gen<T: Display> {
+    #[template(source = "unit")]
+    Unit,
+    #[template(source = "{{ self.0 }}")]
+    Value(T),
+}
+
+#[test]
+fn test_guard() {
+    assert!(matches!(
+        Status::Current.render_cow().unwrap(),
+        Cow::Owned(_)
+    ));
+    assert_eq!(Status::Current.render_cow().unwrap(), "up");
+    MAINTENANCE.store(true, Ordering::Relaxed);
+    assert_eq!(Status::Current.render_cow().unwrap(), "down");
+}
+
+#[test]
+fn test_generic() {
+    // one static would be shared by `Gen<u8>` and `Gen<i64>`
+    let unit = Gen::<u8>::Unit.render_cow().unwrap();
+    assert!(matches!(unit, Cow::Owned(_)));
+    assert_eq!(unit, "unit");
+    assert_eq!(Gen::<i64>::Unit.render_cow().unwrap(), "unit");
+    assert_eq!(Gen::Value(7).render_cow().unwrap(), "7");
+}
+
+#[test]
+fn test() {
+    let first = Page::Index.render_cow().unwrap();
+    let second = Page::Index.render_cow().unwrap();
+    assert!(matches!(first, Cow::Borrowed(_)));
+    assert_eq!(first, "<h1>Welcome</h1>");
+    assert_eq!(first.as_ptr(), second.as_ptr());
+
+    let greeting = Page::Greeting { name: "Ann" }.render_cow().unwrap();
+    assert!(matches!(greeting, Cow::Owned(_)));
+    assert_eq!(greeting, "<p>Hello, Ann!</p>");
+    let greeting = Page::Greeting { name: "Bob" }.render_cow().unwrap();
+    assert_eq!(greeting, "<p>Hello, Bob!</p>");
+}
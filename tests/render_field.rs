@@ -0,0 +1,55 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(Template)]
+#[template(
+    ext = "html",
+    source = "<nav>{% for link in links %}<a>{{ link }}</a>{% endfor %}</nav>"
+)]
+struct Sidebar<'a> {
+    links: &'a [&'a str],
+}
+
+#[derive(Template)]
+#[template(ext = "html", source = "{{ self.fail()? }}")]
+struct Broken;
+
+impl Broken {
+    fn fail(&self) -> askama::Result<&'static str> {
+        Err(askama::Error::Fmt(std::fmt::Error))
+    }
+}
+
+#[derive(EnumTemplate)]
+enum Page<'a> {
+    #[template(
+        ext = "html",
+        source = "<h1>{{ title }}</h1>{{ sidebar|safe }}<p>{{ body }}</p>"
+    )]
+    Article {
+        title: &'a str,
+        #[template_field(render)]
+        sidebar: Sidebar<'a>,
+        body: &'a str,
+    },
+    #[template(ext = "html", source = "{{ self.0|safe }}")]
+    Broken(#[template_field(render)] Broken),
+}
+
+#[test]
+fn test() {
+    let page = Page::Article {
+        title: "<Home>",
+        sidebar: Sidebar {
+            links: &["a & b", "c"],
+        },
+        body: "Hello",
+    };
+    let expected = "<h1>&lt;Home&gt;</h1><nav><a>a &amp; b</a><a>c</a></nav><p>Hello</p>";
+    assert_eq!(page.render().unwrap(), expected);
+    assert_eq!(page.to_string(), expected);
+
+    assert!(Page::Broken(Broken).render().is_err());
+}
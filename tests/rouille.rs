@@ -0,0 +1,58 @@
+#![cfg(feature = "testing")]
+
+use std::io::Read;
+
+use askama_enum::EnumTemplate;
+
+mod filters {
+    pub fn fail<T>(_: T) -> askama::Result<String> {
+        Err(askama::Error::Fmt(std::fmt::Error))
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(rouille)]
+enum Page<'a> {
+    #[template(ext = "html", source = "<p>{{self.0}}</p>")]
+    Html(&'a str),
+    #[template(ext = "txt", source = "{{self.0}}")]
+    Text(&'a str),
+    #[template(ext = "txt", source = "{{self.0|fail}}")]
+    Broken(&'a str),
+}
+
+fn body(response: rouille::Response) -> String {
+    let (mut reader, _) = response.data.into_reader_and_size();
+    let mut body = String::new();
+    reader.read_to_string(&mut body).unwrap();
+    body
+}
+
+fn content_type(response: &rouille::Response) -> &str {
+    response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Content-Type"))
+        .map(|(_, value)| value.as_ref())
+        .unwrap()
+}
+
+#[test]
+fn test() {
+    let response = Page::Html("<hello>").to_rouille_response();
+    assert_eq!(response.status_code, 200);
+    assert_eq!(content_type(&response), "text/html; charset=utf-8");
+    assert_eq!(body(response), "<p>&lt;hello&gt;</p>");
+
+    let response = Page::Text("<hello>").to_rouille_response();
+    assert_eq!(response.status_code, 200);
+    assert_eq!(content_type(&response), "text/plain; charset=utf-8");
+    assert_eq!(body(response), "<hello>");
+
+    let response = Page::Broken("hello").to_rouille_response();
+    assert_eq!(response.status_code, 500);
+    assert_eq!(
+        body(response),
+        askama::Error::Fmt(std::fmt::Error).to_string()
+    );
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use tower::ServiceExt;
+
+#[derive(EnumTemplate)]
+#[enum_template(router)]
+#[template(ext = "html", source = "<h1>Welcome</h1>")]
+enum Pages<'a> {
+    Index,
+    #[template(ext = "txt", source = "About us")]
+    AboutUs,
+    #[template(
+        ext = "html",
+        source = "<p>Prices</p>",
+        route = "/pricing",
+        header("Cache-Control" = "max-age=3600")
+    )]
+    Prices,
+    #[template(ext = "txt", source = "Gone", status = 410)]
+    Gone,
+    #[template(redirect = "/pricing")]
+    Plans,
+    #[template(ext = "html", source = "<p>Hello, {{ name }}!</p>")]
+    Greeting {
+        name: &'a str,
+    },
+}
+
+async fn get(uri: &str) -> (StatusCode, String, String) {
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    let response = Pages::router().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_owned())
+        .unwrap_or_default();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test() {
+    let (status, content_type, body) = get("/").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/html; charset=utf-8");
+    assert_eq!(body, "<h1>Welcome</h1>");
+
+    let (status, content_type, body) = get("/about-us").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+    assert_eq!(body, "About us");
+
+    assert_eq!(get("/pricing").await.2, "<p>Prices</p>");
+    let request = Request::get("/pricing").body(Body::empty()).unwrap();
+    let response = Pages::router().oneshot(request).await.unwrap();
+    assert_eq!(response.headers()[header::CACHE_CONTROL], "max-age=3600");
+    assert_eq!(get("/gone").await.0, StatusCode::GONE);
+    let request = Request::get("/plans").body(Body::empty()).unwrap();
+    let response = Pages::router().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()[header::LOCATION], "/pricing");
+    assert_eq!(get("/prices").await.0, StatusCode::NOT_FOUND);
+    assert_eq!(get("/greeting").await.0, StatusCode::NOT_FOUND);
+
+    assert_eq!(
+        Pages::Greeting { name: "Ann" }.to_string(),
+        "<p>Hello, Ann!</p>",
+    );
+}
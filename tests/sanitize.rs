@@ -0,0 +1,55 @@
+#![cfg(feature = "testing")]
+
+use std::collections::HashSet;
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Comment<'a> {
+    #[template(ext = "html", source = "<p>{{ body|safe }}</p>")]
+    Body {
+        #[template_field(sanitize)]
+        body: String,
+    },
+    #[template(ext = "html", source = "<p>{{ self.0|safe }}</p>")]
+    Quote(#[template_field(sanitize)] &'a str),
+}
+
+fn strict() -> ammonia::Builder<'static> {
+    let mut builder = ammonia::Builder::default();
+    builder.tags(["em"].iter().copied().collect::<HashSet<_>>());
+    builder
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(sanitize(config = "strict()"))]
+enum Strict {
+    #[template(ext = "html", source = "<p>{{ body|safe }}</p>")]
+    Body {
+        #[template_field(sanitize)]
+        body: String,
+    },
+}
+
+#[test]
+fn test() {
+    let payload = "<b>bold</b> <script>alert(1)</script><em>em</em>";
+
+    let html = Comment::Body {
+        body: payload.to_owned(),
+    }
+    .render()
+    .unwrap();
+    assert_eq!(html, "<p><b>bold</b> <em>em</em></p>");
+
+    let html = Comment::Quote(payload).render().unwrap();
+    assert_eq!(html, "<p><b>bold</b> <em>em</em></p>");
+
+    let html = Strict::Body {
+        body: payload.to_owned(),
+    }
+    .render()
+    .unwrap();
+    assert_eq!(html, "<p>bold <em>em</em></p>");
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "testing")]
+#![allow(dead_code)]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{inner}}")]
+#[enum_template(untagged(value = "inner"), schema)]
+enum Page<'a> {
+    #[template(ext = "txt", source = "{{title}}: {{items.len()}}")]
+    List {
+        title: &'a str,
+        items: Vec<Option<&'a str>>,
+    },
+    #[template(ext = "txt", source = "{{self.0}} {{self.1}}")]
+    Pair(u32, bool),
+    Text(&'a str),
+    Other(&'a str),
+}
+
+#[test]
+fn test() {
+    let schema: serde_json::Value = serde_json::from_str(Page::context_schema()).unwrap();
+    assert_eq!(schema["enum"], "Page");
+    assert_eq!(
+        schema["variants"][0],
+        serde_json::json!({
+            "name": "List",
+            "fields": [
+                { "name": "title", "type": "&'a str", "origin": "field" },
+                { "name": "items", "type": "Vec<Option<&'a str>>", "origin": "field" },
+            ],
+        }),
+    );
+    assert_eq!(schema["variants"][1]["fields"][0]["name"], "0");
+    assert_eq!(schema["variants"][1]["fields"][1]["type"], "bool");
+    assert_eq!(
+        schema["variants"][2]["fields"],
+        serde_json::json!([
+            { "name": "inner", "type": "&'a str", "origin": "value" },
+            { "name": "__variant", "type": "&'static str", "origin": "variant" },
+        ]),
+    );
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Page<'a> {
+    #[template(
+        ext = "html",
+        source = "<ul>{% for item in items %}<li>{{item}}</li>{% endfor %}</ul>",
+        size_hint = 8192
+    )]
+    List { items: &'a [&'a str] },
+    #[template(ext = "html", source = "<p>{{text}}</p>")]
+    Text { text: &'a str },
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(size_hint = 100)]
+enum Overridden {
+    #[template(ext = "txt", source = "a", size_hint = 8192)]
+    A,
+}
+
+#[test]
+fn test() {
+    let list = Page::List { items: &["a", "b"] };
+    assert_eq!(list.size_hint(), 8192);
+    let rendered = list.render().unwrap();
+    assert_eq!(rendered, "<ul><li>a</li><li>b</li></ul>");
+    assert!(rendered.capacity() >= 8192);
+
+    let text = Page::Text { text: "x" };
+    assert!(text.size_hint() < 8192);
+    assert_eq!(text.render().unwrap(), "<p>x</p>");
+
+    assert_eq!(<Page<'_> as Template>::SIZE_HINT, 8192);
+    assert_eq!(<Overridden as Template>::SIZE_HINT, 100);
+    assert_eq!(Overridden::A.size_hint(), 8192);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Row<'a> {
+    #[template(skip)]
+    Pending,
+    #[template(skip, ext = "html")]
+    Redacted { secret: &'a str },
+    #[template(ext = "html", source = "<td>{{ name }}</td>")]
+    Visible { name: &'a str },
+}
+
+#[derive(EnumTemplate)]
+#[template(skip)]
+enum Internal {
+    Hidden,
+    #[template(ext = "txt", source = "shown", size_hint = 5)]
+    Shown,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Row::Pending.render().unwrap(), "");
+    assert_eq!(Row::Redacted { secret: "pw" }.render().unwrap(), "");
+    let redacted = Row::Redacted { secret: "pw" };
+    assert_eq!(redacted.to_string(), "");
+    if let Row::Redacted { secret } = redacted {
+        assert_eq!(secret, "pw");
+    }
+    assert_eq!(
+        Row::Visible { name: "<b>" }.render().unwrap(),
+        "<td>&lt;b&gt;</td>"
+    );
+    assert_eq!(Row::EXTENSION, Some("html"));
+    assert_eq!(Row::MIME_TYPE, "text/html; charset=utf-8");
+
+    assert_eq!(Internal::Hidden.render().unwrap(), "");
+    assert_eq!(Internal::Shown.render().unwrap(), "shown");
+    assert_eq!(Internal::EXTENSION, Some("txt"));
+    assert_eq!(Internal::SIZE_HINT, 5);
+    assert_eq!(Internal::Hidden.size_hint(), 0);
+}
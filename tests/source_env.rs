@@ -0,0 +1,23 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+// Cargo sets the variables of the package when the tests are compiled
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum Banner {
+    #[template(source_env = "CARGO_PKG_NAME")]
+    Name,
+    #[template(source_env = "CARGO_PKG_VERSION", prepend_source = "v")]
+    Version,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Banner::Name.render().unwrap(), env!("CARGO_PKG_NAME"));
+    assert_eq!(
+        Banner::Version.render().unwrap(),
+        concat!("v", env!("CARGO_PKG_VERSION")),
+    );
+}
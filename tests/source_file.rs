@@ -0,0 +1,39 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(source_file = "tests/source_file/greeting.html")]
+enum Message<'a> {
+    Hello {
+        name: &'a str,
+    },
+    #[template(source_file = "tests/source_file/farewell.txt")]
+    Bye {
+        name: &'a str,
+    },
+    #[template(source_file = "tests/source_file/farewell.txt", ext = "html")]
+    EscapedBye {
+        name: &'a str,
+    },
+    #[template(ext = "txt", source = "inline")]
+    Inline,
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Message::Hello { name: "<ada>" }.render().unwrap(),
+        "<p>Hello &lt;ada&gt;!</p>",
+    );
+    assert_eq!(
+        Message::Bye { name: "<ada>" }.render().unwrap(),
+        "Bye <ada>"
+    );
+    assert_eq!(
+        Message::EscapedBye { name: "<ada>" }.render().unwrap(),
+        "Bye &lt;ada&gt;",
+    );
+    assert_eq!(Message::Inline.render().unwrap(), "inline");
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = include_str!("templates/snippets/footer.html"))]
+enum Page {
+    Footer {
+        year: u16,
+        owner: &'static str,
+    },
+    #[template(source = concat!("<h1>", "{{ self.0 }}", "</h1>", '\n', 42))]
+    Title(&'static str),
+    #[template(source = concat!(include_str!("templates/snippets/footer.html"), "<!-- {{ id }} -->"))]
+    Section {
+        year: u16,
+        owner: &'static str,
+        id: u8,
+    },
+    #[template(source = std::concat!("{variant}"))]
+    Named,
+}
+
+#[test]
+fn test() {
+    let footer = Page::Footer {
+        year: 2024,
+        owner: "<ACME>",
+    };
+    assert_eq!(
+        footer.render().unwrap(),
+        "<footer>2024 &lt;ACME&gt;</footer>",
+    );
+    assert_eq!(Page::Title("Hi").render().unwrap(), "<h1>Hi</h1>\n42");
+    let section = Page::Section {
+        year: 2025,
+        owner: "me",
+        id: 7,
+    };
+    assert_eq!(
+        section.render().unwrap(),
+        "<footer>2025 me</footer><!-- 7 -->",
+    );
+    assert_eq!(Page::Named.render().unwrap(), "Named");
+}
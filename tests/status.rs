@@ -0,0 +1,33 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(rouille)]
+#[template(ext = "html", source = "<h1>{{ title }}</h1>")]
+enum ErrorPage<'a> {
+    #[template(ext = "html", source = "<h1>Not found</h1>", status = 404)]
+    NotFound,
+    #[template(ext = "html", source = "<h1>Forbidden</h1>", status = 403)]
+    Forbidden,
+    #[template(ext = "txt", source = "{{ self.0 }}", status = 500)]
+    Internal(&'a str),
+    Ok {
+        title: &'a str,
+    },
+}
+
+#[test]
+fn test() {
+    assert_eq!(ErrorPage::NotFound.status(), 404);
+    assert_eq!(ErrorPage::Forbidden.status(), 403);
+    assert_eq!(ErrorPage::Internal("oops").status(), 500);
+    assert_eq!(ErrorPage::Ok { title: "hi" }.status(), 200);
+
+    let response = ErrorPage::NotFound.to_rouille_response();
+    assert_eq!(response.status_code, 404);
+    let response = ErrorPage::Internal("oops").to_rouille_response();
+    assert_eq!(response.status_code, 500);
+    let response = ErrorPage::Ok { title: "hi" }.to_rouille_response();
+    assert_eq!(response.status_code, 200);
+}
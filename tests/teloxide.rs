@@ -0,0 +1,34 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+use teloxide::types::ParseMode;
+
+#[derive(EnumTemplate)]
+#[enum_template(teloxide)]
+enum Message<'a> {
+    #[template(ext = "html", source = "Hello, <b>{{name}}</b>!")]
+    Greeting { name: &'a str },
+    #[template(ext = "txt", source = "Bye, {{name}}!")]
+    Farewell { name: &'a str },
+    #[template(ext = "html", escape = "none", source = "<i>{{text}}</i>")]
+    Raw { text: &'a str },
+}
+
+#[test]
+fn test() {
+    let (text, mode) = Message::Greeting { name: "<you>" }
+        .to_telegram_text()
+        .unwrap();
+    assert_eq!(text, "Hello, <b>&lt;you&gt;</b>!");
+    assert!(matches!(mode, Some(ParseMode::Html)));
+
+    let (text, mode) = Message::Farewell { name: "<you>" }
+        .to_telegram_text()
+        .unwrap();
+    assert_eq!(text, "Bye, <you>!");
+    assert!(mode.is_none());
+
+    let (text, mode) = Message::Raw { text: "x" }.to_telegram_text().unwrap();
+    assert_eq!(text, "<i>x</i>");
+    assert!(mode.is_none());
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template_attr(ext = "txt", source = "default: {{self.0}}")]
+enum Message<'a> {
+    #[enum_template_attr(ext = "txt", source = "hello {{name}}")]
+    Hello {
+        name: &'a str,
+    },
+    Other(&'a str),
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Message::Hello { name: "world" }.render().unwrap(),
+        "hello world"
+    );
+    assert_eq!(Message::Other("x").render().unwrap(), "default: x");
+}
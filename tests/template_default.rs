@@ -0,0 +1,40 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template_default(
+    unit(ext = "txt", source = "-"),
+    fields(ext = "txt", source = "{{ self.0 }}")
+)]
+enum Cell {
+    Empty,
+    Number(u32),
+    Text(&'static str),
+    #[template(ext = "txt", source = "{{ self.0 }}/{{ self.1 }}")]
+    Ratio(u32, u32),
+    #[template(ext = "txt", source = "n/a")]
+    Unknown,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = "<td>{{ self.0 }}</td>")]
+#[template_default(unit(source = "<td></td>"))]
+enum Column {
+    Blank,
+    Value(&'static str),
+}
+
+#[test]
+fn test() {
+    assert_eq!(Cell::Empty.render().unwrap(), "-");
+    assert_eq!(Cell::Number(7).render().unwrap(), "7");
+    assert_eq!(Cell::Text("x").render().unwrap(), "x");
+    assert_eq!(Cell::Ratio(1, 2).render().unwrap(), "1/2");
+    assert_eq!(Cell::Unknown.render().unwrap(), "n/a");
+
+    assert_eq!(Column::Blank.render().unwrap(), "<td></td>");
+    assert_eq!(Column::Value("<b>").render().unwrap(), "<td>&lt;b&gt;</td>");
+    assert_eq!(Column::EXTENSION, Some("html"));
+}
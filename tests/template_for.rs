@@ -0,0 +1,41 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+#[template(ext = "txt", source = "default")]
+#[template_for(variants(Ok, Created, Accepted), source = "{variant}")]
+#[template_for(variants(NotFound, Gone), ext = "html", source = "<h1>{{ code }}</h1>")]
+enum Status {
+    Ok,
+    Created,
+    Accepted,
+    NotFound {
+        code: u16,
+    },
+    Gone {
+        code: u16,
+    },
+    #[template(source = "moved to {{ self.0 }}")]
+    Moved(&'static str),
+    Teapot,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Status::Ok.render().unwrap(), "Ok");
+    assert_eq!(Status::Created.render().unwrap(), "Created");
+    assert_eq!(Status::Accepted.mime_type(), "text/plain; charset=utf-8");
+    assert_eq!(
+        Status::NotFound { code: 404 }.render().unwrap(),
+        "<h1>404</h1>",
+    );
+    assert_eq!(
+        Status::Gone { code: 410 }.mime_type(),
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(Status::Moved("/new").render().unwrap(), "moved to /new");
+    assert_eq!(Status::Teapot.render().unwrap(), "default");
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use time::macros::datetime;
+use time::OffsetDateTime;
+
+#[derive(EnumTemplate)]
+enum Event {
+    #[template(ext = "txt", source = "created {{at}}")]
+    Created {
+        #[template_field(time_format = "%Y-%m-%d %H:%M")]
+        at: DateTime<Utc>,
+    },
+    #[template(ext = "txt", source = "due {{self.0}}")]
+    Due(#[template_field(time_format = "%d.%m.%Y")] NaiveDate),
+    #[template(ext = "txt", source = "deleted {{at}}")]
+    Deleted {
+        #[template_field(time_format = "[year]-[month]-[day] [hour]:[minute]")]
+        at: OffsetDateTime,
+    },
+    #[template(ext = "txt", source = "{{at}}")]
+    Broken {
+        #[template_field(time_format = "%Q")]
+        at: NaiveDate,
+    },
+}
+
+#[test]
+fn test() {
+    let at = Utc.with_ymd_and_hms(2022, 3, 14, 15, 9, 26).unwrap();
+    assert_eq!(
+        Event::Created { at }.render().unwrap(),
+        "created 2022-03-14 15:09",
+    );
+    let date = NaiveDate::from_ymd_opt(2022, 3, 14).unwrap();
+    assert_eq!(Event::Due(date).render().unwrap(), "due 14.03.2022");
+    let at = datetime!(2022-03-14 15:09:26 UTC);
+    assert_eq!(
+        Event::Deleted { at }.render().unwrap(),
+        "deleted 2022-03-14 15:09",
+    );
+}
+
+#[test]
+fn test_invalid_format() {
+    let at = NaiveDate::from_ymd_opt(2022, 3, 14).unwrap();
+    let err = Event::Broken { at }.render().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"could not format field `at` of `Event::Broken` with "%Q""#,
+    );
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(Template)]
+#[template(ext = "html", source = "\n  <b>{{ name }}</b>\n")]
+struct Bold<'a> {
+    name: &'a str,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", trim)]
+enum Mail<'a> {
+    #[template(source = "\n  Hello {{ name }},\n\n  welcome!  \n\n")]
+    Welcome { name: &'a str },
+    #[template(source = "\n  untrimmed", trim = false)]
+    Raw,
+    #[template(ext = "html", source = "  {{ text }}\t\n")]
+    Escaped { text: &'a str },
+    #[template(delegate)]
+    Delegated(Bold<'a>),
+}
+
+#[test]
+fn test() {
+    let welcome = Mail::Welcome { name: "ada" };
+    assert_eq!(welcome.render().unwrap(), "Hello ada,\n\n  welcome!");
+    assert_eq!(welcome.to_string(), "Hello ada,\n\n  welcome!");
+    assert_eq!(Mail::Raw.render().unwrap(), "\n  untrimmed");
+    assert_eq!(Mail::Raw.to_string(), "\n  untrimmed");
+
+    let escaped = Mail::Escaped { text: "<a> & <b>" };
+    assert_eq!(escaped.render().unwrap(), "&lt;a&gt; &amp; &lt;b&gt;");
+    assert_eq!(escaped.to_string(), "&lt;a&gt; &amp; &lt;b&gt;");
+
+    let delegated = Mail::Delegated(Bold { name: "x" });
+    assert_eq!(delegated.render().unwrap(), "<b>x</b>");
+    let mut buf = String::new();
+    delegated.render_into(&mut buf).unwrap();
+    assert_eq!(buf, "<b>x</b>");
+}
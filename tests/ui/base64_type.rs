@@ -0,0 +1,12 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Image {
+    #[template(ext = "txt", source = "{{data}}")]
+    Png {
+        #[template_field(base64)]
+        data: String,
+    },
+}
+
+fn main() {}
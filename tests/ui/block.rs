@@ -0,0 +1,17 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = "default")]
+enum NoPath {
+    #[template(block = "settings")]
+    Settings,
+}
+
+#[derive(EnumTemplate)]
+#[template(path = "blocks/page.html")]
+enum NoBlock {
+    #[template(block = "missing")]
+    Missing,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum NoneActive {
+    #[template(ext = "txt", cfg(any(), source = "A"), cfg(not(all()), source = "B"))]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum BothActive {
+    #[template(ext = "txt", cfg(all(), source = "A"), cfg(not(any()), source = "B"))]
+    A,
+}
+
+fn main() {}
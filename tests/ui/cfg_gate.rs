@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "", cfg = "feature = ")]
+enum Broken {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(compute(name = "String::new()"))]
+enum Page {
+    #[template(ext = "txt", source = "{{name}}")]
+    Named { name: String },
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(compute(now = "String::new()"))]
+enum Tuple {
+    #[template(ext = "txt", source = "{{self.0}}")]
+    Tuple(String),
+}
+
+fn main() {}
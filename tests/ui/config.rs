@@ -0,0 +1,12 @@
+use askama_enum::EnumTemplate;
+
+// askama 0.11 does not know `config`, so its error shows that the variant's own `#[template]`
+// inherited the key, although no variant uses the enum's `#[template]`.
+#[derive(EnumTemplate)]
+#[template(config = "other.toml", ext = "txt")]
+enum Inherited {
+    #[template(source = "A")]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", mime = "text/plain", content_type = "text/csv")]
+enum Both {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum MissingRelease {
+    #[template(ext = "txt", debug(source = "A"))]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(default_variant = "Nope")]
+enum Missing {
+    #[template(ext = "txt", source = "")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(default_variant = "B")]
+enum NoTemplate {
+    #[template(ext = "txt", source = "")]
+    A,
+    B,
+}
+
+#[derive(EnumTemplate)]
+#[template(default_variant = "NotFound")]
+enum Fields {
+    #[template(ext = "txt", source = "{{ path }}")]
+    NotFound { path: String },
+    Gone { url: String },
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(askama::Template)]
+#[template(ext = "html", source = "{{ title }}")]
+struct Card {
+    title: &'static str,
+}
+
+#[derive(EnumTemplate)]
+enum Entry {
+    #[template(delegate, ext = "html", source = "<li>{{ card }}</li>")]
+    Item(Card),
+}
+
+fn main() {}
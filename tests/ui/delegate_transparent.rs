@@ -0,0 +1,39 @@
+use askama_enum::EnumTemplate;
+
+#[derive(askama::Template)]
+#[template(ext = "html", source = "{{ title }}")]
+struct Card {
+    title: &'static str,
+}
+
+#[derive(EnumTemplate)]
+enum WithPath {
+    #[template(delegate, path = "card.html")]
+    Item(Card),
+}
+
+#[derive(EnumTemplate)]
+enum WithSource {
+    #[template(delegate = "card", ext = "html", source = "{{ card }}")]
+    Item { card: Card },
+}
+
+#[derive(EnumTemplate)]
+enum TwoFields {
+    #[template(delegate)]
+    Item(u32, Card),
+}
+
+#[derive(EnumTemplate)]
+enum UnknownField {
+    #[template(delegate = "cart")]
+    Item { card: Card },
+}
+
+#[derive(EnumTemplate)]
+enum Skipped {
+    #[template(delegate, skip)]
+    Item(Card),
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use askama_enum::EnumTemplate;
+
+fn not_a_template(value: &u32) -> String {
+    value.to_string()
+}
+
+#[derive(EnumTemplate)]
+enum Response {
+    #[template(ext = "txt", delegate_with = "not_a_template")]
+    Number(u32),
+}
+
+#[derive(EnumTemplate)]
+enum NoExt {
+    #[template(delegate_with = "not_a_template")]
+    Number(u32),
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "default", deny_default)]
+enum Mail {
+    #[template(source = "welcome")]
+    Welcome,
+    Reminder,
+    Bye { name: String },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "default", strict)]
+enum Page {
+    #[template(source = "home")]
+    Home,
+    About,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![deny(deprecated)]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Notification {
+    #[template(ext = "txt", source = "new")]
+    New,
+    #[template(ext = "txt", source = "old", deprecated = "use New instead")]
+    Old,
+}
+
+fn main() {}
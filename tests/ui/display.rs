@@ -0,0 +1,27 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Missing {
+    #[template(display = "name")]
+    A(u32),
+}
+
+#[derive(EnumTemplate)]
+enum WithSource {
+    #[template(display = "0", ext = "txt", source = "{{ self.0 }}")]
+    A(u32),
+}
+
+#[derive(EnumTemplate)]
+enum Skipped {
+    #[template(display = "0", skip)]
+    A(u32),
+}
+
+#[derive(EnumTemplate)]
+enum Constants {
+    #[template(display = "0", meta_default)]
+    A(u32),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Undocumented {
+    #[template(doc, ext = "txt")]
+    Missing,
+}
+
+#[derive(EnumTemplate)]
+enum Both {
+    /// Hello
+    #[template(doc, ext = "txt", source = "Hi")]
+    Greeting,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", enforce_escape = "html")]
+enum Page {
+    #[template(source = "a")]
+    Fine,
+    #[template(source = "b", escape = "none")]
+    Unescaped,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", enforce_escape = "html")]
+enum Alternate {
+    #[template(source = "a", alternate(ext = "txt", escape = "none"))]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", escape = "none", source = "", enforce_escape = "html")]
+enum Default {
+    A,
+}
+
+fn main() {}
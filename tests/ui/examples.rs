@@ -0,0 +1,27 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(examples(strict))]
+enum Strict {
+    #[template(ext = "html", source = "{{ self.0 }}")]
+    Missing(u32),
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(examples)]
+enum Partial {
+    #[template(ext = "html", source = "{{ a }} {{ b }}")]
+    Fields {
+        #[template_field(example = "1")]
+        a: u32,
+        b: u32,
+    },
+}
+
+#[derive(EnumTemplate)]
+enum Disabled {
+    #[template(ext = "html", source = "{{ self.0 }}", example = "Self::A(1)")]
+    A(u32),
+}
+
+fn main() {}
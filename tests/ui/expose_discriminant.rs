@@ -0,0 +1,16 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ discriminant }}", expose_discriminant)]
+enum Collision {
+    A { discriminant: u32 },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", expose_discriminant)]
+enum Tuple {
+    A,
+    B(u32),
+}
+
+fn main() {}
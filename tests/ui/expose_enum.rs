@@ -0,0 +1,16 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ outer }}", expose_enum = "name")]
+enum Collision {
+    A { name: String },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", expose_enum = "outer")]
+enum Tuple {
+    A,
+    B(u32),
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum Twice {
+    #[template(source = "default")]
+    #[template(feature = "std", source = "a")]
+    #[template(feature = "std", source = "b")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum NoDefault {
+    #[template(feature = "std", source = "a")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum TwoDefaults {
+    #[template(source = "a")]
+    #[template(feature = "std", source = "b")]
+    #[template(source = "c")]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum UnknownField {
+    #[template(fmt = "user {nmae} not found")]
+    NotFound { name: String },
+}
+
+#[derive(EnumTemplate)]
+enum Positional {
+    #[template(fmt = "{}")]
+    A(u32),
+}
+
+#[derive(EnumTemplate)]
+enum OutOfRange {
+    #[template(fmt = "{0} {1}")]
+    A(u32),
+}
+
+#[derive(EnumTemplate)]
+enum Unmatched {
+    #[template(fmt = "{0")]
+    A(u32),
+}
+
+#[derive(EnumTemplate)]
+enum WithSource {
+    #[template(fmt = "{0}", ext = "txt", source = "{{ self.0 }}")]
+    A(u32),
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Twice {
+    #[template(ext = "txt", source = "a")]
+    #[template(format = "txt", ext = "txt", source = "b")]
+    #[template(format = "txt", ext = "txt", source = "c")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum TwoDefaults {
+    #[template(ext = "txt", source = "a")]
+    #[template(ext = "txt", source = "b")]
+    #[template(format = "txt", ext = "txt", source = "c")]
+    A,
+}
+
+fn main() {}
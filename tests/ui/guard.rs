@@ -0,0 +1,23 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum NoFallback {
+    #[template(if = "*_0", ext = "txt", source = "yes")]
+    A(bool),
+}
+
+#[derive(EnumTemplate)]
+enum FallbackFirst {
+    #[template(ext = "txt", source = "no")]
+    #[template(if = "*_0", ext = "txt", source = "yes")]
+    A(bool),
+}
+
+#[derive(EnumTemplate)]
+enum NotAnExpression {
+    #[template(if = "let", ext = "txt", source = "yes")]
+    #[template(ext = "txt", source = "no")]
+    A(bool),
+}
+
+fn main() {}
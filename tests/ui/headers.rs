@@ -0,0 +1,21 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum BadName {
+    #[template(ext = "txt", source = "", header("Cache Control" = "no-store"))]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum BadValue {
+    #[template(ext = "txt", source = "", header("X-Note" = "line\nbreak"))]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum BadSyntax {
+    #[template(ext = "txt", source = "", header(name = "X-Note"))]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use askama_enum::EnumTemplate;
+
+enum Theme {
+    Light,
+    Dark,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum MissingField {
+    #[template(key = "theme", arms(Light(source = "light"), _(source = "other")))]
+    A { mode: Theme },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum DuplicateArm {
+    #[template(
+        key = "theme",
+        arms(Light(source = "a"), Dark(source = "b"), Light(source = "c"), _(source = "d")),
+    )]
+    A { theme: Theme },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum NoDefault {
+    #[template(key = "theme", arms(Light(source = "a"), Dark(source = "b")))]
+    A { theme: Theme },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum NotAnEnum {
+    #[template(key = "0", arms(Light(source = "a"), _(source = "b")))]
+    A([Theme; 2]),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = "a", layout = true)]
+enum NotAPath {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = "a", slot = "content")]
+enum SlotOnly {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Missing {
+    #[template(like = "Nope")]
+    Gone,
+}
+
+#[derive(EnumTemplate)]
+enum Chained {
+    #[template(ext = "txt", source = "")]
+    A,
+    #[template(like = "A")]
+    B,
+    #[template(like = "B")]
+    C,
+}
+
+#[derive(EnumTemplate)]
+enum Fields {
+    #[template(ext = "txt", source = "{{ path }}")]
+    NotFound { path: String },
+    #[template(like = "NotFound")]
+    Gone { url: String },
+}
+
+fn main() {}
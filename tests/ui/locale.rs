@@ -0,0 +1,49 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum NoDefault {
+    #[template(locale = "en", ext = "txt", source = "a")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(default_locale = "en")]
+enum MissingDefault {
+    #[template(locale = "de", ext = "txt", source = "a")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(default_locale = "en")]
+enum Mixed {
+    #[template(locale = "en", ext = "txt", source = "a")]
+    #[template(ext = "txt", source = "b")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(default_locale = "en")]
+enum Twice {
+    #[template(locale = "en", ext = "txt", source = "a")]
+    #[template(locale = "en", ext = "txt", source = "b")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(locale = "en", ext = "txt", source = "a")]
+#[enum_template(default_locale = "en")]
+enum OnEnum {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(default_locale = "en")]
+enum Skipped {
+    #[template(locale = "en", skip)]
+    #[template(locale = "de", ext = "txt", source = "a")]
+    A,
+    #[template(ext = "txt", source = "b")]
+    B,
+}
+
+fn main() {}
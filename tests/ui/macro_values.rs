@@ -0,0 +1,21 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Unknown {
+    #[template(ext = "txt", source = format!("Hello, {}!", "world"))]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum Missing {
+    #[template(ext = "txt", source = concat!("Hello, ", include_str!("templates/missing.txt")))]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum NotALiteral {
+    #[template(ext = "txt", source = concat!("Hello, ", b"world"))]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use askama_enum::EnumTemplate;
+
+struct NotHash;
+
+impl std::fmt::Display for NotHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not hash")
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(memoize)]
+enum Badge {
+    #[template(ext = "txt", source = "{{self.0}}")]
+    Value(NotHash),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(memoize)]
+enum Twice {
+    #[template(ext = "txt", source = "{{ self.0 }}", memoize = "_0")]
+    A(u32),
+}
+
+#[derive(EnumTemplate)]
+enum InvalidKey {
+    #[template(ext = "txt", source = "{{ self.0 }}", memoize = "_0 +")]
+    A(u32),
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Twice {
+    #[template(ext = "txt", source = "a", meta_default)]
+    A,
+    #[template(ext = "txt", source = "b", meta_default)]
+    B,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "default", meta_default)]
+enum OnEnum {
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum Skipped {
+    #[template(skip, meta_default)]
+    A,
+    #[template(ext = "txt", source = "b")]
+    B,
+}
+
+fn main() {}
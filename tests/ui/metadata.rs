@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Duplicated {
+    #[template(ext = "txt", source = "", meta(team = "auth", team = "billing"))]
+    Variant,
+}
+
+#[derive(EnumTemplate)]
+enum NotAString {
+    #[template(ext = "txt", source = "", meta(priority = 1))]
+    Variant,
+}
+
+fn main() {}
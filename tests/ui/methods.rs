@@ -0,0 +1,25 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(byte_length))]
+enum Unknown {
+    #[template(ext = "txt", source = "")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(status, status))]
+enum Duplicated {
+    #[template(ext = "txt", source = "")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "")]
+enum NotUsed {
+    A,
+}
+
+fn main() {
+    let _ = NotUsed::A.status();
+}
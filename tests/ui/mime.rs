@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Response {
+    #[template(ext = "txt", source = "", mime = "problem json")]
+    Problem,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Text {
+    #[template(ext = "txt", minify, source = "plain")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", minify, source = "<p>page</p>")]
+enum Inherited {
+    A,
+    #[template(ext = "txt", source = "plain")]
+    B,
+}
+
+#[derive(EnumTemplate)]
+enum NoFlag {
+    #[template(ext = "html", minify = "yes", source = "<p>page</p>")]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html")]
+enum UnknownField<'a> {
+    #[template(source = "{{ title }}", output = "blog/{slug}/index.html")]
+    Post { title: &'a str },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "html")]
+enum Missing {
+    #[template(source = "home", output = "index.html")]
+    Home,
+    #[template(source = "about")]
+    About,
+}
+
+fn main() {}
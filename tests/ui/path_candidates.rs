@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Page {
+    #[template(path = ["themes/custom/missing.html", "themes/default/missing.html"])]
+    Missing,
+}
+
+fn main() {}
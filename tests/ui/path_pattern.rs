@@ -0,0 +1,21 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(path_pattern = "pages/{variant}.html")]
+enum MyEnum {
+    Settings,
+}
+
+#[derive(EnumTemplate)]
+#[template(path_pattern = "pages/{variant}.html", case = "camel")]
+enum UnknownCase {
+    Settings,
+}
+
+#[derive(EnumTemplate)]
+enum WithoutPattern {
+    #[template(ext = "txt", source = "", case = "snake")]
+    Settings,
+}
+
+fn main() {}
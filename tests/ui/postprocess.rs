@@ -0,0 +1,19 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", postprocess = "not a path")]
+enum NotAPath {
+    A,
+}
+
+fn wrong(_: &str) -> String {
+    String::new()
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", postprocess = "wrong")]
+enum WrongSignature {
+    A,
+}
+
+fn main() {}
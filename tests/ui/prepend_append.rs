@@ -0,0 +1,24 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "default")]
+enum OwnSource {
+    #[template(source = "own", prepend = "> ")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt")]
+enum NoDefault {
+    #[template(append = "!")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "default")]
+enum Both {
+    #[template(prepend = "> ", prepend_source = ">> ")]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "a", print = "tokens")]
+enum Unknown {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "a", print = "none")]
+enum Quiet {
+    A,
+}
+
+fn main() {
+    let _ = Quiet::A;
+}
@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum WithSource {
+    #[template(ext = "txt", source = "login", redirect = "/login")]
+    Login,
+}
+
+#[derive(EnumTemplate)]
+enum Whitespace {
+    #[template(redirect = "/log in")]
+    Login,
+}
+
+fn main() {}
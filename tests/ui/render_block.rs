@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum NoPath {
+    #[template(ext = "html", source = "page", blocks("header"))]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum Duplicate {
+    #[template(path = "blocks/inbox.html", blocks("header", "body", "header"))]
+    A { count: usize, subjects: Vec<String> },
+}
+
+fn main() {}
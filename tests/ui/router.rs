@@ -0,0 +1,25 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(router)]
+enum Pages<'a> {
+    #[template(ext = "html", source = "{{ name }}", route = "/greeting")]
+    Greeting { name: &'a str },
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(router)]
+enum Duplicates {
+    #[template(ext = "html", source = "A")]
+    AboutUs,
+    #[template(ext = "html", source = "B", route = "/about-us")]
+    About,
+}
+
+#[derive(EnumTemplate)]
+enum NoRouter {
+    #[template(ext = "html", source = "A", route = "/a")]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Page {
+    #[template(ext = "txt", source = "", size_hint = "8192")]
+    Text,
+}
+
+fn main() {}
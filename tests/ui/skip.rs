@@ -0,0 +1,9 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Row {
+    #[template(skip, ext = "txt", source = "x")]
+    Pending,
+}
+
+fn main() {}
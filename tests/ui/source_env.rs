@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Unset {
+    #[template(ext = "txt", source_env = "ASKAMA_ENUM_SURELY_NOT_SET")]
+    A,
+}
+
+#[derive(EnumTemplate)]
+enum Both {
+    #[template(ext = "txt", source = "", source_env = "CARGO_PKG_NAME")]
+    A,
+}
+
+fn main() {}
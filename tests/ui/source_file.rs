@@ -0,0 +1,15 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source_file = "tests/source_file/missing.txt")]
+enum Missing {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", source_file = "tests/source_file/farewell.txt")]
+enum Both {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", status = 99)]
+enum TooLow {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", status = 600)]
+enum TooHigh {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "-", status = "404")]
+enum NotANumber {
+    A,
+}
+
+fn main() {}
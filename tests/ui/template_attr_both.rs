@@ -0,0 +1,10 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+enum Message {
+    #[template(ext = "txt", source = "a")]
+    #[enum_template_attr(ext = "txt", source = "a")]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template_default(ext = "txt", source = "-")]
+enum NoShape {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template_default()]
+enum Empty {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template_default(unit(ext = "txt", source = "-"))]
+#[template_default(fields(ext = "txt", source = "+"))]
+enum Twice {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template_default(fields(ext = "txt", source = "{{ self.0 }}"))]
+enum MissingField {
+    A(u32),
+    B { value: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template_for(variants(A, Missing), ext = "txt", source = "a")]
+enum Unknown {
+    A,
+}
+
+#[derive(EnumTemplate)]
+#[template_for(variants(A, B), ext = "txt", source = "a")]
+#[template_for(variants(B), ext = "txt", source = "b")]
+enum Twice {
+    A,
+    B,
+}
+
+#[derive(EnumTemplate)]
+#[template_for(variants(A, B), ext = "txt", source = "a")]
+enum Conflict {
+    A,
+    #[template(ext = "txt", source = "b")]
+    B,
+}
+
+#[derive(EnumTemplate)]
+#[template_for(ext = "txt", source = "a")]
+enum NoList {
+    #[template(ext = "txt", source = "a")]
+    A,
+}
+
+fn main() {}
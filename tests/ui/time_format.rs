@@ -0,0 +1,13 @@
+use askama_enum::EnumTemplate;
+use time::OffsetDateTime;
+
+#[derive(EnumTemplate)]
+enum Event {
+    #[template(ext = "txt", source = "{{at}}")]
+    Created {
+        #[template_field(time_format = "[yeer]")]
+        at: OffsetDateTime,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(untagged(value = "inner"))]
+#[template(ext = "txt", source = "{{inner}}")]
+enum Length {
+    Cm(f64),
+    Px(u32),
+}
+
+fn main() {}
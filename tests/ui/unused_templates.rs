@@ -0,0 +1,10 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(check_unused_templates = "missing")]
+enum Mail {
+    #[template(ext = "txt", source = "Welcome!")]
+    Welcome,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ variant_name }}")]
+#[enum_template(variant_name)]
+enum Collision {
+    A { variant_name: String },
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ kind }}")]
+#[enum_template(variant_name = "kind", compute(kind = "1"))]
+enum Twice {
+    A,
+}
+
+fn main() {}
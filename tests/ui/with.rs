@@ -0,0 +1,22 @@
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(Template)]
+#[template(ext = "txt", source = "{{ name }}")]
+struct Greeting<'a> {
+    name: &'a str,
+}
+
+#[derive(EnumTemplate)]
+enum Missing<'a> {
+    #[template(with = "Greeting")]
+    Hello { nick: &'a str },
+}
+
+#[derive(EnumTemplate)]
+enum Combined<'a> {
+    #[template(with = "Greeting", source = "hi")]
+    Hello { name: &'a str },
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = "<p>{{ self.0 }}</p>")]
+#[enum_template(unescaped)]
+enum Preview<'a> {
+    Paragraph(&'a str),
+    #[template(source = "<h1>{{ title }}</h1>")]
+    Title {
+        title: &'a str,
+    },
+    #[template(ext = "html", escape = "html", source = "<code>{{ code }}</code>")]
+    Code {
+        code: &'a str,
+    },
+    #[template(
+        cfg(all(), source = "<i>{{ self.0 }}</i>"),
+        cfg(any(), source = "never")
+    )]
+    Italic(&'a str),
+    #[template(ext = "txt", source = "{{ self.0 }}")]
+    Text(&'a str),
+}
+
+#[test]
+fn test() {
+    let paragraph = Preview::Paragraph("<b>bold</b>");
+    assert_eq!(
+        paragraph.render().unwrap(),
+        "<p>&lt;b&gt;bold&lt;/b&gt;</p>"
+    );
+    assert_eq!(paragraph.render_unescaped().unwrap(), "<p><b>bold</b></p>");
+
+    let title = Preview::Title { title: "A & B" };
+    assert_eq!(title.render().unwrap(), "<h1>A &amp; B</h1>");
+    assert_eq!(title.render_unescaped().unwrap(), "<h1>A & B</h1>");
+
+    let code = Preview::Code { code: "<br>" };
+    assert_eq!(code.render().unwrap(), "<code>&lt;br&gt;</code>");
+    assert_eq!(code.render_unescaped().unwrap(), "<code>&lt;br&gt;</code>");
+
+    let italic = Preview::Italic("<u>");
+    assert_eq!(italic.render().unwrap(), "<i>&lt;u&gt;</i>");
+    assert_eq!(italic.render_unescaped().unwrap(), "<i><u></i>");
+
+    let text = Preview::Text("<raw>");
+    assert_eq!(text.render_unescaped().unwrap(), text.render().unwrap());
+}
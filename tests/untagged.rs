@@ -0,0 +1,36 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(untagged(value = "inner"))]
+#[template(ext = "txt", source = "{{inner}} {{__variant}}")]
+enum Length {
+    Cm(f64),
+    Inch(f64),
+    Pt(f64),
+    #[template(ext = "txt", source = "{{self.0}} mm")]
+    Mm(u32),
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(untagged(value = "value"))]
+#[template(ext = "html", source = "<{{__variant}}>{{value}}</{{__variant}}>")]
+enum Tag<'a, T: std::fmt::Display> {
+    B(&'a T),
+    I(&'a T),
+    #[template(ext = "html", source = "<br>")]
+    Br,
+}
+
+#[test]
+fn test() {
+    assert_eq!(Length::Cm(1.5).to_string(), "1.5 Cm");
+    assert_eq!(Length::Inch(2.0).to_string(), "2 Inch");
+    assert_eq!(Length::Pt(12.0).to_string(), "12 Pt");
+    assert_eq!(Length::Mm(7).to_string(), "7 mm");
+
+    assert_eq!(Tag::B(&"<bold>").to_string(), "<B>&lt;bold&gt;</B>");
+    assert_eq!(Tag::I(&123).to_string(), "<I>123</I>");
+    assert_eq!(Tag::Br::<u8>.to_string(), "<br>");
+}
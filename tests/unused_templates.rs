@@ -0,0 +1,23 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+
+// `templates/mails/orphan.txt` is not used by any variant.
+#[derive(EnumTemplate)]
+#[enum_template(check_unused_templates(dir = "mails", ignore("orphan.txt")))]
+enum Mail<'a> {
+    #[template(path = "mails/welcome.txt")]
+    Welcome { name: &'a str },
+    #[template(
+        cfg(debug_assertions, path = "mails/goodbye.txt"),
+        cfg(not(debug_assertions), path = "mails/partials/signature.txt")
+    )]
+    Goodbye { name: &'a str },
+}
+
+#[test]
+fn test() {
+    assert_eq!(Mail::Welcome { name: "Ann" }.to_string(), "Welcome, Ann!");
+    let goodbye = Mail::Goodbye { name: "Ann" }.to_string();
+    assert!(goodbye == "Goodbye, Ann!" || goodbye == "Ann");
+}
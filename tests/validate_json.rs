@@ -0,0 +1,47 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(validate_json)]
+enum Response<'a> {
+    #[template(
+        ext = "json",
+        escape = "none",
+        source = r#"{"name": "{{name}}", "tags": [{% for tag in tags %}"{{tag}}"{% if !loop.last %}, {% endif %}{% endfor %}]}"#
+    )]
+    Valid { name: &'a str, tags: &'a [&'a str] },
+    #[template(
+        ext = "json",
+        escape = "none",
+        source = r#"{"name": "{{name}}", "tags": [{% for tag in tags %}"{{tag}}", {% endfor %}]}"#
+    )]
+    TrailingComma { name: &'a str, tags: &'a [&'a str] },
+    #[template(ext = "txt", source = "{not json")]
+    Text,
+}
+
+#[test]
+fn test() {
+    let json = Response::Valid {
+        name: "x",
+        tags: &["a", "b"],
+    }
+    .render()
+    .unwrap();
+    assert_eq!(json, r#"{"name": "x", "tags": ["a", "b"]}"#);
+
+    assert_eq!(Response::Text.render().unwrap(), "{not json");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "rendering Response::TrailingComma produced invalid JSON")]
+fn test_invalid() {
+    let _ = Response::TrailingComma {
+        name: "x",
+        tags: &["a", "b"],
+    }
+    .render();
+}
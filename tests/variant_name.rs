@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[template(ext = "html", source = r#"<div class="{{ variant_name }}"></div>"#)]
+#[enum_template(variant_name)]
+enum Alert<'a> {
+    Info,
+    Warning,
+    #[template(source = "{{ variant_name }}: {{ message }}")]
+    Error {
+        message: &'a str,
+    },
+    r#Static,
+}
+
+#[derive(EnumTemplate)]
+#[template(ext = "txt", source = "{{ kind }}({{ inner }})")]
+#[enum_template(variant_name = "kind", untagged(value = "inner"))]
+enum Value {
+    Small(i64),
+    Large(i64),
+}
+
+#[test]
+fn test() {
+    assert_eq!(Alert::Info.render().unwrap(), r#"<div class="Info"></div>"#);
+    assert_eq!(
+        Alert::Warning.render().unwrap(),
+        r#"<div class="Warning"></div>"#
+    );
+    assert_eq!(
+        Alert::Error { message: "oops" }.render().unwrap(),
+        "Error: oops"
+    );
+    assert_eq!(
+        Alert::Static.render().unwrap(),
+        r#"<div class="Static"></div>"#
+    );
+
+    assert_eq!(Value::Small(1).render().unwrap(), "Small(1)");
+    assert_eq!(Value::Large(1000).render().unwrap(), "Large(1000)");
+}
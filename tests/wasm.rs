@@ -0,0 +1,22 @@
+#![cfg(feature = "testing")]
+
+// Calling `render_js()` needs a JavaScript host, so the native test only checks the signature.
+// `testing/wasm` is built for `wasm32-unknown-unknown` in the CI.
+
+use askama_enum::EnumTemplate;
+use js_sys::JsString;
+use wasm_bindgen::JsValue;
+
+#[derive(EnumTemplate)]
+#[enum_template(wasm)]
+enum Page<'a> {
+    #[template(ext = "html", source = "<p>{{self.0}}</p>")]
+    Text(&'a str),
+}
+
+#[test]
+fn test() {
+    let render_js: fn(&Page<'static>) -> Result<JsString, JsValue> = Page::render_js;
+    let _ = render_js;
+    assert_eq!(Page::Text("x").to_string(), "<p>x</p>");
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "testing")]
+
+use askama::Template;
+use askama_enum::EnumTemplate;
+
+struct Money(u32);
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${}.{:02}", self.0 / 100, self.0 % 100)
+    }
+}
+
+#[derive(Template)]
+#[template(ext = "html", source = "<p>{{ customer }} owes {{ total }}</p>")]
+struct InvoiceMail<'a> {
+    total: &'a Money,
+    customer: &'a str,
+}
+
+#[derive(Template)]
+#[template(ext = "txt", source = "{{ self.0 }} of {{ self.1 }}")]
+struct Progress<'a>(&'a u8, &'a u8);
+
+#[derive(Template)]
+#[template(ext = "txt", source = "goodbye")]
+struct Farewell;
+
+#[derive(EnumTemplate)]
+#[enum_template(methods(mime_type))]
+enum Mail<'a> {
+    #[template(with = "InvoiceMail")]
+    Invoice { customer: &'a str, total: Money },
+    #[template(with = "Progress")]
+    Progress(u8, u8),
+    #[template(with = "Farewell")]
+    Farewell,
+}
+
+#[test]
+fn test() {
+    let invoice = Mail::Invoice {
+        customer: "<Ann>",
+        total: Money(12345),
+    };
+    assert_eq!(invoice.render().unwrap(), "<p>&lt;Ann&gt; owes $123.45</p>");
+    assert_eq!(invoice.to_string(), invoice.render().unwrap());
+    assert_eq!(invoice.mime_type(), "text/html; charset=utf-8");
+    assert_eq!(Mail::Progress(3, 7).render().unwrap(), "3 of 7");
+    assert_eq!(
+        Mail::Progress(3, 7).mime_type(),
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(Mail::Farewell.render().unwrap(), "goodbye");
+    assert_eq!(Mail::EXTENSION, Some("html"));
+
+    let mail = InvoiceMail {
+        total: &Money(5),
+        customer: "Bob",
+    };
+    assert_eq!(mail.render().unwrap(), "<p>Bob owes $0.05</p>");
+}
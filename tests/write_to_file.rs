@@ -0,0 +1,62 @@
+#![cfg(feature = "testing")]
+
+use std::error::Error;
+use std::fs::read_to_string;
+
+use askama_enum::EnumTemplate;
+
+mod filters {
+    pub fn fail<T>(_: T) -> askama::Result<String> {
+        Err(askama::Error::Fmt(std::fmt::Error))
+    }
+}
+
+#[derive(EnumTemplate)]
+#[enum_template(write_to_file)]
+enum Report<'a> {
+    #[template(ext = "txt", source = "Hello, {{self.0}}!")]
+    Hello(&'a str),
+    #[template(ext = "txt", source = "before {{self.0|fail}} after")]
+    Broken(&'a str),
+}
+
+#[test]
+fn test_success() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hello.txt");
+    Report::Hello("world").write_to_file(&path).unwrap();
+    assert_eq!(read_to_string(&path).unwrap(), "Hello, world!");
+}
+
+#[test]
+fn test_missing_parent() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir
+        .path()
+        .join("some")
+        .join("sub")
+        .join("dir")
+        .join("hello.txt");
+    Report::Hello("world").write_to_file(&path).unwrap();
+    assert_eq!(read_to_string(&path).unwrap(), "Hello, world!");
+}
+
+#[test]
+fn test_render_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broken.txt");
+    let err = Report::Broken("x").write_to_file(&path).unwrap_err();
+    assert!(matches!(err, ReportWriteError::Render(_)));
+    assert_eq!(err.to_string(), "could not render template");
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn test_io_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = Report::Hello("world")
+        .write_to_file(dir.path())
+        .unwrap_err();
+    assert!(matches!(err, ReportWriteError::Io(_)));
+    assert_eq!(err.to_string(), "could not write file");
+}
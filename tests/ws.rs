@@ -0,0 +1,43 @@
+#![cfg(feature = "testing")]
+
+use askama_enum::EnumTemplate;
+
+#[derive(EnumTemplate)]
+#[enum_template(ws_axum, ws_tungstenite)]
+enum Fragment<'a> {
+    #[template(ext = "html", source = "<p>{{self.0}}</p>")]
+    Paragraph(&'a str),
+    #[template(ext = "txt", source = "ping")]
+    Ping,
+}
+
+#[test]
+fn test_axum() {
+    use axum::extract::ws::Message;
+
+    match Fragment::Paragraph("<hi>").to_axum_ws_message().unwrap() {
+        Message::Text(text) => assert_eq!(text.as_str(), "<p>&lt;hi&gt;</p>"),
+        msg => panic!("expected a text message, got {:?}", msg),
+    }
+    match Fragment::Ping.to_axum_ws_message().unwrap() {
+        Message::Text(text) => assert_eq!(text.as_str(), "ping"),
+        msg => panic!("expected a text message, got {:?}", msg),
+    }
+}
+
+#[test]
+fn test_tungstenite() {
+    use tokio_tungstenite::tungstenite::Message;
+
+    match Fragment::Paragraph("<hi>")
+        .to_tungstenite_message()
+        .unwrap()
+    {
+        Message::Text(text) => assert_eq!(text.as_str(), "<p>&lt;hi&gt;</p>"),
+        msg => panic!("expected a text message, got {:?}", msg),
+    }
+    match Fragment::Ping.to_tungstenite_message().unwrap() {
+        Message::Text(text) => assert_eq!(text.as_str(), "ping"),
+        msg => panic!("expected a text message, got {:?}", msg),
+    }
+}